@@ -515,6 +515,7 @@ impl System {
                             ui.checkbox("Sleep", &mut s_settings.m_enable_sleep);
                             ui.checkbox("Warm Starting", &mut s_settings.m_enable_warm_starting);
                             ui.checkbox("Time of Impact", &mut s_settings.m_enable_continuous);
+                            ui.checkbox("Speculative Contacts", &mut s_settings.m_enable_speculative);
                             ui.checkbox("Sub-Stepping", &mut s_settings.m_enable_sub_stepping);
      
                             ui.separator();
@@ -26,6 +26,7 @@ impl Default for Settings {
 			m_draw_profile: false,
 			m_enable_warm_starting: true,
 			m_enable_continuous: true,
+			m_enable_speculative: false,
 			m_enable_sub_stepping: false,
 			m_enable_sleep: true,
 			m_pause: false,
@@ -70,6 +71,7 @@ pub(crate) struct Settings {
 	pub(crate) m_draw_profile: bool,
 	pub(crate) m_enable_warm_starting: bool,
 	pub(crate) m_enable_continuous: bool,
+	pub(crate) m_enable_speculative: bool,
 	pub(crate) m_enable_sub_stepping: bool,
 	pub(crate) m_enable_sleep: bool,
 	pub(crate) m_pause: bool,
@@ -297,6 +297,7 @@ pub(crate) fn step<D: UserDataType, F: Facade>(
 		m_world.set_allow_sleeping(settings.m_enable_sleep);
 		m_world.set_warm_starting(settings.m_enable_warm_starting);
 		m_world.set_continuous_physics(settings.m_enable_continuous);
+		m_world.set_speculative_contacts(settings.m_enable_speculative);
 		m_world.set_sub_stepping(settings.m_enable_sub_stepping);
 
 		m_world.step(
@@ -158,7 +158,7 @@ impl<D: UserDataType, F: Facade> TestDyn<D, F> for Confined<D> {
 		let m_world = self.base.borrow().m_world.clone();
 
 		let mut sleeping: bool = true;
-		for b in m_world.borrow_mut().get_body_list().iter()
+		for b in m_world.borrow().bodies()
 		{
 			let b = b.borrow_mut();
 			if b.get_type() != B2bodyType::B2DynamicBody
@@ -184,7 +184,7 @@ impl<D: UserDataType, F: Facade> TestDyn<D, F> for Confined<D> {
 
 		Test::step(self.base.clone(), ui, display, target, settings, *camera);
 
-		for b in m_world.borrow_mut().get_body_list().iter()
+		for b in m_world.borrow().bodies()
 		{
 			let b = b.borrow_mut();
 			if b.get_type() != B2bodyType::B2DynamicBody
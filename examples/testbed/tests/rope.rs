@@ -1,8 +1,11 @@
 use super::super::draw::*;
 use super::super::settings::*;
 use super::super::test::*;
+use box2d_rs::b2_body::*;
 use box2d_rs::b2_math::*;
 use box2d_rs::b2_rope::*;
+use box2d_rs::b2_world::*;
+use box2d_rs::shapes::b2_polygon_shape::*;
 use box2d_rs::b2rs_common::UserDataType;
 use box2d_rs::b2_world_callbacks::*;
 
@@ -25,6 +28,7 @@ pub(crate) struct Rope<D: UserDataType> {
 	m_position1: B2vec2,
 	m_position2: B2vec2,
 	m_speed: f32,
+	m_cable: Option<B2ropePtr<D>>,
 
 	left_pressed: bool,
 	right_pressed: bool,
@@ -51,6 +55,7 @@ impl<D: UserDataType> Rope<D> {
 			m_position1: Default::default(),
 			m_position2: Default::default(),
 			m_speed: Default::default(),
+			m_cable: None,
 
 			left_pressed: false,
 			right_pressed: false,
@@ -123,6 +128,37 @@ impl<D: UserDataType> Rope<D> {
 		self.m_iterations1 = 8;
 		self.m_iterations2 = 8;
 
+		// A cable stepped by the world. The crate swings down and hits a
+		// ledge, the cable wraps around it.
+		{
+			let world = self.base.borrow().m_world.clone();
+			let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+			let mut shape = B2polygonShape::default();
+			shape.set_as_box_angle(2.0, 0.25, B2vec2::new(18.0, 6.0), 0.0);
+			B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(shape)), 0.0);
+
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			body_def.position.set(15.0 + l * (N - 1) as f32, 15.0);
+			let crate_body = B2world::create_body(world.clone(), &body_def);
+			let mut shape = B2polygonShape::default();
+			shape.set_as_box(0.5, 0.5);
+			B2body::create_fixture_by_shape(crate_body.clone(), Rc::new(RefCell::new(shape)), 1.0);
+
+			let mut def = B2ropeDef::default();
+			for i in 0..N {
+				def.vertices.push(B2ropeDefVertices {
+					position: B2vec2::new(15.0 + l * i as f32, 15.0),
+					mass: 0.1,
+				});
+			}
+			def.tuning = self.m_tuning2;
+			let rope = world.borrow_mut().create_rope(&def);
+			rope.borrow_mut().attach(0, ground, B2vec2::new(15.0, 15.0));
+			rope.borrow_mut().attach(N - 1, crate_body, B2vec2::new(15.0 + l * (N - 1) as f32, 15.0));
+			self.m_cable = Some(rope);
+		}
+
 		self.m_speed = 10.0;
 	}
 }
@@ -330,6 +366,23 @@ impl<D: UserDataType, F: Facade> TestDyn<D, F> for Rope<D> {
 					self.m_rope1.reset(self.m_position1);
 					self.m_rope2.reset(self.m_position2);
 				}
+
+				ui.separator();
+
+				if let Some(cable) = self.m_cable.clone() {
+					if ui.button("Reel In") {
+						cable.borrow_mut().reel(-0.5);
+					}
+					if ui.button("Reel Out") {
+						cable.borrow_mut().reel(0.5);
+					}
+					if ui.button("Cut Cable") {
+						let world = self.base.borrow().m_world.clone();
+						let segment: usize = cable.borrow().get_rope().get_segment_count() / 2;
+						world.borrow_mut().cut_rope(&cable, segment);
+						self.m_cable = None;
+					}
+				}
 				
 				//TODO_humman fix
 				//width_token.pop(ui);
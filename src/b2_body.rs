@@ -4,6 +4,7 @@ use std::rc::{Rc, Weak};
 #[cfg(feature="serde_support")]
 use serde::{Serialize, Deserialize};
 
+use crate::b2_common::*;
 use crate::b2_contact::*;
 use crate::b2_fixture::*;
 use crate::b2_joint::*;
@@ -15,6 +16,7 @@ use crate::private::dynamics::b2_body as private;
 
 use crate::b2rs_linked_list::*;
 use crate::b2rs_double_linked_list::*;
+use crate::b2rs_iterators::*;
 
 use bitflags::bitflags;
 
@@ -49,6 +51,10 @@ impl<D: UserDataType> Default for B2bodyDef<D> {
 			allow_sleep: true,
 			awake: true,
 			fixed_rotation: false,
+			fixed_translation_x: false,
+			fixed_translation_y: false,
+			max_linear_speed: B2_MAX_FLOAT,
+			max_angular_speed: B2_MAX_FLOAT,
 			bullet: false,
 			body_type: B2bodyType::B2StaticBody,
 			enabled: true,
@@ -128,6 +134,26 @@ pub struct B2bodyDef<D: UserDataType> {
 	/// Should this body be prevented from rotating? Useful for characters.
 	pub fixed_rotation: bool,
 
+	/// Should the center of mass be prevented from moving along the world x axis?
+	/// Useful for elevators.
+	#[cfg_attr(feature = "serde_support", serde(default))]
+	pub fixed_translation_x: bool,
+
+	/// Should the center of mass be prevented from moving along the world y axis?
+	/// Useful for sliders.
+	#[cfg_attr(feature = "serde_support", serde(default))]
+	pub fixed_translation_y: bool,
+
+	/// The maximum linear speed of this body, usually in m/s. The global
+	/// B2_MAX_TRANSLATION limit per step still applies.
+	#[cfg_attr(feature = "serde_support", serde(default = "default_max_speed"))]
+	pub max_linear_speed: f32,
+
+	/// The maximum angular speed of this body, usually in radians per second. The global
+	/// B2_MAX_ROTATION limit per step still applies.
+	#[cfg_attr(feature = "serde_support", serde(default = "default_max_speed"))]
+	pub max_angular_speed: f32,
+
 	/// Is this a fast moving body that should be prevented from tunneling through
 	/// other moving bodies? Note that all bodies are prevented from tunneling through
 	/// kinematic and static bodies. This setting is only considered on dynamic bodies.
@@ -146,6 +172,14 @@ pub struct B2bodyDef<D: UserDataType> {
 	pub gravity_scale: f32,
 }
 
+// Self-describing saves, such as JSON, from before the speed limits existed
+// load without a limit. Binary saves of that time are version 0 worlds, which
+// read their bodies with the old layout.
+#[cfg(feature = "serde_support")]
+fn default_max_speed() -> f32 {
+	return B2_MAX_FLOAT;
+}
+
 pub type BodyPtr<D> = Rc<RefCell<B2body<D>>>;
 pub type BodyWeakPtr<D> = Weak<RefCell<B2body<D>>>;
 
@@ -192,6 +226,12 @@ pub struct B2body<D: UserDataType>
 	pub(crate) m_angular_damping: f32,
 	pub(crate) m_gravity_scale: f32,
 
+	pub(crate) m_max_linear_speed: f32,
+	pub(crate) m_max_angular_speed: f32,
+
+	/// The center of mass and angle requested by set_target_transform for the next step.
+	pub(crate) m_target_sweep: Option<(B2vec2, f32)>,
+
 	pub(crate) m_sleep_time: f32,
 
 	pub(crate) m_user_data: Option<D::Body>,
@@ -217,6 +257,9 @@ bitflags! {
 		const E_FIXED_ROTATION_FLAG	= 0x0010;
 		const E_ENABLED_FLAG		= 0x0020;
 		const E_TOI_FLAG			= 0x0040;
+		const E_FIXED_TRANSLATION_X_FLAG	= 0x0080;
+		const E_FIXED_TRANSLATION_Y_FLAG	= 0x0100;
+		const E_SNAP_TO_TARGET_FLAG	= 0x0200;
 	}
 }
 
@@ -326,6 +369,26 @@ impl<D: UserDataType> B2body<D> {
 		return inline::get_angular_velocity(self);
 	}
 
+	/// Set the velocities that move the body to the target transform of its origin by the
+	/// end of the next step. The angle is wrapped so the body turns the short way around.
+	/// This is meant for kinematic bodies driven by animation. Call it before every step.
+	/// * `target` - the transform the body origin should reach.
+	/// * `dt` - the time step that will be passed to B2world::step.
+	pub fn set_target_transform(&mut self, target: B2Transform, dt: f32) {
+		private::set_target_transform(self, target, dt);
+	}
+
+	/// Should the body snap exactly to the target of set_target_transform when it arrives?
+	/// This removes the small drift left by integration.
+	pub fn set_snap_to_target(&mut self, flag: bool) {
+		self.m_flags.set(BodyFlags::E_SNAP_TO_TARGET_FLAG, flag);
+	}
+
+	/// Does the body snap to the target of set_target_transform?
+	pub fn is_snap_to_target(&self) -> bool {
+		return self.m_flags.contains(BodyFlags::E_SNAP_TO_TARGET_FLAG);
+	}
+
 	/// Apply a force at a world point. If the force is not
 	/// applied at the center of mass, it will generate a torque and
 	/// affect the angular velocity. This wakes up the body.
@@ -567,6 +630,48 @@ impl<D: UserDataType> B2body<D> {
 		return inline::is_fixed_rotation(self);
 	}
 
+	/// Prevent the center of mass of this body from moving along the world x axis.
+	pub fn set_fixed_translation_x(&mut self, flag: bool) {
+		private::set_fixed_translation_x(self, flag);
+	}
+
+	/// Is the translation of this body along the world x axis locked?
+	pub fn is_fixed_translation_x(&self) -> bool {
+		return inline::is_fixed_translation_x(self);
+	}
+
+	/// Prevent the center of mass of this body from moving along the world y axis.
+	pub fn set_fixed_translation_y(&mut self, flag: bool) {
+		private::set_fixed_translation_y(self, flag);
+	}
+
+	/// Is the translation of this body along the world y axis locked?
+	pub fn is_fixed_translation_y(&self) -> bool {
+		return inline::is_fixed_translation_y(self);
+	}
+
+	/// Set the maximum linear speed of this body, usually in m/s.
+	pub fn set_max_linear_speed(&mut self, speed: f32) {
+		b2_assert(speed >= 0.0);
+		self.m_max_linear_speed = speed;
+	}
+
+	/// Get the maximum linear speed of this body, usually in m/s.
+	pub fn get_max_linear_speed(&self) -> f32 {
+		return self.m_max_linear_speed;
+	}
+
+	/// Set the maximum angular speed of this body, usually in radians per second.
+	pub fn set_max_angular_speed(&mut self, speed: f32) {
+		b2_assert(speed >= 0.0);
+		self.m_max_angular_speed = speed;
+	}
+
+	/// Get the maximum angular speed of this body, usually in radians per second.
+	pub fn get_max_angular_speed(&self) -> f32 {
+		return self.m_max_angular_speed;
+	}
+
 	/// Get the list of all fixtures attached to this body.
 	pub fn get_fixture_list(&self) -> &LinkedList<B2fixture<D>> {
 		return inline::get_fixture_list(self);
@@ -595,6 +700,26 @@ impl<D: UserDataType> B2body<D> {
 	// 	return inline::get_contact_list_mut(self);
 	// }
 
+	/// Iterate over the fixtures attached to this body.
+	pub fn fixtures(&self) -> B2fixtureIter<D> {
+		return B2fixtureIter { inner: self.m_fixture_list.iter() };
+	}
+
+	/// Iterate over the joints attached to this body. Each item is the joint and
+	/// the other body it is connected to.
+	pub fn joints(&self) -> B2bodyJointIter<D> {
+		return B2bodyJointIter { inner: self.m_joint_list.iter() };
+	}
+
+	/// Iterate over the touching contacts of this body. Each item is the contact and
+	/// the other body.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> like get_contact_list, this changes during the time step.
+	/// </p>
+	pub fn contacts(&self) -> B2bodyContactIter<D> {
+		return B2bodyContactIter { inner: self.m_contact_list.iter() };
+	}
+
 	/// Get the next body in the world's body list.
 	pub fn get_next(&self) -> Option<BodyPtr<D>> {
 		return inline::get_next(self);
@@ -812,6 +937,14 @@ mod inline {
 		return self_.m_flags.contains(BodyFlags::E_FIXED_ROTATION_FLAG);
 	}
 
+	pub fn is_fixed_translation_x<D: UserDataType>(self_: &B2body<D>) -> bool {
+		return self_.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_X_FLAG);
+	}
+
+	pub fn is_fixed_translation_y<D: UserDataType>(self_: &B2body<D>) -> bool {
+		return self_.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_Y_FLAG);
+	}
+
 	pub fn set_sleeping_allowed<D: UserDataType>(self_: &mut B2body<D>, flag: bool) {
 		self_.m_flags.set(BodyFlags::E_AUTO_SLEEP_FLAG, flag);
 		if flag {
@@ -263,7 +263,7 @@ pub fn b2_collide_circles(
     circle_b: &B2circleShape,
     xf_b: &B2Transform,
 ) {
-    private::b2_collide_circle::b2_collide_circles(manifold, circle_a, xf_a, circle_b, xf_b);
+    private::b2_collide_circle::b2_collide_circles(manifold, circle_a, xf_a, circle_b, xf_b, 0.0);
 }
 
 /// Compute the collision manifold between a polygon and a circle.
@@ -275,7 +275,7 @@ pub fn b2_collide_polygon_and_circle(
     xf_b: &B2Transform,
 ) {
     private::b2_collide_circle::b2_collide_polygon_and_circle(
-        manifold, polygon_a, xf_a, circle_b, xf_b,
+        manifold, polygon_a, xf_a, circle_b, xf_b, 0.0,
     );
 }
 
@@ -288,7 +288,7 @@ pub fn b2_collide_polygons(
     xf_b: &B2Transform,
 ) {
     private::b2_collide_polygon::b2_collide_polygons(
-        manifold, *polygon_a, *xf_a, *polygon_b, *xf_b,
+        manifold, *polygon_a, *xf_a, *polygon_b, *xf_b, 0.0,
     );
 }
 
@@ -300,7 +300,7 @@ pub fn b2_collide_edge_and_circle(
     circle_b: &B2circleShape,
     xf_b: &B2Transform,
 ) {
-    private::b2_collide_edge::b2_collide_edge_and_circle(manifold, edge_a, xf_a, circle_b, xf_b);
+    private::b2_collide_edge::b2_collide_edge_and_circle(manifold, edge_a, xf_a, circle_b, xf_b, 0.0);
 }
 
 /// Compute the collision manifold between an edge and a polygon.
@@ -311,7 +311,23 @@ pub fn b2_collide_edge_and_polygon(
     polygon_b: &B2polygonShape,
     xf_b: &B2Transform,
 ) {
-    private::b2_collide_edge::b2_collide_edge_and_polygon(manifold, edge_a, xf_a, polygon_b, xf_b);
+    private::b2_collide_edge::b2_collide_edge_and_polygon(manifold, edge_a, xf_a, polygon_b, xf_b, 0.0);
+}
+
+/// Compute the collision manifold between two convex child shapes using GJK.
+/// Works for any pair of shapes and is how custom shapes collide.
+pub fn b2_collide_convex(
+    manifold: &mut B2manifold,
+    shape_a: &dyn B2shapeDynTrait,
+    index_a: usize,
+    xf_a: &B2Transform,
+    shape_b: &dyn B2shapeDynTrait,
+    index_b: usize,
+    xf_b: &B2Transform,
+) {
+    private::b2_collide_custom::b2_collide_convex(
+        manifold, shape_a, index_a, *xf_a, shape_b, index_b, *xf_b, 0.0,
+    );
 }
 
 // /// Clipping for contact manifolds.
@@ -273,10 +273,11 @@ impl<D: UserDataType> B2contact<D> {
 	pub(crate) fn update(
 		self_dyn: &mut dyn B2contactDynTrait<D>,
 		listener: Option<B2contactListenerPtr<D>>,
+		speculative_time: f32,
 	) where
 		Self: Sized,
 	{
-		private::b2_contact_update(self_dyn, listener);
+		private::b2_contact_update(self_dyn, listener, speculative_time);
 	}
 }
 
@@ -301,6 +302,9 @@ bitflags! {
 
 		// This contact has a valid TOI in m_toi
 		const E_TOI_FLAG			= 0x0020;
+
+		// This contact with a one-way fixture was accepted as solid
+		const E_ONE_WAY_SOLID_FLAG	= 0x0040;
 	}
 }
 
@@ -336,6 +340,9 @@ pub struct B2contact<D: UserDataType> {
 	pub(crate) m_restitution_threshold: f32,
 
 	pub(crate) m_tangent_speed: f32,
+
+	// Manifold points are generated up to this distance apart.
+	pub(crate) m_speculative_distance: f32,
 }
 
 mod inline {
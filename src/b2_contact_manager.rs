@@ -29,6 +29,7 @@ impl<D: UserDataType> B2contactManager<D> {
 			m_contact_count: 0,
 			m_contact_filter: Some(Rc::new(RefCell::new(B2contactFilterDefault{}))),
 			m_contact_listener: Default::default(),
+			m_speculative_time: 0.0,
 		};
 	}
 
@@ -71,6 +72,8 @@ pub struct B2contactManager<D: UserDataType> {
 	pub(crate) m_contact_count: usize,
 	pub(crate) m_contact_filter: Option<B2contactFilterPtr<D>>,
 	pub(crate) m_contact_listener: Option<B2contactListenerPtr<D>>,
+	// The time step used to size speculative contacts, zero when they are disabled.
+	pub(crate) m_speculative_time: f32,
 }
 
 impl<D: UserDataType> Drop for B2contactManager<D>
@@ -24,7 +24,7 @@ impl B2distanceProxy {
     /// initialize the proxy using the given shape. The shape
     /// must remain in scope while the proxy is in use.
     pub fn set_shape(&mut self, shape: ShapePtr, index: usize) {
-        private::set_shape(self, shape, index);
+        private::set_shape(self, &*shape, index);
     }
 
     /// initialize the proxy using a vertex cloud and radius. The vertices
@@ -53,6 +53,8 @@ impl<D: UserDataType> Default for B2fixtureDef<D> {
 			density: 0.0,
 			is_sensor: false,
 			filter: B2filter::default(),
+			one_way_direction: None,
+			toughness: 0.0,
 		};
 	}
 }
@@ -87,6 +89,32 @@ pub struct B2fixtureDef<D: UserDataType> {
 
 	/// Contact filtering data.
 	pub filter: B2filter,
+
+	/// Makes this fixture one-way, for example a platform that can be jumped through from
+	/// below. This is the direction the solid side faces, in body coordinates. Other fixtures
+	/// only collide when they are on that side and not moving away from it. The direction
+	/// rotates and moves with the body.
+	pub one_way_direction: Option<B2vec2>,
+
+	/// The impulse that breaks a polygon fixture into pieces when it is hit, in N*s.
+	/// Zero makes the fixture unbreakable. See B2fractureListener.
+	pub toughness: f32,
+}
+
+/// Closest points between a fixture and another fixture or a point.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct B2fixtureDistanceOutput {
+	/// closest point on the first fixture, in world coordinates
+	pub point_a: B2vec2,
+	/// closest point on the second fixture or the query point, in world coordinates
+	pub point_b: B2vec2,
+	/// unit vector pointing from point_a to point_b. Zero when the shapes overlap.
+	pub normal: B2vec2,
+	pub distance: f32,
+	/// child of the first fixture holding point_a (e.g. chain edge index)
+	pub child_index_a: usize,
+	/// child of the second fixture holding point_b
+	pub child_index_b: usize,
 }
 
 pub type FixturePtr<D> = Rc<RefCell<B2fixture<D>>>;
@@ -143,6 +171,10 @@ pub struct B2fixture<D: UserDataType> {
 
 	pub(crate) m_is_sensor: bool,
 
+	pub(crate) m_one_way_direction: Option<B2vec2>,
+
+	pub(crate) m_toughness: f32,
+
 	pub(crate) m_user_data: Option<D::Fixture>,
 }
 
@@ -173,6 +205,29 @@ impl<D: UserDataType> B2fixture<D> {
 		return inline::is_sensor(self);
 	}
 
+	/// Set the one-way direction in body coordinates, None makes the fixture collide
+	/// from all sides.
+	pub fn set_one_way_direction(&mut self, direction: Option<B2vec2>) {
+		private::b2_fixture_set_one_way_direction(self, direction);
+	}
+
+	/// Get the one-way direction in body coordinates.
+	pub fn get_one_way_direction(&self) -> Option<B2vec2> {
+		return self.m_one_way_direction;
+	}
+
+	/// Set the impulse that breaks this fixture, zero makes it unbreakable. Only
+	/// polygon fixtures break.
+	pub fn set_toughness(&mut self, toughness: f32) {
+		b2_assert(b2_is_valid(toughness) && toughness >= 0.0);
+		self.m_toughness = toughness;
+	}
+
+	/// Get the impulse that breaks this fixture.
+	pub fn get_toughness(&self) -> f32 {
+		return self.m_toughness;
+	}
+
 	/// Set the contact filtering data. This will not update contacts until the next time
 	/// step when either parent body is active and awake.
 	/// This automatically calls refilter.
@@ -234,6 +289,20 @@ impl<D: UserDataType> B2fixture<D> {
 		return inline::ray_cast(self, output, input, child_index);
 	}
 
+	/// Compute the closest point on this fixture to a point. All children are
+	/// considered, so this works for chain shapes. The distance is zero if the
+	/// point is inside the fixture.
+	/// * `p` - a point in world coordinates.
+	pub fn closest_point(&self, p: B2vec2) -> B2fixtureDistanceOutput {
+		return private::b2_fixture_closest_point(self, p);
+	}
+
+	/// Compute the distance and witness points between this fixture and another one,
+	/// over all pairs of children. Shape radii are included.
+	pub fn distance(&self, other: &B2fixture<D>) -> B2fixtureDistanceOutput {
+		return private::b2_fixture_distance(self, other);
+	}
+
 	/// Get the mass data for this fixture. The mass data is based on the density and
 	/// the shape. The rotational inertia is about the shape's origin. This operation
 	/// may be expensive.
@@ -0,0 +1,147 @@
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_shape::*;
+use crate::b2rs_common::UserDataType;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// How the strength of radial and vortex fields, and the impulse of explosions, changes
+/// with the distance to the center.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum B2forceFieldFalloff {
+	/// The same strength everywhere.
+	Constant,
+	/// Full strength at the center, fading linearly to zero at the field radius.
+	Linear,
+	/// Full strength at the center, fading quadratically to zero at the field radius.
+	Quadratic,
+	/// Full strength at the field radius, growing with the inverse square of the distance
+	/// towards the center.
+	InverseSquare,
+}
+
+/// The kind of force a field applies. All strengths are accelerations, so bodies of
+/// different mass are affected alike, the same way gravity works.
+#[derive(Clone, Copy, Debug)]
+pub enum B2forceFieldType {
+	/// A constant acceleration in world coordinates, such as wind.
+	Directional { acceleration: B2vec2 },
+	/// Pulls bodies towards the center, or pushes them away for a negative strength.
+	/// The center is in the local coordinates of the field area.
+	Radial {
+		center: B2vec2,
+		strength: f32,
+		falloff: B2forceFieldFalloff,
+		radius: f32,
+	},
+	/// Spins bodies counter-clockwise around the center, or clockwise for a negative
+	/// strength, while pulling them in with inward_strength. The center is in the local
+	/// coordinates of the field area.
+	Vortex {
+		center: B2vec2,
+		strength: f32,
+		inward_strength: f32,
+		falloff: B2forceFieldFalloff,
+		radius: f32,
+	},
+	/// Replaces the world gravity for the bodies in the field. The gravity scale of each
+	/// body still applies.
+	GravityOverride { gravity: B2vec2 },
+}
+
+/// The region of a force field.
+#[derive(Clone)]
+pub enum B2forceFieldArea<D: UserDataType> {
+	/// A shape placed in the world.
+	Shape {
+		shape: ShapeDefPtr,
+		transform: B2Transform,
+	},
+	/// A fixture, the field moves with its body. Usually a sensor.
+	Fixture(FixturePtr<D>),
+}
+
+/// Force field definition.
+#[derive(Clone)]
+pub struct B2forceFieldDef<D: UserDataType> {
+	/// The region of the field. A body is inside when its center of mass is.
+	pub area: Option<B2forceFieldArea<D>>,
+
+	/// The force applied by the field.
+	pub field_type: B2forceFieldType,
+
+	/// The field only affects bodies with a fixture whose category bits match these bits.
+	pub mask_bits: u16,
+
+	/// Does the field start enabled?
+	pub enabled: bool,
+}
+
+impl<D: UserDataType> Default for B2forceFieldDef<D> {
+	fn default() -> Self {
+		return Self {
+			area: None,
+			field_type: B2forceFieldType::Directional {
+				acceleration: B2vec2::zero(),
+			},
+			mask_bits: 0xFFFF,
+			enabled: true,
+		};
+	}
+}
+
+pub type B2forceFieldPtr<D> = Rc<RefCell<B2forceField<D>>>;
+
+/// A force field is a region of the world that applies forces to the dynamic bodies
+/// inside it. Fields are applied by B2world::step right before velocity integration.
+/// Sleeping bodies are not woken up by fields.
+/// Create fields with B2world::create_force_field.
+pub struct B2forceField<D: UserDataType> {
+	pub(crate) m_area: B2forceFieldArea<D>,
+	pub(crate) m_type: B2forceFieldType,
+	pub(crate) m_mask_bits: u16,
+	pub(crate) m_enabled: bool,
+}
+
+impl<D: UserDataType> B2forceField<D> {
+	/// Get the region of the field.
+	pub fn get_area(&self) -> &B2forceFieldArea<D> {
+		return &self.m_area;
+	}
+
+	/// Set the region of the field.
+	pub fn set_area(&mut self, area: B2forceFieldArea<D>) {
+		self.m_area = area;
+	}
+
+	/// Get the force applied by the field.
+	pub fn get_type(&self) -> B2forceFieldType {
+		return self.m_type;
+	}
+
+	/// Set the force applied by the field.
+	pub fn set_type(&mut self, field_type: B2forceFieldType) {
+		self.m_type = field_type;
+	}
+
+	/// Get the mask bits.
+	pub fn get_mask_bits(&self) -> u16 {
+		return self.m_mask_bits;
+	}
+
+	/// Set the mask bits.
+	pub fn set_mask_bits(&mut self, mask_bits: u16) {
+		self.m_mask_bits = mask_bits;
+	}
+
+	/// Is the field enabled?
+	pub fn is_enabled(&self) -> bool {
+		return self.m_enabled;
+	}
+
+	/// Enable or disable the field.
+	pub fn set_enabled(&mut self, flag: bool) {
+		self.m_enabled = flag;
+	}
+}
@@ -14,7 +14,10 @@ use std::rc::{Rc, Weak};
 #[cfg(feature="serde_support")]
 use serde::{Serialize, Deserialize};
 
+use crate::joints::b2_angle_joint::*;
+use crate::joints::b2_custom_joint::*;
 use crate::joints::b2_distance_joint::*;
+use crate::joints::b2_filter_joint::*;
 use crate::joints::b2_friction_joint::*;
 use crate::joints::b2_gear_joint::*;
 use crate::joints::b2_motor_joint::*;
@@ -38,6 +41,9 @@ pub enum B2JointDefEnum<D: UserDataType> {
 	PrismaticJoint(B2prismaticJointDef<D>),
 	WeldJoint(B2weldJointDef<D>),
 	WheelJoint(B2wheelJointDef<D>),
+	AngleJoint(B2angleJointDef<D>),
+	FilterJoint(B2filterJointDef<D>),
+	CustomJoint(B2customJointDef<D>),
 }
 
 
@@ -54,7 +60,10 @@ pub enum B2jointType {
 	EPulleyJoint,
 	ERevoluteJoint,
 	EWeldJoint,
-	EWheelJoint,	
+	EWheelJoint,
+	EAngleJoint,
+	EFilterJoint,
+	ECustomJoint,
 }
 
 impl Default for B2jointType {
@@ -301,6 +310,9 @@ pub enum JointAsDerived<'a, D: UserDataType> {
 	EPrismaticJoint(&'a B2prismaticJoint<D>),
 	EWeldJoint(&'a B2weldJoint<D>),
 	EWheelJoint(&'a B2wheelJoint<D>),
+	EAngleJoint(&'a B2angleJoint<D>),
+	EFilterJoint(&'a B2filterJoint<D>),
+	ECustomJoint(&'a B2customJointHost<D>),
 }
 
 pub enum JointAsDerivedMut<'a, D: UserDataType> {
@@ -314,6 +326,9 @@ pub enum JointAsDerivedMut<'a, D: UserDataType> {
 	EPrismaticJoint(&'a mut B2prismaticJoint<D>),
 	EWeldJoint(&'a mut B2weldJoint<D>),
 	EWheelJoint(&'a mut B2wheelJoint<D>),
+	EAngleJoint(&'a mut B2angleJoint<D>),
+	EFilterJoint(&'a mut B2filterJoint<D>),
+	ECustomJoint(&'a mut B2customJointHost<D>),
 }
 
 #[derive(Clone)]
@@ -0,0 +1,147 @@
+use crate::b2_draw::*;
+use crate::b2_math::*;
+use crate::b2_shape::*;
+
+use bitflags::bitflags;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A particle definition holds the data needed to create a single particle
+/// with B2particleSystem::create_particle.
+#[derive(Clone, Copy)]
+pub struct B2particleDef {
+	/// The world position of the particle.
+	pub position: B2vec2,
+
+	/// The linear velocity of the particle in world coordinates.
+	pub velocity: B2vec2,
+
+	/// The color used by the debug draw.
+	pub color: B2color,
+}
+
+impl Default for B2particleDef {
+	fn default() -> B2particleDef {
+		Self {
+			position: B2vec2::zero(),
+			velocity: B2vec2::zero(),
+			color: B2color::new(0.3, 0.5, 0.9),
+		}
+	}
+}
+
+bitflags! {
+	/// How the particles of a group behave together.
+	#[derive(Debug, Clone, Copy, PartialEq, Default)]
+	pub struct B2particleGroupFlags: u16 {
+		/// Neighbours in the group are joined by springs when the group is
+		/// created, so the group keeps its shape but can be deformed.
+		const ELASTIC = 0x0001;
+		/// The group moves as one rigid body. Wins over ELASTIC.
+		const RIGID = 0x0002;
+	}
+}
+
+/// A particle group definition holds the data needed to create a group of
+/// particles with B2particleSystem::create_particle_group. The particles fill
+/// the shape, if there is one, and are placed at the given positions.
+#[derive(Clone)]
+pub struct B2particleGroupDef {
+	pub flags: B2particleGroupFlags,
+
+	/// The world position of the group.
+	pub position: B2vec2,
+
+	/// The world angle of the group in radians.
+	pub angle: f32,
+
+	/// The linear velocity of the group origin in world coordinates.
+	pub linear_velocity: B2vec2,
+
+	/// The angular velocity of the group around its origin.
+	pub angular_velocity: f32,
+
+	/// The shape filled with particles, in the group coordinates. This only
+	/// works for convex shapes.
+	pub shape: Option<ShapeDefPtr>,
+
+	/// The distance between the particles filling the shape. Zero uses the
+	/// spacing of the system, which makes the particles of a resting fluid
+	/// just touch.
+	pub stride: f32,
+
+	/// More particles in the group coordinates.
+	pub positions: Vec<B2vec2>,
+
+	/// The color used by the debug draw.
+	pub color: B2color,
+}
+
+impl Default for B2particleGroupDef {
+	fn default() -> B2particleGroupDef {
+		Self {
+			flags: B2particleGroupFlags::default(),
+			position: B2vec2::zero(),
+			angle: 0.0,
+			linear_velocity: B2vec2::zero(),
+			angular_velocity: 0.0,
+			shape: None,
+			stride: 0.0,
+			positions: Vec::new(),
+			color: B2color::new(0.3, 0.5, 0.9),
+		}
+	}
+}
+
+pub type B2particleGroupPtr = Rc<RefCell<B2particleGroup>>;
+
+/// A group of particles created together. The particle system updates the
+/// statistics of the group at the end of every step. A group without
+/// particles stays in the system until it is destroyed.
+#[derive(Default)]
+pub struct B2particleGroup {
+	pub(crate) m_flags: B2particleGroupFlags,
+	pub(crate) m_count: usize,
+	pub(crate) m_mass: f32,
+	pub(crate) m_center: B2vec2,
+	pub(crate) m_linear_velocity: B2vec2,
+	pub(crate) m_angular_velocity: f32,
+	pub(crate) m_angle: f32,
+}
+
+impl B2particleGroup {
+	pub fn get_flags(&self) -> B2particleGroupFlags {
+		return self.m_flags;
+	}
+
+	/// Get the number of particles in the group.
+	pub fn get_particle_count(&self) -> usize {
+		return self.m_count;
+	}
+
+	/// Get the total mass of the particles.
+	pub fn get_mass(&self) -> f32 {
+		return self.m_mass;
+	}
+
+	/// Get the center of mass of the particles.
+	pub fn get_center(&self) -> B2vec2 {
+		return self.m_center;
+	}
+
+	/// Get the average velocity of the particles.
+	pub fn get_linear_velocity(&self) -> B2vec2 {
+		return self.m_linear_velocity;
+	}
+
+	/// Get the angular velocity of the particles around the center.
+	pub fn get_angular_velocity(&self) -> f32 {
+		return self.m_angular_velocity;
+	}
+
+	/// Get how far a rigid group turned since it was created.
+	pub fn get_angle(&self) -> f32 {
+		return self.m_angle;
+	}
+}
@@ -0,0 +1,168 @@
+use crate::b2_draw::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_particle::*;
+
+use crate::private::particle::b2_particle_system as private;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A particle system definition holds the data needed to create a particle
+/// system with B2world::create_particle_system.
+#[derive(Clone, Copy)]
+pub struct B2particleSystemDef {
+	/// The radius of the particles. Particles closer than the diameter are
+	/// neighbours.
+	pub radius: f32,
+
+	/// The density of the fluid, the particle mass is derived from it.
+	pub density: f32,
+
+	/// Scale of the world gravity for the particles.
+	pub gravity_scale: f32,
+
+	/// How hard crowded particles push each other apart, usually well below
+	/// one.
+	pub pressure_strength: f32,
+
+	/// How much of the velocity difference of neighbours is removed in a
+	/// step, between zero and one.
+	pub viscous_strength: f32,
+
+	/// How much of the approaching velocity of neighbours and fixtures is
+	/// removed in a step, between zero and one.
+	pub damping_strength: f32,
+
+	/// The stiffness of the springs in elastic groups, between zero and one.
+	pub elastic_strength: f32,
+
+	/// The fixtures the particles collide with. Sensors are ignored.
+	pub filter: B2filter,
+}
+
+impl Default for B2particleSystemDef {
+	fn default() -> B2particleSystemDef {
+		Self {
+			radius: 0.1,
+			density: 1.0,
+			gravity_scale: 1.0,
+			pressure_strength: 0.05,
+			viscous_strength: 0.25,
+			damping_strength: 1.0,
+			elastic_strength: 0.25,
+			filter: B2filter::default(),
+		}
+	}
+}
+
+pub type B2particleSystemPtr = Rc<RefCell<B2particleSystem>>;
+
+// A spring between two particles of an elastic group.
+#[derive(Clone, Copy)]
+pub(crate) struct B2particleSpring {
+	pub(crate) index_a: usize,
+	pub(crate) index_b: usize,
+	pub(crate) rest_length: f32,
+}
+
+/// A set of particles simulated as a fluid, in the spirit of LiquidFun.
+/// Particle systems are created by a world, which steps them after the soft
+/// bodies. Neighbours are found with a spatial hash. Particles collide with
+/// the world fixtures found in the broad-phase and push the dynamic bodies
+/// they hit, but different systems don't see each other.
+/// The particles are stored in arrays, so destroying a particle changes the
+/// index of the last one.
+pub struct B2particleSystem {
+	pub(crate) m_def: B2particleSystemDef,
+
+	pub(crate) m_positions: Vec<B2vec2>,
+	pub(crate) m_velocities: Vec<B2vec2>,
+	pub(crate) m_colors: Vec<B2color>,
+	// The sum of the contact weights found in the last step.
+	pub(crate) m_weights: Vec<f32>,
+	pub(crate) m_groups: Vec<Option<B2particleGroupPtr>>,
+	// Where the particles were created, the shape kept by rigid groups.
+	pub(crate) m_rest_positions: Vec<B2vec2>,
+
+	pub(crate) m_group_list: Vec<B2particleGroupPtr>,
+	pub(crate) m_springs: Vec<B2particleSpring>,
+}
+
+impl B2particleSystem {
+	/// Create a particle and return its index.
+	pub fn create_particle(&mut self, def: &B2particleDef) -> usize {
+		return private::create_particle(self, def, None);
+	}
+
+	/// Destroy a particle. The last particle takes its index.
+	pub fn destroy_particle(&mut self, index: usize) {
+		private::destroy_particle(self, index);
+	}
+
+	/// Create a group of particles, see B2particleGroupDef.
+	pub fn create_particle_group(&mut self, def: &B2particleGroupDef) -> B2particleGroupPtr {
+		return private::create_particle_group(self, def);
+	}
+
+	/// Destroy a group and its particles.
+	pub fn destroy_particle_group(&mut self, group: B2particleGroupPtr) {
+		private::destroy_particle_group(self, group);
+	}
+
+	/// Get the groups of the system.
+	pub fn get_particle_groups(&self) -> &[B2particleGroupPtr] {
+		return &self.m_group_list;
+	}
+
+	/// Get the number of particles.
+	pub fn get_particle_count(&self) -> usize {
+		return self.m_positions.len();
+	}
+
+	/// Get the world positions of the particles.
+	pub fn get_positions(&self) -> &[B2vec2] {
+		return &self.m_positions;
+	}
+
+	/// Get the velocities of the particles.
+	pub fn get_velocities(&self) -> &[B2vec2] {
+		return &self.m_velocities;
+	}
+
+	pub fn set_particle_velocity(&mut self, index: usize, velocity: B2vec2) {
+		self.m_velocities[index] = velocity;
+	}
+
+	pub fn get_colors(&self) -> &[B2color] {
+		return &self.m_colors;
+	}
+
+	/// Get how crowded the particles were in the last step. A particle
+	/// resting in a fluid has a weight close to one.
+	pub fn get_weights(&self) -> &[f32] {
+		return &self.m_weights;
+	}
+
+	/// Get the group of a particle.
+	pub fn get_particle_group(&self, index: usize) -> Option<B2particleGroupPtr> {
+		return self.m_groups[index].clone();
+	}
+
+	pub fn get_radius(&self) -> f32 {
+		return self.m_def.radius;
+	}
+
+	/// Get the mass of one particle.
+	pub fn get_particle_mass(&self) -> f32 {
+		return private::get_particle_mass(self);
+	}
+
+	pub fn get_def(&self) -> &B2particleSystemDef {
+		return &self.m_def;
+	}
+
+	pub fn draw(&self, draw: &mut dyn B2drawTrait) {
+		private::draw(self, draw);
+	}
+}
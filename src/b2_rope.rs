@@ -1,15 +1,27 @@
+use crate::b2_body::*;
 use crate::b2_draw::*;
+use crate::b2_fixture::*;
 use crate::b2_math::*;
+use crate::b2rs_common::UserDataType;
 
 use crate::private::rope::b2_rope as private;
+use crate::private::rope::b2_rope_world as private_world;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[cfg(feature="serde_support")]
+use serde::{Serialize, Deserialize};
 
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub enum B2stretchingModel {
 	B2PbdStretchingModel=0,
 	B2XpbdStretchingModel,
 }
 
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub enum B2bendingModel {
 	B2SpringAngleBendingModel = 0,
 	B2PbdAngleBendingModel,
@@ -20,6 +32,7 @@ pub enum B2bendingModel {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct B2ropeTuning {
 	pub stretching_model: B2stretchingModel,
 	pub bending_model: B2bendingModel,
@@ -55,17 +68,30 @@ impl Default for B2ropeTuning {
 }
 
 #[derive(Default, Clone, Copy)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct B2ropeDefVertices {
 	pub position: B2vec2,
 	pub mass: f32,
 }
 
 ///
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct B2ropeDef {
 	pub position: B2vec2,
 	pub vertices: Vec<B2ropeDefVertices>,
+	/// Ropes created by a world use the world gravity instead.
 	pub gravity: B2vec2,
 	pub tuning: B2ropeTuning,
+
+	/// The radius of the rope vertices when colliding with fixtures. Only
+	/// ropes created by a world collide.
+	pub radius: f32,
+
+	/// The friction of the rope against fixtures.
+	pub friction: f32,
+
+	/// The fixtures the rope collides with. Sensors are ignored.
+	pub filter: B2filter,
 }
 
 impl Default for B2ropeDef {
@@ -75,11 +101,25 @@ impl Default for B2ropeDef {
 			vertices: Vec::default(),
 			gravity: B2vec2::zero(),
 			tuning: Default::default(),
+			radius: 0.05,
+			friction: 0.2,
+			filter: B2filter::default(),
 		}
 	}
 }
 
+/// A rope vertex pinned to a point on a body. Tension pulls the body at the
+/// anchor, and the vertex follows the body.
+#[derive(Clone)]
+pub struct B2ropeAttachment<D: UserDataType> {
+	pub vertex: usize,
+	pub body: BodyPtr<D>,
+	/// The anchor point in the body local coordinates.
+	pub local_anchor: B2vec2,
+}
+
 #[derive(Default, Clone, Copy)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub(crate) struct B2ropeStretch {
 	pub i1: i32,
 	pub i2: i32,
@@ -92,6 +132,7 @@ pub(crate) struct B2ropeStretch {
 }
 
 #[derive(Default, Clone, Copy)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub(crate) struct B2ropeBend {
 	pub i1: i32,
 	pub i2: i32,
@@ -110,6 +151,7 @@ pub(crate) struct B2ropeBend {
 }
 
 #[derive(Default, Clone, Copy)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub(crate) struct B2ropePositions {
 	pub(crate) m_bind_positions: B2vec2,
 	pub(crate) m_ps: B2vec2,
@@ -118,6 +160,12 @@ pub(crate) struct B2ropePositions {
 	pub(crate) m_inv_masses: f32,
 }
 
+/// A position based rope. A rope can be stepped on its own with B2rope::step,
+/// or created with B2world::create_rope, in which case the world steps it
+/// after the bodies and collides it with the world fixtures, see B2worldRope.
+///
+/// With serde_support a rope serializes its tuning and current vertex state.
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct B2rope {
 	pub(crate) m_position: B2vec2,
 
@@ -132,6 +180,13 @@ pub struct B2rope {
 	pub(crate) m_gravity: B2vec2,
 
 	pub(crate) m_tuning: B2ropeTuning,
+
+	// The time step of the last step, for the tension.
+	pub(crate) m_dt: f32,
+
+	pub(crate) m_radius: f32,
+	pub(crate) m_friction: f32,
+	pub(crate) m_filter: B2filter,
 }
 
 impl Default for B2rope {
@@ -165,4 +220,134 @@ impl B2rope {
 	pub fn draw(&self, draw: &mut dyn B2drawTrait) {
 		private::draw(self, draw);
 	}
+
+	/// Get the number of vertices.
+	pub fn get_vertex_count(&self) -> usize {
+		return self.m_positions.len();
+	}
+
+	/// Get the world position of a vertex.
+	pub fn get_vertex(&self, index: usize) -> B2vec2 {
+		return self.m_positions[index].m_ps;
+	}
+
+	/// Get the velocity of a vertex.
+	pub fn get_vertex_velocity(&self, index: usize) -> B2vec2 {
+		return self.m_positions[index].m_vs;
+	}
+
+	/// Get the number of segments, one less than the vertices.
+	pub fn get_segment_count(&self) -> usize {
+		return self.m_stretch_constraints.len();
+	}
+
+	/// Get the rest length of the rope, the sum of the segment rest lengths.
+	pub fn get_length(&self) -> f32 {
+		return private::get_length(self);
+	}
+
+	/// Get the current length of the rope along its vertices.
+	pub fn get_current_length(&self) -> f32 {
+		return private::get_current_length(self);
+	}
+
+	/// Get the tension of a segment in Newtons during the last step. The
+	/// tension is negative when the segment is compressed.
+	pub fn get_tension(&self, segment: usize) -> f32 {
+		return private::get_tension(self, segment);
+	}
+
+	/// Cut the rope at a segment. The segment is removed, this rope keeps the
+	/// vertices before it and the returned rope gets the vertices after it.
+	/// Use B2world::cut_rope for ropes in a world.
+	pub fn cut(&mut self, segment: usize) -> B2rope {
+		return private::cut(self, segment);
+	}
+
+	/// Reel the rope in or out at its first vertex, like a winch. A positive
+	/// delta lengthens the rope. Vertices are inserted or removed after the
+	/// first vertex to keep the segments near their rest length, without
+	/// changing the strain of the rope, so the tension is preserved.
+	pub fn reel(&mut self, delta: f32) {
+		private::reel(self, delta);
+	}
+
+	/// Get the collision radius of the vertices.
+	pub fn get_radius(&self) -> f32 {
+		return self.m_radius;
+	}
+
+	/// Set the collision radius of the vertices.
+	pub fn set_radius(&mut self, radius: f32) {
+		self.m_radius = radius;
+	}
+
+	/// Get the friction against fixtures.
+	pub fn get_friction(&self) -> f32 {
+		return self.m_friction;
+	}
+
+	/// Set the friction against fixtures.
+	pub fn set_friction(&mut self, friction: f32) {
+		self.m_friction = friction;
+	}
+
+	/// Get the filter of the fixtures the rope collides with.
+	pub fn get_filter_data(&self) -> B2filter {
+		return self.m_filter;
+	}
+
+	/// Set the filter of the fixtures the rope collides with.
+	pub fn set_filter_data(&mut self, filter: B2filter) {
+		self.m_filter = filter;
+	}
+}
+
+pub type B2ropePtr<D> = Rc<RefCell<B2worldRope<D>>>;
+
+/// A rope created by a world. The world steps the rope after the bodies,
+/// couples it to the bodies it is attached to and collides it with the world
+/// fixtures.
+///
+/// With serde_support the rope and its attachments are saved with the world,
+/// see B2world.
+pub struct B2worldRope<D: UserDataType> {
+	pub(crate) m_rope: B2rope,
+	pub(crate) m_attachments: Vec<B2ropeAttachment<D>>,
+}
+
+impl<D: UserDataType> B2worldRope<D> {
+	/// Get the rope.
+	pub fn get_rope(&self) -> &B2rope {
+		return &self.m_rope;
+	}
+
+	/// Get the rope to change it. Use B2world::cut_rope to cut it and
+	/// B2worldRope::reel to reel it, they keep the attachments on their vertices.
+	pub fn get_rope_mut(&mut self) -> &mut B2rope {
+		return &mut self.m_rope;
+	}
+
+	/// Reel the rope in or out at its first vertex, see B2rope::reel. An
+	/// attachment on a removed vertex is removed.
+	pub fn reel(&mut self, delta: f32) {
+		private_world::reel(self, delta);
+	}
+
+	/// Pin a vertex to a point on a body, given in world coordinates. The
+	/// vertex keeps its mass: a vertex without mass does not move, so it
+	/// holds the body in place. A vertex has at most one attachment.
+	pub fn attach(&mut self, vertex: usize, body: BodyPtr<D>, anchor: B2vec2) {
+		private_world::attach(self, vertex, body, anchor);
+	}
+
+	/// Remove the attachment of a vertex, if any.
+	pub fn detach(&mut self, vertex: usize) {
+		self.m_attachments.retain(|a| a.vertex != vertex);
+	}
+
+	/// Get the attachments of the rope.
+	pub fn get_attachments(&self) -> &[B2ropeAttachment<D>] {
+		return &self.m_attachments;
+	}
 }
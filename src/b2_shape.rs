@@ -27,7 +27,8 @@ pub enum B2ShapeType {
 	EEdge = 1,
 	EPolygon = 2,
 	EChain = 3,
-	ETypeCount = 4,
+	ECustom = 4,
+	ETypeCount = 5,
 }
 
 impl Default for B2ShapeType {
@@ -0,0 +1,177 @@
+use crate::b2_draw::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_rope::*;
+
+use crate::private::rope::b2_soft_body as private;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A soft body definition holds the data needed to create a soft body with
+/// B2world::create_soft_body.
+#[derive(Clone)]
+pub struct B2softBodyDef {
+	/// The world position of the soft body.
+	pub position: B2vec2,
+
+	/// The outline relative to the position, at least three points. The
+	/// outline is closed, the last point connects to the first one.
+	pub vertices: Vec<B2vec2>,
+
+	/// The initial velocity of all the vertices.
+	pub linear_velocity: B2vec2,
+
+	/// The mass per area, spread evenly over the vertices.
+	pub density: f32,
+
+	/// Damping of the vertex velocities.
+	pub damping: f32,
+
+	/// The frequency of the springs along the outline in Hertz. Zero makes
+	/// the edges stiff.
+	pub edge_hertz: f32,
+
+	/// The damping ratio of the edge springs.
+	pub edge_damping_ratio: f32,
+
+	/// The area the body tries to keep, as a multiple of the initial area.
+	/// Above one the body is inflated.
+	pub pressure: f32,
+
+	/// How much of the area error is removed in an iteration, between zero
+	/// and one.
+	pub pressure_stiffness: f32,
+
+	/// The radius of the vertices when colliding with fixtures.
+	pub radius: f32,
+
+	/// The friction against fixtures.
+	pub friction: f32,
+
+	/// The fixtures the soft body collides with. Sensors are ignored.
+	pub filter: B2filter,
+}
+
+impl Default for B2softBodyDef {
+	fn default() -> B2softBodyDef {
+		Self {
+			position: B2vec2::zero(),
+			vertices: Vec::new(),
+			linear_velocity: B2vec2::zero(),
+			density: 1.0,
+			damping: 0.0,
+			edge_hertz: 0.0,
+			edge_damping_ratio: 0.0,
+			pressure: 1.0,
+			pressure_stiffness: 1.0,
+			radius: 0.05,
+			friction: 0.4,
+			filter: B2filter::default(),
+		}
+	}
+}
+
+impl B2softBodyDef {
+	/// Set the outline to a circle around the position.
+	pub fn set_as_circle(&mut self, radius: f32, count: usize) {
+		private::set_as_circle(self, radius, count);
+	}
+}
+
+pub type B2softBodyPtr = Rc<RefCell<B2softBody>>;
+
+/// A deformable body made of a ring of particles. Springs along the outline
+/// keep its shape and a pressure constraint keeps its area. Soft bodies are
+/// created by a world, which steps them after the ropes with the same solver.
+/// They collide with the world fixtures and push the dynamic bodies they hit,
+/// but don't collide with ropes or other soft bodies.
+pub struct B2softBody {
+	// The outline is a rope without constraints, the soft body solves its
+	// edges and area in the rope steps.
+	pub(crate) m_ring: B2rope,
+	// The edges of the outline, the last one closes the ring.
+	pub(crate) m_edges: Vec<B2ropeStretch>,
+	pub(crate) m_rest_area: f32,
+	pub(crate) m_pressure: f32,
+	pub(crate) m_pressure_stiffness: f32,
+}
+
+impl B2softBody {
+	/// Get the number of vertices.
+	pub fn get_vertex_count(&self) -> usize {
+		return self.m_ring.get_vertex_count();
+	}
+
+	/// Get the world position of a vertex.
+	pub fn get_vertex(&self, index: usize) -> B2vec2 {
+		return self.m_ring.get_vertex(index);
+	}
+
+	/// Get the velocity of a vertex.
+	pub fn get_vertex_velocity(&self, index: usize) -> B2vec2 {
+		return self.m_ring.get_vertex_velocity(index);
+	}
+
+	/// Get the outline in world coordinates, counter clockwise. The
+	/// collision skin is the outline grown by the radius.
+	pub fn get_outline(&self) -> Vec<B2vec2> {
+		return self.m_ring.m_positions.iter().map(|p| p.m_ps).collect();
+	}
+
+	/// Get the centroid of the outline.
+	pub fn get_center(&self) -> B2vec2 {
+		return private::get_center(self);
+	}
+
+	/// Get the average velocity of the vertices.
+	pub fn get_linear_velocity(&self) -> B2vec2 {
+		return private::get_linear_velocity(self);
+	}
+
+	/// Get the current area inside the outline.
+	pub fn get_area(&self) -> f32 {
+		return private::get_area(&self.m_ring);
+	}
+
+	/// Get the area the soft body was created with.
+	pub fn get_rest_area(&self) -> f32 {
+		return self.m_rest_area;
+	}
+
+	/// Set the area to keep as a multiple of the rest area.
+	pub fn set_pressure(&mut self, pressure: f32) {
+		self.m_pressure = pressure;
+	}
+
+	pub fn get_pressure(&self) -> f32 {
+		return self.m_pressure;
+	}
+
+	/// Get the total mass of the vertices.
+	pub fn get_mass(&self) -> f32 {
+		return private::get_mass(self);
+	}
+
+	/// Apply an impulse to the whole soft body. All the vertices get the
+	/// same velocity change.
+	pub fn apply_linear_impulse(&mut self, impulse: B2vec2) {
+		private::apply_linear_impulse(self, impulse);
+	}
+
+	pub fn get_radius(&self) -> f32 {
+		return self.m_ring.get_radius();
+	}
+
+	pub fn get_friction(&self) -> f32 {
+		return self.m_ring.get_friction();
+	}
+
+	pub fn set_friction(&mut self, friction: f32) {
+		self.m_ring.set_friction(friction);
+	}
+
+	pub fn draw(&self, draw: &mut dyn B2drawTrait) {
+		private::draw(self, draw);
+	}
+}
@@ -15,27 +15,28 @@ pub struct B2Profile {
 
 /// This is an internal structure.
 #[derive(Default, Clone, Copy, Debug)]
-pub(crate) struct B2timeStep {
+pub struct B2timeStep {
 	pub dt: f32,      // time step
 	pub inv_dt: f32,  // inverse time step (0 if dt == 0).
 	pub dt_ratio: f32, // dt * inv_dt0
 	pub velocity_iterations: i32,
 	pub position_iterations: i32,
 	pub warm_starting: bool,
+	pub speculative_contacts: bool,
 }
 
 /// This is an internal structure.
 #[derive(Default, Clone, Copy, Debug)]
 pub struct B2position {
-	pub(crate) c: B2vec2,
-	pub(crate) a: f32,
+	pub c: B2vec2,
+	pub a: f32,
 }
 
 /// This is an internal structure.
 #[derive(Default, Clone, Copy, Debug)]
 pub struct B2velocity {
-	pub(crate) v: B2vec2,
-	pub(crate) w: f32,
+	pub v: B2vec2,
+	pub w: f32,
 }
 
 //box2d-rs: not using struct because of borrowing and mutability
@@ -49,7 +50,7 @@ pub struct B2velocity {
 /// Solver Data
 #[derive(Default, Debug)]
 pub struct B2solverData {
-	pub(crate) step: B2timeStep,
+	pub step: B2timeStep,
 	//pub(crate) positions: &'a mut  [B2position],
 	//pub(crate) velocities: &'a mut  [B2velocity],
 }
@@ -7,14 +7,28 @@ use crate::b2_contact::*;
 use crate::b2_contact_manager::*;
 use crate::b2_draw::*;
 use crate::b2_fixture::*;
+use crate::b2_force_field::*;
 use crate::b2_joint::*;
 use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_rope::*;
+use crate::b2_soft_body::*;
+use crate::b2_particle_system::*;
+use crate::b2_shape::*;
 use crate::b2rs_common::*;
 use crate::b2_time_step::*;
 use crate::b2_world_callbacks::*;
 use crate::b2rs_double_linked_list::*;
+use crate::b2rs_iterators::*;
 
 use crate::private::dynamics::b2_world as private;
+use crate::private::dynamics::b2_world_query as private_query;
+use crate::private::dynamics::b2_force_field as private_force_field;
+use crate::private::rope::b2_rope_world as private_rope;
+use crate::private::rope::b2_soft_body as private_soft_body;
+use crate::private::particle::b2_particle_system as private_particle;
+use crate::private::dynamics::b2_fracture as private_fracture;
+use crate::private::dynamics::b2_fracture::B2fractureImpact;
 
 pub type B2worldPtr<D> = Rc<RefCell<B2world<D>>>;
 pub type B2worldWeakPtr<D> = Weak<RefCell<B2world<D>>>;
@@ -45,6 +59,13 @@ impl<D: UserDataType> B2world<D> {
 		private::set_contact_listener(self, listener);
 	}
 
+	/// Register a fracture listener to copy the user data of broken fixtures and
+	/// bodies to their pieces. Otherwise the user data is cloned. The listener is
+	/// owned by you and must remain in scope.
+	pub fn set_fracture_listener(&mut self, listener: B2fractureListenerPtr<D>) {
+		private_fracture::set_fracture_listener(self, listener);
+	}
+
 	/// Register a routine for debug drawing. The debug draw functions are called
 	/// inside with debug_draw method. The debug draw object is owned
 	/// by you and must remain in scope.
@@ -132,6 +153,157 @@ impl<D: UserDataType> B2world<D> {
 	pub fn ray_cast<F: B2rayCastCallback<D>>(&self, callback: F, point1: B2vec2, point2: B2vec2) {
 		private::ray_cast(self, callback, point1, point2);
 	}
+
+	/// query the world for all fixtures that potentially overlap the
+	/// provided AABB and pass the filter.
+	/// * `callback` - a user implemented callback class.
+	/// * `aabb` - the query box.
+	/// * `filter` - fixtures rejected by this filter are not reported.
+	pub fn query_aabb_filtered<F: B2queryCallback<D>>(&self, callback: F, aabb: B2AABB, filter: &B2queryFilter<D>) {
+		private_query::query_aabb_filtered(self, callback, aabb, filter);
+	}
+
+	/// Ray-cast the world like ray_cast, skipping fixtures rejected by the filter
+	/// before the exact ray test.
+	/// * `callback` - a user implemented callback class.
+	/// * `point1` - the ray starting point
+	/// * `point2` - the ray ending point
+	/// * `filter` - the query filter.
+	pub fn ray_cast_filtered<F: B2rayCastCallback<D>>(
+		&self,
+		callback: F,
+		point1: B2vec2,
+		point2: B2vec2,
+		filter: &B2queryFilter<D>,
+	) {
+		private_query::ray_cast_filtered(self, callback, point1, point2, filter);
+	}
+
+	/// Ray-cast the world and return the closest hit, if any.
+	pub fn ray_cast_closest(&self, point1: B2vec2, point2: B2vec2, filter: &B2queryFilter<D>) -> Option<B2castHit<D>> {
+		return private_query::ray_cast_closest(self, point1, point2, filter);
+	}
+
+	/// Ray-cast the world and return all hits sorted by fraction.
+	pub fn ray_cast_all(&self, point1: B2vec2, point2: B2vec2, filter: &B2queryFilter<D>) -> Vec<B2castHit<D>> {
+		return private_query::ray_cast_all(self, point1, point2, filter);
+	}
+
+	/// Cast a shape through the world and report every fixture it would hit. Your
+	/// callback controls whether you get the closest hit, any hit, or n-hits, in the
+	/// same way as for ray_cast. Fixtures that initially overlap the shape are ignored.
+	/// * `shape` - the shape to sweep, all of its children are cast.
+	/// * `transform` - the shape transform at the start of the cast.
+	/// * `translation` - the translation of the shape over the cast.
+	/// * `filter` - fixtures rejected by this filter are skipped before the shape test.
+	/// * `callback` - a user implemented callback class.
+	pub fn cast_shape<F: B2shapeCastCallback<D>>(
+		&self,
+		shape: &dyn B2shapeDynTrait,
+		transform: B2Transform,
+		translation: B2vec2,
+		filter: &B2queryFilter<D>,
+		callback: F,
+	) {
+		private_query::cast_shape(self, shape, transform, translation, filter, callback);
+	}
+
+	/// Cast a shape through the world and return the closest hit, if any.
+	/// [see](B2world::cast_shape)
+	pub fn cast_shape_closest(
+		&self,
+		shape: &dyn B2shapeDynTrait,
+		transform: B2Transform,
+		translation: B2vec2,
+		filter: &B2queryFilter<D>,
+	) -> Option<B2castHit<D>> {
+		return private_query::cast_shape_closest(self, shape, transform, translation, filter);
+	}
+
+	/// query the world for all fixtures whose geometry overlaps the provided shape.
+	/// Unlike query_aabb this performs the exact narrow-phase test, and each fixture
+	/// is reported once even when several of its children overlap.
+	/// * `shape` - the query shape, all of its children are tested.
+	/// * `transform` - the world transform of the query shape.
+	/// * `filter` - fixtures rejected by this filter are skipped.
+	/// * `callback` - a user implemented callback class.
+	pub fn overlap_shape<F: B2queryCallback<D>>(
+		&self,
+		shape: &dyn B2shapeDynTrait,
+		transform: B2Transform,
+		filter: &B2queryFilter<D>,
+		callback: F,
+	) {
+		private_query::overlap_shape(self, shape, transform, filter, callback);
+	}
+
+	/// Compute the distance and witness points between two fixtures, over all pairs
+	/// of their children. The normal points from fixture_a to fixture_b and is zero
+	/// when the fixtures overlap.
+	pub fn distance(&self, fixture_a: FixturePtr<D>, fixture_b: FixturePtr<D>) -> B2fixtureDistanceOutput {
+		return private_query::distance(fixture_a, fixture_b);
+	}
+
+	/// Find the fixture closest to a point within max_distance. The dynamic tree is
+	/// used to skip fixtures that are farther than the best candidate.
+	/// 
+	/// @return the nearest fixture and its closest point, point_b is the query point.
+	pub fn query_nearest(
+		&self,
+		point: B2vec2,
+		max_distance: f32,
+		filter: &B2queryFilter<D>,
+	) -> Option<(FixturePtr<D>, B2fixtureDistanceOutput)> {
+		return private_query::query_nearest(self, point, max_distance, filter);
+	}
+
+	/// query the world for all fixtures overlapping a circle.
+	/// [see](B2world::overlap_shape)
+	pub fn overlap_circle<F: B2queryCallback<D>>(&self, center: B2vec2, radius: f32, filter: &B2queryFilter<D>, callback: F) {
+		private_query::overlap_circle(self, center, radius, filter, callback);
+	}
+
+	/// query the world for all fixtures overlapping a convex polygon given by its
+	/// world vertices. The vertices are processed like B2polygonShape::set.
+	/// [see](B2world::overlap_shape)
+	pub fn overlap_polygon<F: B2queryCallback<D>>(&self, vertices: &[B2vec2], filter: &B2queryFilter<D>, callback: F) {
+		private_query::overlap_polygon(self, vertices, filter, callback);
+	}
+
+	/// query the world for all fixtures containing a point. Like B2fixture::test_point
+	/// this only reports solid shapes, edges and chains are never hit.
+	pub fn overlap_point<F: B2queryCallback<D>>(&self, point: B2vec2, filter: &B2queryFilter<D>, callback: F) {
+		private_query::overlap_point(self, point, filter, callback);
+	}
+
+	/// Apply a radial impulse to the dynamic bodies around a point, like an explosion.
+	/// Each body within the radius is pushed once, away from the center at the surface
+	/// point of its fixtures closest to the center, so bodies also start spinning. The
+	/// impulse of a body is scaled by the falloff at that point.
+	/// * `center` - the explosion center in world coordinates.
+	/// * `radius` - the bodies further away are not affected.
+	/// * `impulse` - the largest impulse a body receives, usually in N-seconds or kg-m/s.
+	/// * `falloff` - how the impulse changes with the distance. Constant applies the full
+	///   impulse up to the radius. Linear and Quadratic apply it at the center and fade to zero
+	///   at the radius. InverseSquare is full strength at the radius and grows towards the
+	///   center, so with the impulse as a cap it acts like Constant.
+	/// * `occlusion` - sample rays from the center to each body against the static fixtures
+	/// of the world and scale the impulse by the unblocked share, so walls shelter bodies.
+	///
+	/// @return the affected bodies with the impulse each one received.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> This function is locked during callbacks.
+	/// </p>
+	pub fn explode(
+		&self,
+		center: B2vec2,
+		radius: f32,
+		impulse: f32,
+		falloff: B2forceFieldFalloff,
+		occlusion: bool,
+	) -> Vec<B2explosionHit<D>> {
+		return private_query::explode(self, center, radius, impulse, falloff, occlusion);
+	}
 	/// Get the world body list. With the returned body, use b2_body::get_next to get
 	/// the next body in the world list. A None body indicates the end of the list.
 	/// 
@@ -160,6 +332,123 @@ impl<D: UserDataType> B2world<D> {
 		return inline::get_contact_list(self);
 	}
 
+	/// Iterate over the world bodies. The iterator does not borrow the world, so
+	/// the current body may be destroyed while iterating.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> do not use this while the world is locked.
+	/// </p>
+	pub fn bodies(&self) -> B2bodyIter<D> {
+		b2_assert(self.is_locked() == false);
+		return B2bodyIter { inner: self.m_body_list.iter() };
+	}
+
+	/// Iterate over the world joints. The current joint may be destroyed while iterating.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> do not use this while the world is locked.
+	/// </p>
+	pub fn joints(&self) -> B2jointIter<D> {
+		b2_assert(self.is_locked() == false);
+		return B2jointIter { inner: self.m_joint_list.iter() };
+	}
+
+	/// Iterate over the touching contacts of the world. Use get_contact_list to also
+	/// see contacts whose fixture AABBs overlap but that have no contact points.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> do not use this while the world is locked.
+	/// </p>
+	pub fn contacts(&self) -> B2contactIter<D> {
+		b2_assert(self.is_locked() == false);
+		return B2contactIter { inner: self.m_contact_manager.borrow().m_contact_list.iter() };
+	}
+
+	/// create a force field given a definition. Fields are applied during each step
+	/// right before the velocities are integrated.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> This function is locked during callbacks.
+	/// </p>
+	pub fn create_force_field(&mut self, def: &B2forceFieldDef<D>) -> B2forceFieldPtr<D> {
+		return private_force_field::create_force_field(self, def);
+	}
+
+	/// destroy a force field.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> This function is locked during callbacks.
+	/// </p>
+	pub fn destroy_force_field(&mut self, field: B2forceFieldPtr<D>) {
+		private_force_field::destroy_force_field(self, field);
+	}
+
+	/// Get the force fields of the world.
+	pub fn get_force_fields(&self) -> &[B2forceFieldPtr<D>] {
+		return &self.m_force_field_list;
+	}
+
+	/// create a rope given a definition. The world steps the rope after the
+	/// bodies, using the world gravity and the velocity iterations as the
+	/// rope iterations. The rope collides with the world fixtures and can be
+	/// attached to bodies with B2worldRope::attach.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> This function is locked during callbacks.
+	/// </p>
+	pub fn create_rope(&mut self, def: &B2ropeDef) -> B2ropePtr<D> {
+		return private_rope::create_rope(self, def);
+	}
+
+	/// destroy a rope. This function is locked during callbacks.
+	pub fn destroy_rope(&mut self, rope: B2ropePtr<D>) {
+		private_rope::destroy_rope(self, rope);
+	}
+
+	/// Cut a rope of the world at a segment, see B2rope::cut. The new piece
+	/// is added to the world with the attachments of its vertices.
+	pub fn cut_rope(&mut self, rope: &B2ropePtr<D>, segment: usize) -> B2ropePtr<D> {
+		return private_rope::cut_rope(self, rope, segment);
+	}
+
+	/// Get the ropes of the world.
+	pub fn get_ropes(&self) -> &[B2ropePtr<D>] {
+		return &self.m_rope_list;
+	}
+
+	/// create a soft body given a definition. The world steps the soft body
+	/// after the ropes, with the velocity iterations as the solver iterations.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> This function is locked during callbacks.
+	/// </p>
+	pub fn create_soft_body(&mut self, def: &B2softBodyDef) -> B2softBodyPtr {
+		return private_soft_body::create_soft_body(self, def);
+	}
+
+	/// destroy a soft body. This function is locked during callbacks.
+	pub fn destroy_soft_body(&mut self, soft_body: B2softBodyPtr) {
+		private_soft_body::destroy_soft_body(self, soft_body);
+	}
+
+	/// Get the soft bodies of the world.
+	pub fn get_soft_bodies(&self) -> &[B2softBodyPtr] {
+		return &self.m_soft_body_list;
+	}
+
+	/// create a particle system given a definition. The world steps the
+	/// particles after the soft bodies, using the world gravity.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> This function is locked during callbacks.
+	/// </p>
+	pub fn create_particle_system(&mut self, def: &B2particleSystemDef) -> B2particleSystemPtr {
+		return private_particle::create_particle_system(self, def);
+	}
+
+	/// destroy a particle system and its particles. This function is locked
+	/// during callbacks.
+	pub fn destroy_particle_system(&mut self, system: B2particleSystemPtr) {
+		private_particle::destroy_particle_system(self, system);
+	}
+
+	/// Get the particle systems of the world.
+	pub fn get_particle_systems(&self) -> &[B2particleSystemPtr] {
+		return &self.m_particle_system_list;
+	}
+
 	/// Enable/disable sleep.
 	pub fn set_allow_sleeping(&mut self, flag: bool) {
 		private::set_allow_sleeping(self, flag);
@@ -184,6 +473,18 @@ impl<D: UserDataType> B2world<D> {
 		return self.m_continuous_physics;
 	}
 
+	/// Enable/disable speculative contacts. Contact points are then generated ahead of
+	/// time, up to the distance the bodies can close within a step, and the solver only
+	/// lets them close the gap. This stops fast dynamic bodies from tunneling through
+	/// each other at a much lower cost than bullets. Contacts begin a little early and
+	/// fast bodies passing close to a polygon corner may hit its extended face.
+	pub fn set_speculative_contacts(&mut self, flag: bool) {
+		self.m_speculative_contacts = flag;
+	}
+	pub fn get_speculative_contacts(&self) -> bool {
+		return self.m_speculative_contacts;
+	}
+
 	/// Enable/disable single stepped continuous physics. For testing.
 	pub fn set_sub_stepping(&mut self, flag: bool) {
 		self.m_sub_stepping = flag;
@@ -313,11 +614,24 @@ pub struct B2world<D: UserDataType> {
 	// These are for debugging the solver.
 	pub(crate) m_warm_starting: bool,
 	pub(crate) m_continuous_physics: bool,
+	pub(crate) m_speculative_contacts: bool,
 	pub(crate) m_sub_stepping: bool,
 
 	pub(crate) m_step_complete: bool,
 
 	pub(crate) m_profile: B2Profile,
+
+	pub(crate) m_force_field_list: Vec<B2forceFieldPtr<D>>,
+
+	pub(crate) m_rope_list: Vec<B2ropePtr<D>>,
+
+	pub(crate) m_soft_body_list: Vec<B2softBodyPtr>,
+
+	pub(crate) m_particle_system_list: Vec<B2particleSystemPtr>,
+
+	pub(crate) m_fracture_listener: Option<B2fractureListenerPtr<D>>,
+	// The breakable fixtures hit in the current step.
+	pub(crate) m_fracture_list: Vec<B2fractureImpact<D>>,
 }
 
 impl<D: UserDataType> Drop for B2world<D>
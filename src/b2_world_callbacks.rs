@@ -1,3 +1,4 @@
+use crate::b2_body::*;
 use crate::b2_collision::*;
 use crate::b2_contact::*;
 use crate::b2_fixture::*;
@@ -14,6 +15,8 @@ use crate::private::dynamics::b2_world_callbacks as private;
 pub type B2destructionListenerPtr<D> = Rc<RefCell<dyn B2destructionListener<D>>>;
 pub type B2contactFilterPtr<D> = Rc<RefCell<dyn B2contactFilter<D>>>;
 pub type B2contactListenerPtr<D> = Rc<RefCell<dyn B2contactListener<D>>>;
+pub type B2fractureListenerPtr<D> = Rc<RefCell<dyn B2fractureListener<D>>>;
+pub type B2queryPredicatePtr<D> = Rc<dyn Fn(FixturePtr<D>) -> bool>;
 
 /// Joints and fixtures are destroyed when their associated
 /// body is destroyed. Implement this listener so that you
@@ -107,6 +110,106 @@ pub struct B2contactListenerDefault;
 
 impl<D: UserDataType> B2contactListener<D> for B2contactListenerDefault {}
 
+/// Implement this class to follow the fixtures broken by impacts, see
+/// B2fixtureDef::toughness. A fixture breaks into pieces at the end of the time
+/// step in which it was hit, each piece gets its own body.
+/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+/// <strong>Warning:</strong> You cannot create/destroy Box2D entities inside these callbacks.
+/// </p>
+pub trait B2fractureListener<D: UserDataType> {
+	/// Get the user data of a body created for a piece of the broken fixture.
+	/// By default the user data of the broken body is cloned.
+	fn copy_body_user_data(&mut self, body: BodyPtr<D>) -> Option<D::Body> {
+		return body.borrow().get_user_data();
+	}
+
+	/// Get the user data of a piece of the broken fixture. By default the user
+	/// data of the broken fixture is cloned.
+	fn copy_fixture_user_data(&mut self, fixture: FixturePtr<D>) -> Option<D::Fixture> {
+		return fixture.borrow().get_user_data();
+	}
+
+	/// Called when a fixture broke, before it is destroyed. The body of the
+	/// fixture is destroyed too when it has no fixtures left.
+	fn fractured(&mut self, fixture: FixturePtr<D>, pieces: &[FixturePtr<D>]) {
+		b2_not_used(fixture);
+		b2_not_used(pieces);
+	}
+}
+
+pub struct B2fractureListenerDefault;
+
+impl<D: UserDataType> B2fractureListener<D> for B2fractureListenerDefault {}
+
+/// This holds the filtering data used by world queries and casts. The bits are
+/// tested against the fixture B2filter in the same way as the default contact filter.
+#[derive(Clone)]
+pub struct B2queryFilter<D: UserDataType> {
+	/// The collision category bits of the query.
+	pub category_bits: u16,
+
+	/// The categories that the query accepts.
+	pub mask_bits: u16,
+
+	/// Fixtures in the same non-zero group are always (positive) or never (negative) reported.
+	pub group_index: i16,
+
+	/// Report sensor fixtures.
+	pub include_sensors: bool,
+
+	/// Optional user test called before the shape test. Return false to skip the fixture.
+	pub predicate: Option<B2queryPredicatePtr<D>>,
+}
+
+impl<D: UserDataType> Default for B2queryFilter<D> {
+	/// The default query filter accepts every fixture with the default filter, sensors included.
+	fn default() -> Self {
+		return B2queryFilter::from_filter(B2filter::default());
+	}
+}
+
+impl<D: UserDataType> B2queryFilter<D> {
+	/// Build a query filter from fixture filtering data.
+	pub fn from_filter(filter: B2filter) -> Self {
+		return B2queryFilter {
+			category_bits: filter.category_bits,
+			mask_bits: filter.mask_bits,
+			group_index: filter.group_index,
+			include_sensors: true,
+			predicate: None,
+		};
+	}
+
+	/// Return true if a query using this filter should test the fixture.
+	pub fn should_query(&self, fixture: FixturePtr<D>) -> bool {
+		return private::should_query(self, fixture);
+	}
+}
+
+/// A fixture hit reported by the closest and all-hits ray and shape cast helpers.
+#[derive(Clone)]
+pub struct B2castHit<D: UserDataType> {
+	pub fixture: FixturePtr<D>,
+	/// The point of initial intersection.
+	pub point: B2vec2,
+	/// The surface normal at the point of intersection.
+	pub normal: B2vec2,
+	/// The fraction along the ray or translation.
+	pub fraction: f32,
+}
+
+/// A body pushed by an explosion. [see](crate::b2_world::B2world::explode)
+#[derive(Clone)]
+pub struct B2explosionHit<D: UserDataType> {
+	pub body: BodyPtr<D>,
+	/// The impulse applied to the body.
+	pub impulse: B2vec2,
+	/// The surface point closest to the explosion center.
+	pub point: B2vec2,
+	/// The distance from the explosion center to the point.
+	pub distance: f32,
+}
+
 /// Called for each fixture found in the query AABB.
 /// 
 /// @return false to terminate the query.
@@ -137,3 +240,26 @@ pub trait B2rayCastCallback<D:UserDataType>: FnMut(
 impl<F, D: UserDataType> B2rayCastCallback<D> for F where
 	F: FnMut(FixturePtr<D>, B2vec2, B2vec2, f32) -> f32
 {}
+
+/// Called for each fixture hit by a shape cast. You control how the shape cast
+/// proceeds by returning a f32, with the same meaning as for B2rayCastCallback:
+/// return -1: ignore this fixture and continue
+/// return 0: terminate the shape cast
+/// return fraction: clip the cast to this point
+/// return 1: don't clip the cast and continue
+/// * `fixture` - the fixture hit by the shape
+/// * `point` - the point of initial contact
+/// * `normal` - the surface normal of the fixture at the point of contact
+/// * `fraction` - the fraction of the translation at the point of contact
+/// 
+/// @return -1 to filter, 0 to terminate, fraction to clip the cast for
+/// closest hit, 1 to continue
+pub trait B2shapeCastCallback<D:UserDataType>: FnMut(
+	/*fixture:*/ FixturePtr<D>,
+	/*point:*/ B2vec2,
+	/*normal:*/ B2vec2,
+	/*fraction:*/ f32) -> f32 {}
+
+impl<F, D: UserDataType> B2shapeCastCallback<D> for F where
+	F: FnMut(FixturePtr<D>, B2vec2, B2vec2, f32) -> f32
+{}
@@ -0,0 +1,91 @@
+use crate::b2_body::*;
+use crate::b2_contact::*;
+use crate::b2_fixture::*;
+use crate::b2_joint::*;
+use crate::b2rs_common::*;
+use crate::b2rs_double_linked_list as double_linked_list;
+use crate::b2rs_linked_list as linked_list;
+
+/// Iterator over the bodies of a world. [see](crate::b2_world::B2world::bodies)
+pub struct B2bodyIter<D: UserDataType> {
+	pub(crate) inner: double_linked_list::Iter<B2body<D>>,
+}
+
+impl<D: UserDataType> Iterator for B2bodyIter<D> {
+	type Item = BodyPtr<D>;
+	fn next(&mut self) -> Option<Self::Item> {
+		return self.inner.next();
+	}
+}
+
+/// Iterator over the joints of a world. [see](crate::b2_world::B2world::joints)
+pub struct B2jointIter<D: UserDataType> {
+	pub(crate) inner: double_linked_list::Iter<dyn B2jointTraitDyn<D>>,
+}
+
+impl<D: UserDataType> Iterator for B2jointIter<D> {
+	type Item = B2jointPtr<D>;
+	fn next(&mut self) -> Option<Self::Item> {
+		return self.inner.next();
+	}
+}
+
+/// Iterator over the touching contacts of a world. [see](crate::b2_world::B2world::contacts)
+pub struct B2contactIter<D: UserDataType> {
+	pub(crate) inner: double_linked_list::Iter<dyn B2contactDynTrait<D>>,
+}
+
+impl<D: UserDataType> Iterator for B2contactIter<D> {
+	type Item = ContactPtr<D>;
+	fn next(&mut self) -> Option<Self::Item> {
+		return self.inner.find(|contact| contact.borrow().get_base().is_touching());
+	}
+}
+
+/// Iterator over the fixtures of a body. [see](B2body::fixtures)
+pub struct B2fixtureIter<D: UserDataType> {
+	pub(crate) inner: linked_list::Iter<B2fixture<D>>,
+}
+
+impl<D: UserDataType> Iterator for B2fixtureIter<D> {
+	type Item = FixturePtr<D>;
+	fn next(&mut self) -> Option<Self::Item> {
+		return self.inner.next();
+	}
+}
+
+/// Iterator over the joints attached to a body, with the body on the other side.
+/// [see](B2body::joints)
+pub struct B2bodyJointIter<D: UserDataType> {
+	pub(crate) inner: double_linked_list::Iter<B2jointEdge<D>>,
+}
+
+impl<D: UserDataType> Iterator for B2bodyJointIter<D> {
+	type Item = (B2jointPtr<D>, BodyPtr<D>);
+	fn next(&mut self) -> Option<Self::Item> {
+		return self.inner.next().map(|edge| {
+			let edge = edge.borrow();
+			(upgrade(&edge.joint), upgrade(&edge.other))
+		});
+	}
+}
+
+/// Iterator over the touching contacts of a body, with the body on the other side.
+/// [see](B2body::contacts)
+pub struct B2bodyContactIter<D: UserDataType> {
+	pub(crate) inner: double_linked_list::Iter<B2contactEdge<D>>,
+}
+
+impl<D: UserDataType> Iterator for B2bodyContactIter<D> {
+	type Item = (ContactPtr<D>, BodyPtr<D>);
+	fn next(&mut self) -> Option<Self::Item> {
+		for edge in self.inner.by_ref() {
+			let edge = edge.borrow();
+			let contact = upgrade(&edge.contact);
+			if contact.borrow().get_base().is_touching() {
+				return Some((contact, upgrade(&edge.other)));
+			}
+		}
+		return None;
+	}
+}
@@ -0,0 +1,187 @@
+use crate::b2_body::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_shape::*;
+use crate::b2_world::*;
+use crate::b2_world_callbacks::*;
+use crate::b2rs_common::UserDataType;
+use crate::private::character::b2_character_controller as private;
+
+impl<D: UserDataType> Default for B2characterControllerDef<D> {
+	fn default() -> Self {
+		return Self {
+			shape: None,
+			position: B2vec2::zero(),
+			up: B2vec2::new(0.0, 1.0),
+			max_slope_angle: 0.25 * B2_PI,
+			step_height: 0.25,
+			skin_width: 2.0 * B2_LINEAR_SLOP,
+			max_iterations: 4,
+			ground_probe_distance: 0.1,
+			filter: B2filter::default(),
+			user_data: None,
+		};
+	}
+}
+
+/// Character controller definition. The controller creates a kinematic body with a
+/// single fixture using the provided shape. Rounded shapes such as circles work best.
+#[derive(Clone)]
+pub struct B2characterControllerDef<D: UserDataType> {
+	/// The collision shape of the character, this must be set.
+	pub shape: Option<ShapeDefPtr>,
+
+	/// The initial world position of the character body.
+	pub position: B2vec2,
+
+	/// The up direction, usually opposite to gravity. Must be a unit vector.
+	pub up: B2vec2,
+
+	/// The steepest slope the character can stand on and walk up, in radians.
+	pub max_slope_angle: f32,
+
+	/// The highest ledge the character automatically steps onto, usually in meters.
+	pub step_height: f32,
+
+	/// The gap kept between the character and other fixtures, usually in meters.
+	pub skin_width: f32,
+
+	/// The maximum number of slide iterations per move.
+	pub max_iterations: i32,
+
+	/// How far below the character the ground is searched for, usually in meters.
+	pub ground_probe_distance: f32,
+
+	/// Filtering data of the character fixture. The same bits select which fixtures
+	/// block the character. Sensors never block.
+	pub filter: B2filter,
+
+	/// Use this to store application specific body data.
+	pub user_data: Option<D::Body>,
+}
+
+/// The ground the character is standing on.
+#[derive(Clone)]
+pub struct B2characterGround<D: UserDataType> {
+	/// The ground fixture.
+	pub fixture: FixturePtr<D>,
+	/// The contact point in world coordinates.
+	pub point: B2vec2,
+	/// The ground surface normal.
+	pub normal: B2vec2,
+	/// The velocity of the ground at the contact point. The character is carried along.
+	pub velocity: B2vec2,
+}
+
+/// A kinematic move-and-slide character controller. Each frame call move_and_slide with
+/// the desired displacement before B2world::step. The controller sweeps its shape through
+/// the world, slides along walls, climbs walkable slopes and steps, and then sets the
+/// velocity of its kinematic body so the step moves it to the resolved position. Because
+/// the body is kinematic, dynamic bodies are pushed by the character but never push it.
+pub struct B2characterController<D: UserDataType> {
+	pub(crate) m_world: B2worldWeakPtr<D>,
+	pub(crate) m_body: BodyPtr<D>,
+	pub(crate) m_fixture: FixturePtr<D>,
+	pub(crate) m_shape: ShapePtr,
+	pub(crate) m_query_filter: B2queryFilter<D>,
+
+	pub(crate) m_up: B2vec2,
+	pub(crate) m_min_slope_cosine: f32,
+	pub(crate) m_step_height: f32,
+	pub(crate) m_skin_width: f32,
+	pub(crate) m_max_iterations: i32,
+	pub(crate) m_ground_probe_distance: f32,
+
+	pub(crate) m_ground: Option<B2characterGround<D>>,
+	pub(crate) m_velocity: B2vec2,
+}
+
+impl<D: UserDataType> B2characterController<D> {
+	/// Create the character body in the world.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> This function is locked during callbacks.
+	/// </p>
+	pub fn new(world: B2worldPtr<D>, def: &B2characterControllerDef<D>) -> Self {
+		return private::new(world, def);
+	}
+
+	/// Move the character by the desired displacement, usually velocity * dt plus gravity.
+	/// The move is resolved against the world and the body velocity is set so that the
+	/// next B2world::step with the same dt reaches the resolved position. When standing on
+	/// a moving body the ground velocity is added.
+	///
+	/// @return the resolved displacement.
+	pub fn move_and_slide(&mut self, displacement: B2vec2, dt: f32) -> B2vec2 {
+		return private::move_and_slide(self, displacement, dt);
+	}
+
+	/// Push the character out of fixtures it overlaps, for example after teleporting or
+	/// when a platform moved into it. This moves the body immediately.
+	///
+	/// @return the correction that was applied.
+	pub fn depenetrate(&mut self) -> B2vec2 {
+		return private::depenetrate(self);
+	}
+
+	/// Get the kinematic body driven by this controller.
+	pub fn get_body(&self) -> BodyPtr<D> {
+		return self.m_body.clone();
+	}
+
+	/// Get the character fixture.
+	pub fn get_fixture(&self) -> FixturePtr<D> {
+		return self.m_fixture.clone();
+	}
+
+	/// Get the world position of the character body.
+	pub fn get_position(&self) -> B2vec2 {
+		return self.m_body.borrow().get_position();
+	}
+
+	/// Get the velocity computed by the last move, including the ground velocity.
+	pub fn get_velocity(&self) -> B2vec2 {
+		return self.m_velocity;
+	}
+
+	/// Is the character standing on walkable ground?
+	pub fn is_grounded(&self) -> bool {
+		return self.m_ground.is_some();
+	}
+
+	/// Get the ground found by the last move, if any.
+	pub fn get_ground(&self) -> Option<&B2characterGround<D>> {
+		return self.m_ground.as_ref();
+	}
+
+	/// Set the steepest walkable slope in radians.
+	pub fn set_max_slope_angle(&mut self, angle: f32) {
+		self.m_min_slope_cosine = f32::cos(angle);
+	}
+
+	/// Get the steepest walkable slope in radians.
+	pub fn get_max_slope_angle(&self) -> f32 {
+		return f32::acos(self.m_min_slope_cosine);
+	}
+
+	/// Set the maximum step height.
+	pub fn set_step_height(&mut self, height: f32) {
+		self.m_step_height = height;
+	}
+
+	/// Get the maximum step height.
+	pub fn get_step_height(&self) -> f32 {
+		return self.m_step_height;
+	}
+
+	/// Set the up direction. Must be a unit vector.
+	pub fn set_up(&mut self, up: B2vec2) {
+		b2_assert(b2_abs(up.length() - 1.0) < 10.0 * B2_EPSILON);
+		self.m_up = up;
+	}
+
+	/// Get the up direction.
+	pub fn get_up(&self) -> B2vec2 {
+		return self.m_up;
+	}
+}
@@ -0,0 +1,119 @@
+use crate::b2_body::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_world::*;
+use crate::b2rs_common::UserDataType;
+use crate::private::controllers::b2_buoyancy_controller as private;
+
+impl Default for B2buoyancyControllerDef {
+	fn default() -> Self {
+		return Self {
+			density: 2.0,
+			linear_drag: 5.0,
+			angular_drag: 0.5,
+			flow_velocity: B2vec2::zero(),
+		};
+	}
+}
+
+/// Buoyancy controller definition.
+#[derive(Clone, Copy, Debug)]
+pub struct B2buoyancyControllerDef {
+	/// The fluid density, usually in kg/m^2.
+	pub density: f32,
+
+	/// Linear drag, scales the velocity relative to the fluid into a force.
+	pub linear_drag: f32,
+
+	/// Angular drag, damps the rotation of submerged bodies.
+	pub angular_drag: f32,
+
+	/// The velocity of the fluid, usually in m/s. Use this for rivers and currents.
+	pub flow_velocity: B2vec2,
+}
+
+/// The submerged part of a body found by the last step.
+#[derive(Clone)]
+pub struct B2submergedBody<D: UserDataType> {
+	/// The submerged body.
+	pub body: BodyPtr<D>,
+	/// The submerged area, usually in m^2.
+	pub area: f32,
+	/// The centroid of the submerged area in world coordinates.
+	pub centroid: B2vec2,
+}
+
+/// A fluid volume defined by a sensor fixture. Each step it clips the polygon and circle
+/// fixtures of the dynamic bodies overlapping the fluid against the fluid shape and applies
+/// buoyancy at the submerged centroid, plus linear and angular drag relative to the flowing
+/// fluid. Call step before each B2world::step.
+///
+/// The fluid fixture must be a polygon or a circle. Circles are approximated by polygons.
+pub struct B2buoyancyController<D: UserDataType> {
+	pub(crate) m_world: B2worldWeakPtr<D>,
+	pub(crate) m_fixture: FixturePtr<D>,
+	pub(crate) m_def: B2buoyancyControllerDef,
+	pub(crate) m_submerged: Vec<B2submergedBody<D>>,
+}
+
+impl<D: UserDataType> B2buoyancyController<D> {
+	/// Create a fluid volume from a sensor fixture.
+	pub fn new(world: B2worldPtr<D>, fixture: FixturePtr<D>, def: &B2buoyancyControllerDef) -> Self {
+		return private::new(world, fixture, def);
+	}
+
+	/// Apply buoyancy and drag forces to the bodies in the fluid.
+	pub fn step(&mut self) {
+		private::step(self);
+	}
+
+	/// Get the fluid fixture.
+	pub fn get_fixture(&self) -> FixturePtr<D> {
+		return self.m_fixture.clone();
+	}
+
+	/// Get the bodies found in the fluid by the last step.
+	pub fn get_submerged_bodies(&self) -> &[B2submergedBody<D>] {
+		return &self.m_submerged;
+	}
+
+	/// Set the fluid density.
+	pub fn set_density(&mut self, density: f32) {
+		self.m_def.density = density;
+	}
+
+	/// Get the fluid density.
+	pub fn get_density(&self) -> f32 {
+		return self.m_def.density;
+	}
+
+	/// Set the linear drag.
+	pub fn set_linear_drag(&mut self, drag: f32) {
+		self.m_def.linear_drag = drag;
+	}
+
+	/// Get the linear drag.
+	pub fn get_linear_drag(&self) -> f32 {
+		return self.m_def.linear_drag;
+	}
+
+	/// Set the angular drag.
+	pub fn set_angular_drag(&mut self, drag: f32) {
+		self.m_def.angular_drag = drag;
+	}
+
+	/// Get the angular drag.
+	pub fn get_angular_drag(&self) -> f32 {
+		return self.m_def.angular_drag;
+	}
+
+	/// Set the fluid flow velocity.
+	pub fn set_flow_velocity(&mut self, velocity: B2vec2) {
+		self.m_def.flow_velocity = velocity;
+	}
+
+	/// Get the fluid flow velocity.
+	pub fn get_flow_velocity(&self) -> B2vec2 {
+		return self.m_def.flow_velocity;
+	}
+}
@@ -0,0 +1,353 @@
+use crate::b2_body::*;
+use crate::b2_joint::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2rs_common::UserDataType;
+use crate::b2_time_step::*;
+use crate::private::dynamics::joints::b2_angle_joint as private;
+
+impl<D: UserDataType> Default for B2angleJointDef<D> {
+	fn default() -> Self {
+		return Self {
+			base: B2jointDef {
+				jtype: B2jointType::EAngleJoint,
+				..Default::default()
+			},
+			reference_angle: 0.0,
+			enable_limit: false,
+			lower_angle: 0.0,
+			upper_angle: 0.0,
+			enable_motor: false,
+			motor_speed: 0.0,
+			max_motor_torque: 0.0,
+			enable_spring: false,
+			stiffness: 0.0,
+			damping: 0.0,
+		};
+	}
+}
+
+/// Angle joint definition. The joint only constrains the relative rotation of
+/// the bodies, so there are no anchor points.
+#[derive(Clone)]
+pub struct B2angleJointDef<D: UserDataType> {
+	pub base: B2jointDef<D>,
+
+	/// The body_b angle minus body_a angle in the reference state (radians).
+	pub reference_angle: f32,
+
+	/// A flag to enable joint limits.
+	pub enable_limit: bool,
+
+	/// The lower angle for the joint limit (radians).
+	pub lower_angle: f32,
+
+	/// The upper angle for the joint limit (radians).
+	pub upper_angle: f32,
+
+	/// A flag to enable the joint motor.
+	pub enable_motor: bool,
+
+	/// The desired motor speed in radians per second.
+	pub motor_speed: f32,
+
+	/// The maximum motor torque used to achieve the desired motor speed.
+	/// Usually in n-m.
+	pub max_motor_torque: f32,
+
+	/// A flag to enable the angular spring. The spring pulls the joint angle
+	/// towards zero.
+	pub enable_spring: bool,
+
+	/// The angular spring stiffness in n-m per radian.
+	pub stiffness: f32,
+
+	/// The angular spring damping in n-m-s per radian.
+	pub damping: f32,
+}
+
+impl<D: UserDataType> B2angleJointDef<D> {
+	/// initialize the bodies and reference angle using the current body angles.
+	pub fn initialize(&mut self, body_a: BodyPtr<D>, body_b: BodyPtr<D>) {
+		self.base.body_a = Some(body_a.clone());
+		self.base.body_b = Some(body_b.clone());
+		self.reference_angle = body_b.borrow().get_angle() - body_a.borrow().get_angle();
+	}
+}
+
+impl<D: UserDataType> ToDerivedJoint<D> for B2angleJoint<D> {
+	fn as_derived(&self) -> JointAsDerived<D> {
+		return JointAsDerived::EAngleJoint(self);
+	}
+	fn as_derived_mut(&mut self) -> JointAsDerivedMut<D> {
+		return JointAsDerivedMut::EAngleJoint(self);
+	}
+}
+
+/// An angle joint constrains the relative rotation of two bodies without
+/// constraining their positions. The relative rotation can be limited,
+/// driven by a motor and pulled back by a spring.
+pub struct B2angleJoint<D: UserDataType> {
+	pub(crate) base: B2joint<D>,
+
+	// Solver shared
+	pub(crate) m_reference_angle: f32,
+	pub(crate) m_spring_impulse: f32,
+	pub(crate) m_motor_impulse: f32,
+	pub(crate) m_lower_impulse: f32,
+	pub(crate) m_upper_impulse: f32,
+
+	pub(crate) m_enable_limit: bool,
+	pub(crate) m_lower_angle: f32,
+	pub(crate) m_upper_angle: f32,
+
+	pub(crate) m_enable_motor: bool,
+	pub(crate) m_motor_speed: f32,
+	pub(crate) m_max_motor_torque: f32,
+
+	pub(crate) m_enable_spring: bool,
+	pub(crate) m_stiffness: f32,
+	pub(crate) m_damping: f32,
+
+	// Solver temp
+	pub(crate) m_index_a: i32,
+	pub(crate) m_index_b: i32,
+	pub(crate) m_inv_ia: f32,
+	pub(crate) m_inv_ib: f32,
+	pub(crate) m_angle: f32,
+	pub(crate) m_axial_mass: f32,
+	pub(crate) m_spring_mass: f32,
+	pub(crate) m_bias: f32,
+	pub(crate) m_gamma: f32,
+}
+
+impl<D: UserDataType> B2angleJoint<D> {
+	/// Get the reference angle.
+	pub fn get_reference_angle(&self) -> f32 {
+		return self.m_reference_angle;
+	}
+
+	/// Get the current joint angle in radians.
+	pub fn get_joint_angle(&self) -> f32 {
+		let b_a = self.base.m_body_a.borrow();
+		let b_b = self.base.m_body_b.borrow();
+		return b_b.m_sweep.a - b_a.m_sweep.a - self.m_reference_angle;
+	}
+
+	/// Get the current joint angle speed in radians per second.
+	pub fn get_joint_speed(&self) -> f32 {
+		let b_a = self.base.m_body_a.borrow();
+		let b_b = self.base.m_body_b.borrow();
+		return b_b.m_angular_velocity - b_a.m_angular_velocity;
+	}
+
+	/// Is the joint limit enabled?
+	pub fn is_limit_enabled(&self) -> bool {
+		return self.m_enable_limit;
+	}
+
+	/// Enable/disable the joint limit.
+	pub fn enable_limit(&mut self, flag: bool) {
+		if flag != self.m_enable_limit {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_enable_limit = flag;
+			self.m_lower_impulse = 0.0;
+			self.m_upper_impulse = 0.0;
+		}
+	}
+
+	/// Get the lower joint limit in radians.
+	pub fn get_lower_limit(&self) -> f32 {
+		return self.m_lower_angle;
+	}
+
+	/// Get the upper joint limit in radians.
+	pub fn get_upper_limit(&self) -> f32 {
+		return self.m_upper_angle;
+	}
+
+	/// Set the joint limits in radians.
+	pub fn set_limits(&mut self, lower: f32, upper: f32) {
+		b2_assert(lower <= upper);
+
+		if lower != self.m_lower_angle || upper != self.m_upper_angle {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_lower_impulse = 0.0;
+			self.m_upper_impulse = 0.0;
+			self.m_lower_angle = lower;
+			self.m_upper_angle = upper;
+		}
+	}
+
+	/// Is the joint motor enabled?
+	pub fn is_motor_enabled(&self) -> bool {
+		return self.m_enable_motor;
+	}
+
+	/// Enable/disable the joint motor.
+	pub fn enable_motor(&mut self, flag: bool) {
+		if flag != self.m_enable_motor {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_enable_motor = flag;
+		}
+	}
+
+	/// Set the motor speed in radians per second.
+	pub fn set_motor_speed(&mut self, speed: f32) {
+		if speed != self.m_motor_speed {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_motor_speed = speed;
+		}
+	}
+
+	/// Get the motor speed in radians per second.
+	pub fn get_motor_speed(&self) -> f32 {
+		return self.m_motor_speed;
+	}
+
+	/// Set the maximum motor torque, usually in n-m.
+	pub fn set_max_motor_torque(&mut self, torque: f32) {
+		if torque != self.m_max_motor_torque {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_max_motor_torque = torque;
+		}
+	}
+
+	/// Get the maximum motor torque, usually in n-m.
+	pub fn get_max_motor_torque(&self) -> f32 {
+		return self.m_max_motor_torque;
+	}
+
+	/// Get the current motor torque given the inverse time step.
+	/// Unit is n*m.
+	pub fn get_motor_torque(&self, inv_dt: f32) -> f32 {
+		return inv_dt * self.m_motor_impulse;
+	}
+
+	/// Is the angular spring enabled?
+	pub fn is_spring_enabled(&self) -> bool {
+		return self.m_enable_spring;
+	}
+
+	/// Enable/disable the angular spring.
+	pub fn enable_spring(&mut self, flag: bool) {
+		if flag != self.m_enable_spring {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_enable_spring = flag;
+			self.m_spring_impulse = 0.0;
+		}
+	}
+
+	/// Set the spring stiffness in n-m per radian.
+	pub fn set_stiffness(&mut self, stiffness: f32) {
+		b2_assert(b2_is_valid(stiffness) && stiffness >= 0.0);
+		self.m_stiffness = stiffness;
+	}
+
+	/// Get the spring stiffness in n-m per radian.
+	pub fn get_stiffness(&self) -> f32 {
+		return self.m_stiffness;
+	}
+
+	/// Set the spring damping in n-m-s per radian.
+	pub fn set_damping(&mut self, damping: f32) {
+		b2_assert(b2_is_valid(damping) && damping >= 0.0);
+		self.m_damping = damping;
+	}
+
+	/// Get the spring damping in n-m-s per radian.
+	pub fn get_damping(&self) -> f32 {
+		return self.m_damping;
+	}
+
+	pub(crate) fn new(def: &B2angleJointDef<D>) -> Self {
+		b2_assert(def.lower_angle <= def.upper_angle);
+
+		return Self {
+			base: B2joint::new(&def.base),
+
+			m_reference_angle: def.reference_angle,
+			m_spring_impulse: 0.0,
+			m_motor_impulse: 0.0,
+			m_lower_impulse: 0.0,
+			m_upper_impulse: 0.0,
+
+			m_enable_limit: def.enable_limit,
+			m_lower_angle: def.lower_angle,
+			m_upper_angle: def.upper_angle,
+
+			m_enable_motor: def.enable_motor,
+			m_motor_speed: def.motor_speed,
+			m_max_motor_torque: def.max_motor_torque,
+
+			m_enable_spring: def.enable_spring,
+			m_stiffness: def.stiffness,
+			m_damping: def.damping,
+
+			m_index_a: 0,
+			m_index_b: 0,
+			m_inv_ia: 0.0,
+			m_inv_ib: 0.0,
+			m_angle: 0.0,
+			m_axial_mass: 0.0,
+			m_spring_mass: 0.0,
+			m_bias: 0.0,
+			m_gamma: 0.0,
+		};
+	}
+}
+
+impl<D: UserDataType> B2jointTraitDyn<D> for B2angleJoint<D> {
+	fn get_base(&self) -> &B2joint<D> {
+		return &self.base;
+	}
+	fn get_base_mut(&mut self) -> &mut B2joint<D> {
+		return &mut self.base;
+	}
+	fn get_anchor_a(&self) -> B2vec2 {
+		return self.base.m_body_a.borrow().get_position();
+	}
+	fn get_anchor_b(&self) -> B2vec2 {
+		return self.base.m_body_b.borrow().get_position();
+	}
+
+	/// The joint has no linear constraint, so the reaction force is zero.
+	fn get_reaction_force(&self, _inv_dt: f32) -> B2vec2 {
+		return B2vec2::zero();
+	}
+	/// Get the reaction torque given the inverse time step.
+	/// Unit is n*m.
+	fn get_reaction_torque(&self, inv_dt: f32) -> f32 {
+		return inv_dt
+			* (self.m_spring_impulse + self.m_motor_impulse + self.m_lower_impulse
+				- self.m_upper_impulse);
+	}
+	fn init_velocity_constraints(
+		&mut self,
+		data: &B2solverData,
+		positions: &[B2position],
+		velocities: &mut [B2velocity],
+	) {
+		private::init_velocity_constraints(self, data, positions, velocities);
+	}
+	fn solve_velocity_constraints(
+		&mut self,
+		data: &B2solverData,
+		velocities: &mut [B2velocity],
+	) {
+		private::solve_velocity_constraints(self, data, velocities);
+	}
+	fn solve_position_constraints(
+		&mut self,
+		data: &B2solverData,
+		positions: &mut [B2position],
+	) -> bool {
+		return private::solve_position_constraints(self, data, positions);
+	}
+}
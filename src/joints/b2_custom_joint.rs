@@ -0,0 +1,204 @@
+use crate::b2_body::*;
+use crate::b2_draw::*;
+use crate::b2_joint::*;
+use crate::b2_math::*;
+use crate::b2rs_common::UserDataType;
+use crate::b2_time_step::*;
+use crate::private::dynamics::joints::b2_custom_joint as private;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub type B2customJointPtr<D> = Rc<RefCell<dyn B2customJoint<D>>>;
+
+/// Body data of a custom joint, refreshed by the solver before
+/// init_velocity_constraints is called. The indices address the positions
+/// and velocities arrays passed to the constraint functions.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct B2customJointBodies {
+	pub index_a: usize,
+	pub index_b: usize,
+	/// Local centers of mass.
+	pub local_center_a: B2vec2,
+	pub local_center_b: B2vec2,
+	pub inv_mass_a: f32,
+	pub inv_mass_b: f32,
+	pub inv_i_a: f32,
+	pub inv_i_b: f32,
+}
+
+/// Implement this trait to add your own constraint to the solver. The joint is
+/// stepped like the built-in joints: it is solved with its island, keeps its
+/// bodies in the same island and lets them sleep together.
+pub trait B2customJoint<D: UserDataType> {
+	/// Get the anchor point on body_a in world coordinates.
+	fn get_anchor_a(&self, base: &B2joint<D>) -> B2vec2;
+
+	/// Get the anchor point on body_b in world coordinates.
+	fn get_anchor_b(&self, base: &B2joint<D>) -> B2vec2;
+
+	/// Get the reaction force on body_b at the joint anchor in Newtons.
+	fn get_reaction_force(&self, inv_dt: f32) -> B2vec2;
+
+	/// Get the reaction torque on body_b in n*m.
+	fn get_reaction_torque(&self, inv_dt: f32) -> f32;
+
+	/// Shift the origin for any points stored in world coordinates.
+	fn shift_origin(&mut self, _new_origin: B2vec2) {}
+
+	/// Debug draw this joint. By default draws the same lines as the
+	/// built-in joints.
+	fn draw(&self, base: &B2joint<D>, draw: &mut dyn B2drawTrait) {
+		private::draw_default(self, base, draw);
+	}
+
+	fn init_velocity_constraints(
+		&mut self,
+		bodies: &B2customJointBodies,
+		data: &B2solverData,
+		positions: &[B2position],
+		velocities: &mut [B2velocity],
+	);
+	fn solve_velocity_constraints(
+		&mut self,
+		bodies: &B2customJointBodies,
+		data: &B2solverData,
+		velocities: &mut [B2velocity],
+	);
+
+	// This returns true if the position errors are within tolerance.
+	fn solve_position_constraints(
+		&mut self,
+		bodies: &B2customJointBodies,
+		data: &B2solverData,
+		positions: &mut [B2position],
+	) -> bool;
+
+	/// Save hook used when the world is serialized. Return the joint state
+	/// encoded as a string; it is handed back to
+	/// B2customJointFactory::create_custom_joint on load. Joints returning
+	/// None are not saved.
+	#[cfg(feature="serde_support")]
+	fn serialize_data(&self) -> Option<String> {
+		return None;
+	}
+}
+
+impl<D: UserDataType> Default for B2customJointDef<D> {
+	fn default() -> Self {
+		return Self {
+			base: B2jointDef {
+				jtype: B2jointType::ECustomJoint,
+				..Default::default()
+			},
+			joint: None,
+		};
+	}
+}
+
+/// Custom joint definition. The joint implementation must be set before the
+/// joint is created.
+#[derive(Clone)]
+pub struct B2customJointDef<D: UserDataType> {
+	pub base: B2jointDef<D>,
+
+	/// The user constraint.
+	pub joint: Option<B2customJointPtr<D>>,
+}
+
+impl<D: UserDataType> B2customJointDef<D> {
+	/// initialize the bodies and the user constraint.
+	pub fn initialize(&mut self, body_a: BodyPtr<D>, body_b: BodyPtr<D>, joint: B2customJointPtr<D>) {
+		self.base.body_a = Some(body_a);
+		self.base.body_b = Some(body_b);
+		self.joint = Some(joint);
+	}
+}
+
+impl<D: UserDataType> ToDerivedJoint<D> for B2customJointHost<D> {
+	fn as_derived(&self) -> JointAsDerived<D> {
+		return JointAsDerived::ECustomJoint(self);
+	}
+	fn as_derived_mut(&mut self) -> JointAsDerivedMut<D> {
+		return JointAsDerivedMut::ECustomJoint(self);
+	}
+}
+
+/// The world side of a custom joint. It links the user constraint into the
+/// body joint lists and forwards the solver calls to it.
+pub struct B2customJointHost<D: UserDataType> {
+	pub(crate) base: B2joint<D>,
+	pub(crate) m_joint: B2customJointPtr<D>,
+
+	// Solver temp
+	pub(crate) m_bodies: B2customJointBodies,
+}
+
+impl<D: UserDataType> B2customJointHost<D> {
+	/// Get the user constraint.
+	pub fn get_custom_joint(&self) -> B2customJointPtr<D> {
+		return self.m_joint.clone();
+	}
+
+	pub(crate) fn new(def: &B2customJointDef<D>) -> Self {
+		return Self {
+			base: B2joint::new(&def.base),
+			m_joint: def.joint.clone().expect("B2customJointDef::joint is not set"),
+			m_bodies: B2customJointBodies::default(),
+		};
+	}
+}
+
+impl<D: UserDataType> B2jointTraitDyn<D> for B2customJointHost<D> {
+	fn get_base(&self) -> &B2joint<D> {
+		return &self.base;
+	}
+	fn get_base_mut(&mut self) -> &mut B2joint<D> {
+		return &mut self.base;
+	}
+	fn get_anchor_a(&self) -> B2vec2 {
+		return self.m_joint.borrow().get_anchor_a(&self.base);
+	}
+	fn get_anchor_b(&self) -> B2vec2 {
+		return self.m_joint.borrow().get_anchor_b(&self.base);
+	}
+	fn get_reaction_force(&self, inv_dt: f32) -> B2vec2 {
+		return self.m_joint.borrow().get_reaction_force(inv_dt);
+	}
+	fn get_reaction_torque(&self, inv_dt: f32) -> f32 {
+		return self.m_joint.borrow().get_reaction_torque(inv_dt);
+	}
+	fn shift_origin(&mut self, new_origin: B2vec2) {
+		self.m_joint.borrow_mut().shift_origin(new_origin);
+	}
+	fn draw(&self, draw: &mut dyn B2drawTrait) {
+		self.m_joint.borrow().draw(&self.base, draw);
+	}
+	fn init_velocity_constraints(
+		&mut self,
+		data: &B2solverData,
+		positions: &[B2position],
+		velocities: &mut [B2velocity],
+	) {
+		private::init_velocity_constraints(self, data, positions, velocities);
+	}
+	fn solve_velocity_constraints(
+		&mut self,
+		data: &B2solverData,
+		velocities: &mut [B2velocity],
+	) {
+		self.m_joint
+			.borrow_mut()
+			.solve_velocity_constraints(&self.m_bodies, data, velocities);
+	}
+	fn solve_position_constraints(
+		&mut self,
+		data: &B2solverData,
+		positions: &mut [B2position],
+	) -> bool {
+		return self
+			.m_joint
+			.borrow_mut()
+			.solve_position_constraints(&self.m_bodies, data, positions);
+	}
+}
@@ -0,0 +1,95 @@
+use crate::b2_body::*;
+use crate::b2_joint::*;
+use crate::b2_math::*;
+use crate::b2rs_common::UserDataType;
+use crate::b2_time_step::*;
+
+impl<D: UserDataType> Default for B2filterJointDef<D> {
+	fn default() -> Self {
+		return Self {
+			base: B2jointDef {
+				jtype: B2jointType::EFilterJoint,
+				..Default::default()
+			},
+		};
+	}
+}
+
+/// Filter joint definition. Only the bodies are used, collide_connected is
+/// always treated as false.
+#[derive(Clone)]
+pub struct B2filterJointDef<D: UserDataType> {
+	pub base: B2jointDef<D>,
+}
+
+impl<D: UserDataType> B2filterJointDef<D> {
+	/// initialize the bodies.
+	pub fn initialize(&mut self, body_a: BodyPtr<D>, body_b: BodyPtr<D>) {
+		self.base.body_a = Some(body_a);
+		self.base.body_b = Some(body_b);
+	}
+}
+
+impl<D: UserDataType> ToDerivedJoint<D> for B2filterJoint<D> {
+	fn as_derived(&self) -> JointAsDerived<D> {
+		return JointAsDerived::EFilterJoint(self);
+	}
+	fn as_derived_mut(&mut self) -> JointAsDerivedMut<D> {
+		return JointAsDerivedMut::EFilterJoint(self);
+	}
+}
+
+/// A filter joint disables collision between two bodies and has no other
+/// effect. The bodies still share an island, so they sleep together.
+pub struct B2filterJoint<D: UserDataType> {
+	pub(crate) base: B2joint<D>,
+}
+
+impl<D: UserDataType> B2filterJoint<D> {
+	pub(crate) fn new(def: &B2filterJointDef<D>) -> Self {
+		let mut base = B2joint::new(&def.base);
+		base.m_collide_connected = false;
+		return Self { base };
+	}
+}
+
+impl<D: UserDataType> B2jointTraitDyn<D> for B2filterJoint<D> {
+	fn get_base(&self) -> &B2joint<D> {
+		return &self.base;
+	}
+	fn get_base_mut(&mut self) -> &mut B2joint<D> {
+		return &mut self.base;
+	}
+	fn get_anchor_a(&self) -> B2vec2 {
+		return self.base.m_body_a.borrow().get_position();
+	}
+	fn get_anchor_b(&self) -> B2vec2 {
+		return self.base.m_body_b.borrow().get_position();
+	}
+	fn get_reaction_force(&self, _inv_dt: f32) -> B2vec2 {
+		return B2vec2::zero();
+	}
+	fn get_reaction_torque(&self, _inv_dt: f32) -> f32 {
+		return 0.0;
+	}
+	fn init_velocity_constraints(
+		&mut self,
+		_data: &B2solverData,
+		_positions: &[B2position],
+		_velocities: &mut [B2velocity],
+	) {
+	}
+	fn solve_velocity_constraints(
+		&mut self,
+		_data: &B2solverData,
+		_velocities: &mut [B2velocity],
+	) {
+	}
+	fn solve_position_constraints(
+		&mut self,
+		_data: &B2solverData,
+		_positions: &mut [B2position],
+	) -> bool {
+		return true;
+	}
+}
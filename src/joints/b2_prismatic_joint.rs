@@ -24,6 +24,15 @@ impl<D: UserDataType> Default for B2prismaticJointDef<D> {
             enable_motor: false,
             max_motor_force: 0.0,
             motor_speed: 0.0,
+            enable_spring: false,
+            stiffness: 0.0,
+            damping: 0.0,
+            rest_translation: 0.0,
+            enable_servo: false,
+            target_translation: 0.0,
+            max_servo_speed: B2_MAX_FLOAT,
+            max_servo_force: 0.0,
+            max_friction_force: 0.0,
         };
     }
 }
@@ -67,6 +76,34 @@ pub struct B2prismaticJointDef<D: UserDataType> {
 
     /// The desired motor speed in radians per second.
     pub motor_speed: f32,
+
+    /// Enable/disable the linear spring.
+    pub enable_spring: bool,
+
+    /// The spring stiffness, usually in n/m. See b2_linear_stiffness to
+    /// compute it from a frequency and damping ratio.
+    pub stiffness: f32,
+
+    /// The spring damping, usually in n*s/m.
+    pub damping: f32,
+
+    /// The joint translation the spring pulls towards, usually in meters.
+    pub rest_translation: f32,
+
+    /// Enable/disable the position servo.
+    pub enable_servo: bool,
+
+    /// The joint translation the servo drives towards, usually in meters.
+    pub target_translation: f32,
+
+    /// The maximum speed of the servo, usually in meters per second.
+    pub max_servo_speed: f32,
+
+    /// The maximum servo force, usually in n.
+    pub max_servo_force: f32,
+
+    /// The maximum joint friction force, usually in n. Zero disables friction.
+    pub max_friction_force: f32,
 }
 
 impl<D: UserDataType> B2prismaticJointDef<D> {
@@ -119,6 +156,18 @@ pub struct B2prismaticJoint<D: UserDataType> {
     pub(crate) m_motor_speed: f32,
     pub(crate) m_enable_limit: bool,
     pub(crate) m_enable_motor: bool,
+    pub(crate) m_spring_impulse: f32,
+    pub(crate) m_servo_impulse: f32,
+    pub(crate) m_friction_impulse: f32,
+    pub(crate) m_enable_spring: bool,
+    pub(crate) m_stiffness: f32,
+    pub(crate) m_damping: f32,
+    pub(crate) m_rest_translation: f32,
+    pub(crate) m_enable_servo: bool,
+    pub(crate) m_target_translation: f32,
+    pub(crate) m_max_servo_speed: f32,
+    pub(crate) m_max_servo_force: f32,
+    pub(crate) m_max_friction_force: f32,
 
     // Solver temp
     pub(crate) m_index_a: i32,
@@ -138,6 +187,9 @@ pub struct B2prismaticJoint<D: UserDataType> {
     pub(crate) m_k: B2Mat22,
     pub(crate) m_translation: f32,
     pub(crate) m_axial_mass: f32,
+    pub(crate) m_spring_mass: f32,
+    pub(crate) m_bias: f32,
+    pub(crate) m_gamma: f32,
 }
 
 impl<D: UserDataType> B2prismaticJoint<D> {
@@ -256,6 +308,136 @@ impl<D: UserDataType> B2prismaticJoint<D> {
         return inv_dt * self.m_motor_impulse;
     }
 
+    /// Is the linear spring enabled?
+    pub fn is_spring_enabled(&self) -> bool {
+        return self.m_enable_spring;
+    }
+
+    /// Enable/disable the linear spring.
+    pub fn enable_spring(&mut self, flag: bool) {
+        if flag != self.m_enable_spring {
+            self.base.m_body_a.borrow_mut().set_awake(true);
+            self.base.m_body_b.borrow_mut().set_awake(true);
+            self.m_enable_spring = flag;
+            self.m_spring_impulse = 0.0;
+        }
+    }
+
+    /// Set the spring stiffness, usually in n/m.
+    pub fn set_stiffness(&mut self, stiffness: f32) {
+        b2_assert(b2_is_valid(stiffness) && stiffness >= 0.0);
+        self.m_stiffness = stiffness;
+    }
+
+    /// Get the spring stiffness, usually in n/m.
+    pub fn get_stiffness(&self) -> f32 {
+        return self.m_stiffness;
+    }
+
+    /// Set the spring damping, usually in n*s/m.
+    pub fn set_damping(&mut self, damping: f32) {
+        b2_assert(b2_is_valid(damping) && damping >= 0.0);
+        self.m_damping = damping;
+    }
+
+    /// Get the spring damping, usually in n*s/m.
+    pub fn get_damping(&self) -> f32 {
+        return self.m_damping;
+    }
+
+    /// Set the joint translation the spring pulls towards, usually in meters.
+    pub fn set_rest_translation(&mut self, translation: f32) {
+        if translation != self.m_rest_translation {
+            self.base.m_body_a.borrow_mut().set_awake(true);
+            self.base.m_body_b.borrow_mut().set_awake(true);
+            self.m_rest_translation = translation;
+        }
+    }
+
+    /// Get the spring rest translation, usually in meters.
+    pub fn get_rest_translation(&self) -> f32 {
+        return self.m_rest_translation;
+    }
+
+    /// Is the position servo enabled?
+    pub fn is_servo_enabled(&self) -> bool {
+        return self.m_enable_servo;
+    }
+
+    /// Enable/disable the position servo.
+    pub fn enable_servo(&mut self, flag: bool) {
+        if flag != self.m_enable_servo {
+            self.base.m_body_a.borrow_mut().set_awake(true);
+            self.base.m_body_b.borrow_mut().set_awake(true);
+            self.m_enable_servo = flag;
+            self.m_servo_impulse = 0.0;
+        }
+    }
+
+    /// Set the joint translation the servo drives towards, usually in meters.
+    pub fn set_target_translation(&mut self, translation: f32) {
+        if translation != self.m_target_translation {
+            self.base.m_body_a.borrow_mut().set_awake(true);
+            self.base.m_body_b.borrow_mut().set_awake(true);
+            self.m_target_translation = translation;
+        }
+    }
+
+    /// Get the servo target translation, usually in meters.
+    pub fn get_target_translation(&self) -> f32 {
+        return self.m_target_translation;
+    }
+
+    /// Set the maximum servo speed, usually in meters per second.
+    pub fn set_max_servo_speed(&mut self, speed: f32) {
+        b2_assert(b2_is_valid(speed) && speed >= 0.0);
+        self.m_max_servo_speed = speed;
+    }
+
+    /// Get the maximum servo speed, usually in meters per second.
+    pub fn get_max_servo_speed(&self) -> f32 {
+        return self.m_max_servo_speed;
+    }
+
+    /// Set the maximum servo force, usually in n.
+    pub fn set_max_servo_force(&mut self, force: f32) {
+        if force != self.m_max_servo_force {
+            self.base.m_body_a.borrow_mut().set_awake(true);
+            self.base.m_body_b.borrow_mut().set_awake(true);
+            self.m_max_servo_force = force;
+        }
+    }
+
+    /// Get the maximum servo force, usually in n.
+    pub fn get_max_servo_force(&self) -> f32 {
+        return self.m_max_servo_force;
+    }
+
+    /// Get the current servo force given the inverse time step, usually in n.
+    pub fn get_servo_force(&self, inv_dt: f32) -> f32 {
+        return inv_dt * self.m_servo_impulse;
+    }
+
+    /// Set the maximum joint friction force, usually in n.
+    pub fn set_max_friction_force(&mut self, force: f32) {
+        b2_assert(b2_is_valid(force) && force >= 0.0);
+        if force != self.m_max_friction_force {
+            self.base.m_body_a.borrow_mut().set_awake(true);
+            self.base.m_body_b.borrow_mut().set_awake(true);
+            self.m_max_friction_force = force;
+        }
+    }
+
+    /// Get the maximum joint friction force, usually in n.
+    pub fn get_max_friction_force(&self) -> f32 {
+        return self.m_max_friction_force;
+    }
+
+    /// Get the current joint friction force given the inverse time step, usually in n.
+    pub fn get_friction_force(&self, inv_dt: f32) -> f32 {
+        return inv_dt * self.m_friction_impulse;
+    }
+
     pub(crate) fn new(def: &B2prismaticJointDef<D>) -> Self {
         return private::new(def);
     }
@@ -287,7 +469,9 @@ impl<D: UserDataType> B2jointTraitDyn<D> for B2prismaticJoint<D> {
     fn get_reaction_force(&self, inv_dt: f32) -> B2vec2 {
         return inv_dt
             * (self.m_impulse.x * self.m_perp
-                + (self.m_motor_impulse + self.m_lower_impulse - self.m_upper_impulse) * self.m_axis);
+                + (self.m_spring_impulse + self.m_friction_impulse + self.m_servo_impulse
+                    + self.m_motor_impulse + self.m_lower_impulse - self.m_upper_impulse)
+                    * self.m_axis);
     }
 
     fn get_reaction_torque(&self, inv_dt: f32) -> f32 {
@@ -23,6 +23,15 @@ impl<D: UserDataType> Default for B2revoluteJointDef<D> {
 			motor_speed: 0.0,
 			enable_limit: false,
 			enable_motor: false,
+			enable_spring: false,
+			stiffness: 0.0,
+			damping: 0.0,
+			rest_angle: 0.0,
+			enable_servo: false,
+			target_angle: 0.0,
+			max_servo_speed: B2_MAX_FLOAT,
+			max_servo_torque: 0.0,
+			max_friction_torque: 0.0,
 		};
 	}
 }
@@ -68,6 +77,34 @@ pub struct B2revoluteJointDef<D: UserDataType> {
 	/// The maximum motor torque used to achieve the desired motor speed.
 	/// Usually in n-m.
 	pub max_motor_torque: f32,
+
+	/// A flag to enable the angular spring.
+	pub enable_spring: bool,
+
+	/// The spring stiffness in n-m per radian. See b2_angular_stiffness to
+	/// compute it from a frequency and damping ratio.
+	pub stiffness: f32,
+
+	/// The spring damping in n-m-s per radian.
+	pub damping: f32,
+
+	/// The joint angle the spring pulls towards (radians).
+	pub rest_angle: f32,
+
+	/// A flag to enable the position servo.
+	pub enable_servo: bool,
+
+	/// The joint angle the servo drives towards (radians).
+	pub target_angle: f32,
+
+	/// The maximum speed of the servo in radians per second.
+	pub max_servo_speed: f32,
+
+	/// The maximum servo torque, usually in n-m.
+	pub max_servo_torque: f32,
+
+	/// The maximum joint friction torque, usually in n-m. Zero disables friction.
+	pub max_friction_torque: f32,
 }
 
 impl<D: UserDataType> B2revoluteJointDef<D> {
@@ -106,6 +143,9 @@ pub struct B2revoluteJoint<D: UserDataType> {
 	pub(crate) m_motor_impulse: f32,
 	pub(crate) m_lower_impulse: f32,
 	pub(crate) m_upper_impulse: f32,
+	pub(crate) m_spring_impulse: f32,
+	pub(crate) m_servo_impulse: f32,
+	pub(crate) m_friction_impulse: f32,
 
 	pub(crate) m_enable_motor: bool,
 	pub(crate) m_max_motor_torque: f32,
@@ -116,6 +156,18 @@ pub struct B2revoluteJoint<D: UserDataType> {
 	pub(crate) m_lower_angle: f32,
 	pub(crate) m_upper_angle: f32,
 
+	pub(crate) m_enable_spring: bool,
+	pub(crate) m_stiffness: f32,
+	pub(crate) m_damping: f32,
+	pub(crate) m_rest_angle: f32,
+
+	pub(crate) m_enable_servo: bool,
+	pub(crate) m_target_angle: f32,
+	pub(crate) m_max_servo_speed: f32,
+	pub(crate) m_max_servo_torque: f32,
+
+	pub(crate) m_max_friction_torque: f32,
+
 	// Solver temp
 	pub(crate) m_index_a: i32,
 	pub(crate) m_index_b: i32,
@@ -130,6 +182,9 @@ pub struct B2revoluteJoint<D: UserDataType> {
 	pub(crate) m_k: B2Mat22,
 	pub(crate) m_angle: f32,
 	pub(crate) m_axial_mass: f32,
+	pub(crate) m_spring_mass: f32,
+	pub(crate) m_bias: f32,
+	pub(crate) m_gamma: f32,
 
 }
 
@@ -250,6 +305,138 @@ impl<D: UserDataType> B2revoluteJoint<D> {
 		return inv_dt * self.m_motor_impulse;
 	}
 
+	/// Is the angular spring enabled?
+	pub fn is_spring_enabled(&self) -> bool {
+		return self.m_enable_spring;
+	}
+
+	/// Enable/disable the angular spring.
+	pub fn enable_spring(&mut self, flag: bool) {
+		if flag != self.m_enable_spring {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_enable_spring = flag;
+			self.m_spring_impulse = 0.0;
+		}
+	}
+
+	/// Set the spring stiffness in n-m per radian.
+	pub fn set_stiffness(&mut self, stiffness: f32) {
+		b2_assert(b2_is_valid(stiffness) && stiffness >= 0.0);
+		self.m_stiffness = stiffness;
+	}
+
+	/// Get the spring stiffness in n-m per radian.
+	pub fn get_stiffness(&self) -> f32 {
+		return self.m_stiffness;
+	}
+
+	/// Set the spring damping in n-m-s per radian.
+	pub fn set_damping(&mut self, damping: f32) {
+		b2_assert(b2_is_valid(damping) && damping >= 0.0);
+		self.m_damping = damping;
+	}
+
+	/// Get the spring damping in n-m-s per radian.
+	pub fn get_damping(&self) -> f32 {
+		return self.m_damping;
+	}
+
+	/// Set the joint angle the spring pulls towards in radians.
+	pub fn set_rest_angle(&mut self, angle: f32) {
+		if angle != self.m_rest_angle {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_rest_angle = angle;
+		}
+	}
+
+	/// Get the spring rest angle in radians.
+	pub fn get_rest_angle(&self) -> f32 {
+		return self.m_rest_angle;
+	}
+
+	/// Is the position servo enabled?
+	pub fn is_servo_enabled(&self) -> bool {
+		return self.m_enable_servo;
+	}
+
+	/// Enable/disable the position servo.
+	pub fn enable_servo(&mut self, flag: bool) {
+		if flag != self.m_enable_servo {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_enable_servo = flag;
+			self.m_servo_impulse = 0.0;
+		}
+	}
+
+	/// Set the joint angle the servo drives towards in radians.
+	pub fn set_target_angle(&mut self, angle: f32) {
+		if angle != self.m_target_angle {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_target_angle = angle;
+		}
+	}
+
+	/// Get the servo target angle in radians.
+	pub fn get_target_angle(&self) -> f32 {
+		return self.m_target_angle;
+	}
+
+	/// Set the maximum servo speed in radians per second.
+	pub fn set_max_servo_speed(&mut self, speed: f32) {
+		b2_assert(b2_is_valid(speed) && speed >= 0.0);
+		self.m_max_servo_speed = speed;
+	}
+
+	/// Get the maximum servo speed in radians per second.
+	pub fn get_max_servo_speed(&self) -> f32 {
+		return self.m_max_servo_speed;
+	}
+
+	/// Set the maximum servo torque, usually in n-m.
+	pub fn set_max_servo_torque(&mut self, torque: f32) {
+		if torque != self.m_max_servo_torque {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_max_servo_torque = torque;
+		}
+	}
+
+	/// Get the maximum servo torque, usually in n-m.
+	pub fn get_max_servo_torque(&self) -> f32 {
+		return self.m_max_servo_torque;
+	}
+
+	/// Get the current servo torque given the inverse time step.
+	/// Unit is n*m.
+	pub fn get_servo_torque(&self, inv_dt: f32) -> f32 {
+		return inv_dt * self.m_servo_impulse;
+	}
+
+	/// Set the maximum joint friction torque, usually in n-m.
+	pub fn set_max_friction_torque(&mut self, torque: f32) {
+		b2_assert(b2_is_valid(torque) && torque >= 0.0);
+		if torque != self.m_max_friction_torque {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_max_friction_torque = torque;
+		}
+	}
+
+	/// Get the maximum joint friction torque, usually in n-m.
+	pub fn get_max_friction_torque(&self) -> f32 {
+		return self.m_max_friction_torque;
+	}
+
+	/// Get the current joint friction torque given the inverse time step.
+	/// Unit is n*m.
+	pub fn get_friction_torque(&self, inv_dt: f32) -> f32 {
+		return inv_dt * self.m_friction_impulse;
+	}
+
 	pub(crate) fn new(def: &B2revoluteJointDef<D>) -> Self {
 		return Self {
 			base: B2joint::new(&def.base),
@@ -261,6 +448,9 @@ impl<D: UserDataType> B2revoluteJoint<D> {
 			m_motor_impulse : 0.0,
 			m_lower_impulse : 0.0,
 			m_upper_impulse : 0.0,
+			m_spring_impulse: 0.0,
+			m_servo_impulse: 0.0,
+			m_friction_impulse: 0.0,
 		
 			m_enable_motor: def.enable_motor,
 			m_max_motor_torque: def.max_motor_torque,
@@ -271,6 +461,18 @@ impl<D: UserDataType> B2revoluteJoint<D> {
 			m_lower_angle: def.lower_angle,
 			m_upper_angle: def.upper_angle,
 
+			m_enable_spring: def.enable_spring,
+			m_stiffness: def.stiffness,
+			m_damping: def.damping,
+			m_rest_angle: def.rest_angle,
+
+			m_enable_servo: def.enable_servo,
+			m_target_angle: def.target_angle,
+			m_max_servo_speed: def.max_servo_speed,
+			m_max_servo_torque: def.max_servo_torque,
+
+			m_max_friction_torque: def.max_friction_torque,
+
 			m_index_a: 0,
 			m_index_b: 0,
 			m_r_a: B2vec2::zero(),
@@ -283,6 +485,9 @@ impl<D: UserDataType> B2revoluteJoint<D> {
 			m_inv_ib: 0.0,
 			m_k: B2Mat22::zero(),
 			m_angle: 0.0,
+			m_spring_mass: 0.0,
+			m_bias: 0.0,
+			m_gamma: 0.0,
 		}
 	}
 }
@@ -323,7 +528,9 @@ impl<D: UserDataType> B2jointTraitDyn<D> for B2revoluteJoint<D> {
 	/// Get the reaction torque due to the joint limit given the inverse time step.
 	/// Unit is n*m.
 	fn get_reaction_torque(&self, inv_dt: f32) -> f32 {
-		return inv_dt * (self.m_motor_impulse + self.m_lower_impulse - self.m_upper_impulse);
+		return inv_dt
+			* (self.m_spring_impulse + self.m_friction_impulse + self.m_servo_impulse + self.m_motor_impulse
+				+ self.m_lower_impulse - self.m_upper_impulse);
 	}
 	fn init_velocity_constraints(
 		&mut self,
@@ -25,6 +25,7 @@ impl<D: UserDataType> Default for B2wheelJointDef<D> {
 			motor_speed: 0.0,
 			stiffness: 0.0,
 			damping: 0.0,
+			max_friction_torque: 0.0,
 		};
 	}
 }
@@ -71,6 +72,9 @@ pub struct B2wheelJointDef<D: UserDataType> {
 
 	/// Suspension damping. Typically in units of n*s/m.
 	pub damping: f32,
+
+	/// The maximum axle friction torque, usually in n-m. Zero disables friction.
+	pub max_friction_torque: f32,
 }
 
 impl<D: UserDataType> B2wheelJointDef<D> {
@@ -115,6 +119,7 @@ pub struct B2wheelJoint<D: UserDataType> {
 	pub(crate) m_impulse: f32,
 	pub(crate) m_motor_impulse: f32,
 	pub(crate) m_spring_impulse: f32,
+	pub(crate) m_friction_impulse: f32,
 
 	pub(crate) m_lower_impulse: f32,
 	pub(crate) m_upper_impulse: f32,
@@ -131,6 +136,8 @@ pub struct B2wheelJoint<D: UserDataType> {
 	pub(crate) m_stiffness: f32,
 	pub(crate) m_damping: f32,
 
+	pub(crate) m_max_friction_torque: f32,
+
 	// Solver temp
 	pub(crate) m_index_a: i32,
 	pub(crate) m_index_b: i32,
@@ -150,6 +157,7 @@ pub struct B2wheelJoint<D: UserDataType> {
 
 	pub(crate) m_mass: f32,
 	pub(crate) m_motor_mass: f32,
+	pub(crate) m_friction_mass: f32,
 	pub(crate) m_axial_mass: f32,
 	pub(crate) m_spring_mass: f32,
 
@@ -326,6 +334,26 @@ impl<D: UserDataType> B2wheelJoint<D> {
 		return self.m_damping;
 	}
 
+	/// Set the maximum axle friction torque, usually in n-m.
+	pub fn set_max_friction_torque(&mut self, torque: f32) {
+		b2_assert(b2_is_valid(torque) && torque >= 0.0);
+		if torque != self.m_max_friction_torque {
+			self.base.m_body_a.borrow_mut().set_awake(true);
+			self.base.m_body_b.borrow_mut().set_awake(true);
+			self.m_max_friction_torque = torque;
+		}
+	}
+
+	/// Get the maximum axle friction torque, usually in n-m.
+	pub fn get_max_friction_torque(&self) -> f32 {
+		return self.m_max_friction_torque;
+	}
+
+	/// Get the current axle friction torque given the inverse time step, usually in n-m.
+	pub fn get_friction_torque(&self, inv_dt: f32) -> f32 {
+		return inv_dt * self.m_friction_impulse;
+	}
+
 	pub(crate) fn new(def: &B2wheelJointDef<D>) -> Self {
 		return Self {
 			base: B2joint::new(&def.base),
@@ -341,6 +369,9 @@ impl<D: UserDataType> B2wheelJoint<D> {
 			m_motor_impulse : 0.0,
 			m_spring_mass : 0.0,
 			m_spring_impulse : 0.0,
+			m_friction_mass : 0.0,
+			m_friction_impulse : 0.0,
+			m_max_friction_torque : def.max_friction_torque,
 
 			m_axial_mass : 0.0,
 			m_lower_impulse : 0.0,
@@ -409,7 +440,7 @@ impl<D: UserDataType> B2jointTraitDyn<D> for B2wheelJoint<D> {
 	}
 
 	fn get_reaction_torque(&self, inv_dt: f32) -> f32 {
-		return inv_dt * self.m_motor_impulse;
+		return inv_dt * (self.m_motor_impulse + self.m_friction_impulse);
 	}
 
 	fn init_velocity_constraints(
@@ -1,7 +1,11 @@
+pub mod serialize_b2_angle_joint;
+pub mod serialize_b2_custom_joint;
 pub mod serialize_b2_distance_joint;
+pub mod serialize_b2_filter_joint;
 pub mod serialize_b2_friction_joint;
 pub mod serialize_b2_gear_joint;
 pub mod serialize_b2_motor_joint;
+pub mod serialize_b2_mouse_joint;
 pub mod serialize_b2_prismatic_joint;
 pub mod serialize_b2_pulley_joint;
 pub mod serialize_b2_revolute_joint;
@@ -0,0 +1,152 @@
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+
+use serde::de::DeserializeSeed;
+use std::fmt;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use crate::b2_body::*;
+use crate::b2rs_common::UserDataType;
+
+use crate::joints::b2_custom_joint::*;
+use crate::serialize::serialize_b2_joint::*;
+
+use strum::VariantNames;
+use strum_macros::EnumVariantNames;
+
+/// Load hook for custom joints. Pass it to B2worldDeserializeContext to
+/// restore the custom joints of a saved world.
+pub trait B2customJointFactory<D: UserDataType> {
+    /// Recreate a joint from the string returned by
+    /// B2customJoint::serialize_data. Returning None fails the load.
+    fn create_custom_joint(&self, data: &str) -> Option<B2customJointPtr<D>>;
+}
+
+pub type B2customJointFactoryPtr<D> = Rc<dyn B2customJointFactory<D>>;
+
+pub(crate) trait B2customJointToDef<D: UserDataType> {
+    fn get_def(&self) -> B2customJointDef<D>;
+}
+
+impl<D: UserDataType> B2customJointToDef<D> for B2customJointHost<D> {
+    fn get_def(&self) -> B2customJointDef<D> {
+        return B2customJointDef {
+            base: self.base.get_def(),
+            joint: Some(self.m_joint.clone()),
+        };
+    }
+}
+
+impl<D: UserDataType> Serialize for B2customJointDef<D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let data = self
+            .joint
+            .as_ref()
+            .and_then(|j| j.borrow().serialize_data())
+            .unwrap_or_default();
+
+        let mut state = serializer.serialize_struct("B2customJointDef", 2)?;
+        state.serialize_field("base", &self.base)?;
+        state.serialize_field("data", &data)?;
+        state.end()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct B2customJointDefContext<D: UserDataType> {
+    pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
+    pub(crate) m_custom_joint_factory: Option<B2customJointFactoryPtr<D>>,
+}
+
+impl<'de, U: UserDataType> DeserializeSeed<'de> for B2customJointDefContext<U> {
+    type Value = B2customJointDef<U>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(field_identifier, rename_all = "lowercase")]
+        #[derive(EnumVariantNames)]
+        #[allow(non_camel_case_types)]
+        enum Field {
+            base,
+            data,
+        }
+
+        struct B2customJointDefVisitor<D: UserDataType>(B2customJointDefContext<D>);
+
+        impl<U: UserDataType> B2customJointDefVisitor<U> {
+            fn create_joint<E: de::Error>(&self, data: &str) -> Result<B2customJointPtr<U>, E> {
+                let factory = self
+                    .0
+                    .m_custom_joint_factory
+                    .as_ref()
+                    .ok_or_else(|| de::Error::custom("custom joint found but no B2customJointFactory is set"))?;
+                return factory
+                    .create_custom_joint(data)
+                    .ok_or_else(|| de::Error::custom("B2customJointFactory failed to create a joint"));
+            }
+        }
+
+        impl<'de, U: UserDataType> Visitor<'de> for B2customJointDefVisitor<U> {
+            type Value = B2customJointDef<U>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct B2customJointDef")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let base = seq
+                    .next_element_seed(B2jointDefVisitorContext {
+                        m_body_array: self.0.m_body_array.clone(),
+                    })?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let data: String = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                Ok(B2customJointDef {
+                    base,
+                    joint: Some(self.create_joint(&data)?),
+                })
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut joint_def = B2customJointDef::default();
+                let mut data: Option<String> = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::base => {
+                            joint_def.base = map.next_value_seed(B2jointDefVisitorContext {
+                                m_body_array: self.0.m_body_array.clone(),
+                            })?;
+                        }
+                        Field::data => {
+                            data = Some(map.next_value()?);
+                        }
+                    }
+                }
+
+                let data = data.ok_or_else(|| de::Error::missing_field("data"))?;
+                joint_def.joint = Some(self.create_joint(&data)?);
+
+                Ok(joint_def)
+            }
+        }
+
+        deserializer.deserialize_struct("B2customJointDef", Field::VARIANTS, B2customJointDefVisitor(self))
+    }
+}
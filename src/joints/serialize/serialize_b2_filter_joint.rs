@@ -0,0 +1,107 @@
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+
+use serde::de::DeserializeSeed;
+use std::fmt;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use crate::b2_body::*;
+use crate::b2rs_common::UserDataType;
+
+use crate::joints::b2_filter_joint::*;
+use crate::serialize::serialize_b2_joint::*;
+
+use strum::VariantNames;
+use strum_macros::EnumVariantNames;
+
+pub(crate) trait B2filterJointToDef<D: UserDataType> {
+    fn get_def(&self) -> B2filterJointDef<D>;
+}
+
+impl<D: UserDataType> B2filterJointToDef<D> for B2filterJoint<D> {
+    fn get_def(&self) -> B2filterJointDef<D> {
+        return B2filterJointDef {
+            base: self.base.get_def(),
+        };
+    }
+}
+
+impl<D: UserDataType> Serialize for B2filterJointDef<D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("B2filterJointDef", 1)?;
+        state.serialize_field("base", &self.base)?;
+        state.end()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct B2filterJointDefContext<D: UserDataType> {
+    pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
+}
+
+impl<'de, U: UserDataType> DeserializeSeed<'de> for B2filterJointDefContext<U> {
+    type Value = B2filterJointDef<U>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(field_identifier, rename_all = "lowercase")]
+        #[derive(EnumVariantNames)]
+        #[allow(non_camel_case_types)]
+        enum Field {
+            base,
+        }
+
+        struct B2filterJointDefVisitor<D: UserDataType>(B2filterJointDefContext<D>);
+
+        impl<'de, U: UserDataType> Visitor<'de> for B2filterJointDefVisitor<U> {
+            type Value = B2filterJointDef<U>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct B2filterJointDef")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let joint_def = B2filterJointDef {
+                    base: seq.next_element_seed(B2jointDefVisitorContext {
+                        m_body_array: self.0.m_body_array.clone(),
+                    })?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+                };
+
+                Ok(joint_def)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut joint_def = B2filterJointDef::default();
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::base => {
+                            joint_def.base = map.next_value_seed(B2jointDefVisitorContext {
+                                m_body_array: self.0.m_body_array.clone(),
+                            })?;
+                        }
+                    }
+                }
+
+                Ok(joint_def)
+            }
+        }
+
+        deserializer.deserialize_struct("B2filterJointDef", Field::VARIANTS, B2filterJointDefVisitor(self))
+    }
+}
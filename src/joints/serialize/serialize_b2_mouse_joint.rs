@@ -0,0 +1,216 @@
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+
+use serde::de::DeserializeSeed;
+use std::fmt;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::b2_body::*;
+use crate::b2_joint::*;
+use crate::b2_math::*;
+use crate::b2_world::*;
+use crate::b2rs_common::UserDataType;
+
+use crate::joints::b2_mouse_joint::*;
+use crate::serialize::serialize_b2_joint::*;
+
+use strum::VariantNames;
+use strum_macros::EnumVariantNames;
+
+// A mouse joint as saved in a world. The target moves during a drag, so the
+// anchor on the body is saved as well.
+pub(crate) struct B2mouseJointState<D: UserDataType> {
+    pub(crate) def: B2mouseJointDef<D>,
+    pub(crate) local_anchor_b: Option<B2vec2>,
+}
+
+pub(crate) trait B2mouseJoinToDef<D: UserDataType> {
+    fn get_def(&self) -> B2mouseJointDef<D>;
+    fn get_state(&self) -> B2mouseJointState<D>;
+}
+
+impl<D: UserDataType> B2mouseJoinToDef<D> for B2mouseJoint<D> {
+    fn get_def(&self) -> B2mouseJointDef<D> {
+        return B2mouseJointDef {
+            base: self.base.get_def(),
+            target: self.m_target_a,
+            max_force: self.m_max_force,
+            stiffness: self.m_stiffness,
+            damping: self.m_damping,
+        };
+    }
+
+    fn get_state(&self) -> B2mouseJointState<D> {
+        return B2mouseJointState {
+            def: self.get_def(),
+            local_anchor_b: Some(self.m_local_anchor_b),
+        };
+    }
+}
+
+impl<D: UserDataType> Serialize for B2mouseJointDef<D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("B2mouseJointDef", 5)?;
+        state.serialize_field("base", &self.base)?;
+        state.serialize_field("target", &self.target)?;
+        state.serialize_field("max_force", &self.max_force)?;
+        state.serialize_field("stiffness", &self.stiffness)?;
+        state.serialize_field("damping", &self.damping)?;
+        state.end()
+    }
+}
+
+impl<D: UserDataType> Serialize for B2mouseJointState<D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("B2mouseJointDef", 6)?;
+        state.serialize_field("base", &self.def.base)?;
+        state.serialize_field("target", &self.def.target)?;
+        state.serialize_field("max_force", &self.def.max_force)?;
+        state.serialize_field("stiffness", &self.def.stiffness)?;
+        state.serialize_field("damping", &self.def.damping)?;
+        state.serialize_field("local_anchor_b", &self.local_anchor_b)?;
+        state.end()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct B2mouseJointDefContext<D: UserDataType> {
+    pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
+}
+
+impl<'de, U: UserDataType> DeserializeSeed<'de> for B2mouseJointDefContext<U> {
+    type Value = B2mouseJointState<U>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(field_identifier, rename_all = "lowercase")]
+        #[derive(EnumVariantNames)]
+        #[allow(non_camel_case_types)]
+        enum Field {
+            base,
+            target,
+            max_force,
+            stiffness,
+            damping,
+            local_anchor_b,
+        }
+
+        struct B2mouseJointDefVisitor<D: UserDataType>(B2mouseJointDefContext<D>);
+
+        impl<'de, U: UserDataType> Visitor<'de> for B2mouseJointDefVisitor<U> {
+            type Value = B2mouseJointState<U>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct B2mouseJointDef")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let joint_def = B2mouseJointDef {
+                    base: seq
+                        .next_element_seed(B2jointDefVisitorContext {
+                            m_body_array: self.0.m_body_array.clone(),
+                        })?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+
+                    target: seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+
+                    max_force: seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+
+                    stiffness: seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+
+                    damping: seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+                };
+
+                let local_anchor_b: Option<B2vec2> = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+                Ok(B2mouseJointState {
+                    def: joint_def,
+                    local_anchor_b,
+                })
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut joint_def = B2mouseJointDef::default();
+                let mut local_anchor_b: Option<B2vec2> = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::base => {
+                            joint_def.base = map.next_value_seed(B2jointDefVisitorContext {
+                                m_body_array: self.0.m_body_array.clone(),
+                            })?;
+                        }
+                        Field::target => {
+                            joint_def.target = map.next_value()?;
+                        }
+                        Field::max_force => {
+                            joint_def.max_force = map.next_value()?;
+                        }
+                        Field::stiffness => {
+                            joint_def.stiffness = map.next_value()?;
+                        }
+                        Field::damping => {
+                            joint_def.damping = map.next_value()?;
+                        }
+                        Field::local_anchor_b => {
+                            local_anchor_b = map.next_value()?;
+                        }
+                    }
+                }
+
+                Ok(B2mouseJointState {
+                    def: joint_def,
+                    local_anchor_b,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct(
+            "B2mouseJointDef",
+            Field::VARIANTS,
+            B2mouseJointDefVisitor(self),
+        )
+    }
+}
+
+// Create the joint and put the anchor back where the drag left it.
+pub(crate) fn create_mouse_joint<D: UserDataType>(
+    world: &B2worldPtr<D>,
+    state: B2mouseJointState<D>,
+) -> B2jointPtr<D> {
+    let joint = world
+        .borrow_mut()
+        .create_joint(&B2JointDefEnum::MouseJoint(state.def));
+    if let Some(local_anchor_b) = state.local_anchor_b {
+        if let JointAsDerivedMut::EMouseJoint(mouse) = joint.borrow_mut().as_derived_mut() {
+            mouse.m_local_anchor_b = local_anchor_b;
+        }
+    }
+    return joint;
+}
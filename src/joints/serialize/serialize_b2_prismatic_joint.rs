@@ -34,6 +34,15 @@ impl<D: UserDataType> B2prismaticJoinToDef<D> for B2prismaticJoint<D> {
             enable_motor: self.m_enable_motor,
             motor_speed: self.m_motor_speed,
             max_motor_force: self.m_max_motor_force,
+            enable_spring: self.m_enable_spring,
+            stiffness: self.m_stiffness,
+            damping: self.m_damping,
+            rest_translation: self.m_rest_translation,
+            enable_servo: self.m_enable_servo,
+            target_translation: self.m_target_translation,
+            max_servo_speed: self.m_max_servo_speed,
+            max_servo_force: self.m_max_servo_force,
+            max_friction_force: self.m_max_friction_force,
         };
     }
 }
@@ -43,7 +52,7 @@ impl<D: UserDataType> Serialize for B2prismaticJointDef<D> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("B2prismaticJointDef", 11)?;
+        let mut state = serializer.serialize_struct("B2prismaticJointDef", 20)?;
         state.serialize_field("base", &self.base)?;
         state.serialize_field("local_anchor_a", &self.local_anchor_a)?;
         state.serialize_field("local_anchor_b", &self.local_anchor_b)?;
@@ -55,6 +64,15 @@ impl<D: UserDataType> Serialize for B2prismaticJointDef<D> {
         state.serialize_field("enable_motor", &self.enable_motor)?;
         state.serialize_field("motor_speed", &self.motor_speed)?;
         state.serialize_field("max_motor_force", &self.max_motor_force)?;
+        state.serialize_field("enable_spring", &self.enable_spring)?;
+        state.serialize_field("stiffness", &self.stiffness)?;
+        state.serialize_field("damping", &self.damping)?;
+        state.serialize_field("rest_translation", &self.rest_translation)?;
+        state.serialize_field("enable_servo", &self.enable_servo)?;
+        state.serialize_field("target_translation", &self.target_translation)?;
+        state.serialize_field("max_servo_speed", &self.max_servo_speed)?;
+        state.serialize_field("max_servo_force", &self.max_servo_force)?;
+        state.serialize_field("max_friction_force", &self.max_friction_force)?;
         state.end()
     }
 }
@@ -62,6 +80,7 @@ impl<D: UserDataType> Serialize for B2prismaticJointDef<D> {
 #[derive(Clone)]
 pub(crate) struct B2prismaticJointDefContext<D: UserDataType> {
     pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
+    pub(crate) m_version: u32,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2prismaticJointDefContext<U> {
@@ -87,6 +106,15 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2prismaticJointDefContext<U
             enable_motor,
             motor_speed,
             max_motor_force,
+            enable_spring,
+            stiffness,
+            damping,
+            rest_translation,
+            enable_servo,
+            target_translation,
+            max_servo_speed,
+            max_servo_force,
+            max_friction_force,
         }
 
         struct B2prismaticJointDefVisitor<D: UserDataType>(B2prismaticJointDefContext<D>);
@@ -102,7 +130,7 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2prismaticJointDefContext<U
             where
                 V: SeqAccess<'de>,
             {
-                let joint_def = B2prismaticJointDef {
+                let mut joint_def = B2prismaticJointDef {
                     base: seq
                         .next_element_seed(B2jointDefVisitorContext {
                             m_body_array: self.0.m_body_array.clone(),
@@ -148,8 +176,41 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2prismaticJointDefContext<U
                     max_motor_force: seq
                         .next_element()?
                         .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+
+                    ..Default::default()
                 };
 
+                // Version 0 joints have no spring, no servo and no friction.
+                if self.0.m_version >= 1 {
+                    joint_def.enable_spring = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.stiffness = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.damping = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.rest_translation = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.enable_servo = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.target_translation = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.max_servo_speed = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.max_servo_force = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.max_friction_force = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                }
+
                 Ok(joint_def)
             }
 
@@ -195,6 +256,33 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2prismaticJointDefContext<U
                         Field::max_motor_force => {
                             joint_def.max_motor_force = map.next_value()?;
                         }
+                        Field::enable_spring => {
+                            joint_def.enable_spring = map.next_value()?;
+                        }
+                        Field::stiffness => {
+                            joint_def.stiffness = map.next_value()?;
+                        }
+                        Field::damping => {
+                            joint_def.damping = map.next_value()?;
+                        }
+                        Field::rest_translation => {
+                            joint_def.rest_translation = map.next_value()?;
+                        }
+                        Field::enable_servo => {
+                            joint_def.enable_servo = map.next_value()?;
+                        }
+                        Field::target_translation => {
+                            joint_def.target_translation = map.next_value()?;
+                        }
+                        Field::max_servo_speed => {
+                            joint_def.max_servo_speed = map.next_value()?;
+                        }
+                        Field::max_servo_force => {
+                            joint_def.max_servo_force = map.next_value()?;
+                        }
+                        Field::max_friction_force => {
+                            joint_def.max_friction_force = map.next_value()?;
+                        }
                     }
                 }
 
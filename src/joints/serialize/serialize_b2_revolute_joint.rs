@@ -33,6 +33,15 @@ impl<D: UserDataType> B2RevoluteJoinToDef<D> for B2revoluteJoint<D> {
             enable_motor: self.m_enable_motor,
             motor_speed: self.m_motor_speed,
             max_motor_torque: self.m_max_motor_torque,
+            enable_spring: self.m_enable_spring,
+            stiffness: self.m_stiffness,
+            damping: self.m_damping,
+            rest_angle: self.m_rest_angle,
+            enable_servo: self.m_enable_servo,
+            target_angle: self.m_target_angle,
+            max_servo_speed: self.m_max_servo_speed,
+            max_servo_torque: self.m_max_servo_torque,
+            max_friction_torque: self.m_max_friction_torque,
         };
     }
 }
@@ -42,7 +51,7 @@ impl<D: UserDataType> Serialize for B2revoluteJointDef<D> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("B2revoluteJointDef", 10)?;
+        let mut state = serializer.serialize_struct("B2revoluteJointDef", 19)?;
         state.serialize_field("base", &self.base)?;
         state.serialize_field("local_anchor_a", &self.local_anchor_a)?;
         state.serialize_field("local_anchor_b", &self.local_anchor_b)?;
@@ -53,6 +62,15 @@ impl<D: UserDataType> Serialize for B2revoluteJointDef<D> {
         state.serialize_field("enable_motor", &self.enable_motor)?;
         state.serialize_field("motor_speed", &self.motor_speed)?;
         state.serialize_field("max_motor_torque", &self.max_motor_torque)?;
+        state.serialize_field("enable_spring", &self.enable_spring)?;
+        state.serialize_field("stiffness", &self.stiffness)?;
+        state.serialize_field("damping", &self.damping)?;
+        state.serialize_field("rest_angle", &self.rest_angle)?;
+        state.serialize_field("enable_servo", &self.enable_servo)?;
+        state.serialize_field("target_angle", &self.target_angle)?;
+        state.serialize_field("max_servo_speed", &self.max_servo_speed)?;
+        state.serialize_field("max_servo_torque", &self.max_servo_torque)?;
+        state.serialize_field("max_friction_torque", &self.max_friction_torque)?;
         state.end()
     }
 }
@@ -60,6 +78,7 @@ impl<D: UserDataType> Serialize for B2revoluteJointDef<D> {
 #[derive(Clone)]
 pub(crate) struct B2revoluteJointDefContext<D: UserDataType> {
     pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
+    pub(crate) m_version: u32,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2revoluteJointDefContext<U> {
@@ -84,6 +103,15 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2revoluteJointDefContext<U>
             enable_motor,
             motor_speed,
             max_motor_torque,
+            enable_spring,
+            stiffness,
+            damping,
+            rest_angle,
+            enable_servo,
+            target_angle,
+            max_servo_speed,
+            max_servo_torque,
+            max_friction_torque,
         }
 
         struct B2revoluteJointDefVisitor<D: UserDataType>(B2revoluteJointDefContext<D>);
@@ -99,7 +127,7 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2revoluteJointDefContext<U>
             where
                 V: SeqAccess<'de>,
             {
-                let joint_def = B2revoluteJointDef {
+                let mut joint_def = B2revoluteJointDef {
                     base: seq
                         .next_element_seed(B2jointDefVisitorContext {
                             m_body_array: self.0.m_body_array.clone(),
@@ -141,8 +169,41 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2revoluteJointDefContext<U>
                     max_motor_torque: seq
                         .next_element()?
                         .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+
+                    ..Default::default()
                 };
 
+                // Version 0 joints have no spring, no servo and no friction.
+                if self.0.m_version >= 1 {
+                    joint_def.enable_spring = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.stiffness = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.damping = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.rest_angle = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.enable_servo = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.target_angle = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.max_servo_speed = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.max_servo_torque = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    joint_def.max_friction_torque = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                }
+
                 Ok(joint_def)
             }
 
@@ -185,6 +246,33 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2revoluteJointDefContext<U>
                         Field::max_motor_torque => {
                             joint_def.max_motor_torque = map.next_value()?;
                         }
+                        Field::enable_spring => {
+                            joint_def.enable_spring = map.next_value()?;
+                        }
+                        Field::stiffness => {
+                            joint_def.stiffness = map.next_value()?;
+                        }
+                        Field::damping => {
+                            joint_def.damping = map.next_value()?;
+                        }
+                        Field::rest_angle => {
+                            joint_def.rest_angle = map.next_value()?;
+                        }
+                        Field::enable_servo => {
+                            joint_def.enable_servo = map.next_value()?;
+                        }
+                        Field::target_angle => {
+                            joint_def.target_angle = map.next_value()?;
+                        }
+                        Field::max_servo_speed => {
+                            joint_def.max_servo_speed = map.next_value()?;
+                        }
+                        Field::max_servo_torque => {
+                            joint_def.max_servo_torque = map.next_value()?;
+                        }
+                        Field::max_friction_torque => {
+                            joint_def.max_friction_torque = map.next_value()?;
+                        }
                     }
                 }
 
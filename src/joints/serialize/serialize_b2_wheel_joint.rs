@@ -35,6 +35,7 @@ impl<D: UserDataType> B2wheelJoinToDef<D> for B2wheelJoint<D> {
             max_motor_torque: self.m_max_motor_torque,
             stiffness: self.m_stiffness,
             damping: self.m_damping,
+            max_friction_torque: self.m_max_friction_torque,
         };
     }
 }
@@ -44,7 +45,7 @@ impl<D: UserDataType> Serialize for B2wheelJointDef<D> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("B2wheelJointDef", 12)?;
+        let mut state = serializer.serialize_struct("B2wheelJointDef", 13)?;
         state.serialize_field("base", &self.base)?;
         state.serialize_field("local_anchor_a", &self.local_anchor_a)?;
         state.serialize_field("local_anchor_b", &self.local_anchor_b)?;
@@ -57,6 +58,7 @@ impl<D: UserDataType> Serialize for B2wheelJointDef<D> {
         state.serialize_field("max_motor_torque", &self.max_motor_torque)?;
         state.serialize_field("stiffness", &self.stiffness)?;
         state.serialize_field("damping", &self.damping)?;
+        state.serialize_field("max_friction_torque", &self.max_friction_torque)?;
         state.end()
     }
 }
@@ -64,6 +66,7 @@ impl<D: UserDataType> Serialize for B2wheelJointDef<D> {
 #[derive(Clone)]
 pub(crate) struct B2wheelJointDefContext<D: UserDataType> {
     pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
+    pub(crate) m_version: u32,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2wheelJointDefContext<U> {
@@ -90,6 +93,7 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2wheelJointDefContext<U> {
             max_motor_torque,
             stiffness,
             damping,
+            max_friction_torque,
         }
 
         struct B2wheelJointDefVisitor<D: UserDataType>(B2wheelJointDefContext<D>);
@@ -105,7 +109,7 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2wheelJointDefContext<U> {
             where
                 V: SeqAccess<'de>,
             {
-                let joint_def = B2wheelJointDef {
+                let mut joint_def = B2wheelJointDef {
                     base: seq
                         .next_element_seed(B2jointDefVisitorContext {
                             m_body_array: self.0.m_body_array.clone(),
@@ -154,8 +158,17 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2wheelJointDefContext<U> {
                     damping: seq
                         .next_element()?
                         .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+
+                    ..Default::default()
                 };
 
+                // Version 0 joints have no friction.
+                if self.0.m_version >= 1 {
+                    joint_def.max_friction_torque = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                }
+
                 Ok(joint_def)
             }
 
@@ -204,6 +217,9 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2wheelJointDefContext<U> {
                         Field::damping => {
                             joint_def.damping = map.next_value()?;
                         }
+                        Field::max_friction_torque => {
+                            joint_def.max_friction_torque = map.next_value()?;
+                        }
                     }
                 }
 
@@ -18,6 +18,7 @@ pub mod b2_fixture;
 pub mod b2_time_step;
 pub mod b2_world;
 pub mod b2_world_callbacks;
+pub mod b2_force_field;
 
 pub mod joints;
 
@@ -30,6 +31,14 @@ pub mod b2_joint;
 pub mod b2_growable_stack;
 pub mod b2_contact_manager;
 pub mod b2_rope;
+pub mod b2_soft_body;
+pub mod b2_particle;
+pub mod b2_particle_system;
+pub mod b2rs_iterators;
+
+pub mod character;
+pub mod vehicle;
+pub mod controllers;
 
 mod b2rs_double_linked_list;
 mod b2rs_linked_list;
@@ -0,0 +1,287 @@
+use crate::b2_body::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_world::*;
+use crate::b2_world_callbacks::*;
+use crate::b2rs_common::*;
+use crate::character::b2_character_controller::*;
+use crate::private::collision::b2_collide_shapes::b2_collide_shapes;
+
+use std::rc::Rc;
+
+pub(crate) fn new<D: UserDataType>(
+	world: B2worldPtr<D>,
+	def: &B2characterControllerDef<D>,
+) -> B2characterController<D> {
+	b2_assert(def.shape.is_some());
+
+	let body_def = B2bodyDef {
+		body_type: B2bodyType::B2KinematicBody,
+		position: def.position,
+		fixed_rotation: true,
+		allow_sleep: false,
+		user_data: def.user_data.clone(),
+		..Default::default()
+	};
+	let body = B2world::create_body(world.clone(), &body_def);
+
+	let fixture_def = B2fixtureDef {
+		shape: def.shape.clone(),
+		filter: def.filter,
+		..Default::default()
+	};
+	let fixture = B2body::create_fixture(body.clone(), &fixture_def);
+	let shape = fixture.borrow().get_shape();
+
+	// Never block on the character itself.
+	let mut query_filter = B2queryFilter::from_filter(def.filter);
+	query_filter.include_sensors = false;
+	let self_body = Rc::downgrade(&body);
+	query_filter.predicate = Some(Rc::new(move |fixture: FixturePtr<D>| -> bool {
+		return Rc::downgrade(&fixture.borrow().get_body()).ptr_eq(&self_body) == false;
+	}));
+
+	return B2characterController {
+		m_world: Rc::downgrade(&world),
+		m_body: body,
+		m_fixture: fixture,
+		m_shape: shape,
+		m_query_filter: query_filter,
+		m_up: def.up,
+		m_min_slope_cosine: f32::cos(def.max_slope_angle),
+		m_step_height: def.step_height,
+		m_skin_width: def.skin_width,
+		m_max_iterations: def.max_iterations,
+		m_ground_probe_distance: def.ground_probe_distance,
+		m_ground: None,
+		m_velocity: B2vec2::zero(),
+	};
+}
+
+fn is_walkable<D: UserDataType>(self_: &B2characterController<D>, normal: B2vec2) -> bool {
+	return b2_dot(normal, self_.m_up) >= self_.m_min_slope_cosine;
+}
+
+fn character_transform<D: UserDataType>(self_: &B2characterController<D>, position: B2vec2) -> B2Transform {
+	return B2Transform::new(position, self_.m_body.borrow().get_transform().q);
+}
+
+// Sweep the character from position along translation.
+// Returns the travelled distance (kept skin_width away from the hit) and the hit.
+fn sweep<D: UserDataType>(
+	self_: &B2characterController<D>,
+	world: &B2world<D>,
+	position: B2vec2,
+	translation: B2vec2,
+) -> (f32, Option<B2castHit<D>>) {
+	let length: f32 = translation.length();
+	if length < B2_EPSILON {
+		return (0.0, None);
+	}
+
+	// Extend the cast by the skin so the character never ends up touching, the
+	// shape cast reports no hit for shapes that start in contact.
+	let cast_length: f32 = length + self_.m_skin_width;
+	let xf = character_transform(self_, position);
+	let hit = world.cast_shape_closest(&*self_.m_shape, xf, (cast_length / length) * translation, &self_.m_query_filter);
+	match hit {
+		Some(hit) => {
+			let travel = b2_clamp(hit.fraction * cast_length - self_.m_skin_width, 0.0, length);
+			return (travel, Some(hit));
+		}
+		None => {
+			return (length, None);
+		}
+	}
+}
+
+// Try to climb a ledge of at most step_height while moving horizontally.
+// Returns the landing position when the ledge top is walkable.
+fn try_step_up<D: UserDataType>(
+	self_: &B2characterController<D>,
+	world: &B2world<D>,
+	position: B2vec2,
+	horizontal: B2vec2,
+) -> Option<B2vec2> {
+	let up = self_.m_up;
+	let (up_travel, _) = sweep(self_, world, position, self_.m_step_height * up);
+	if up_travel < B2_LINEAR_SLOP {
+		return None;
+	}
+	let raised = position + up_travel * up;
+
+	let horizontal_length = horizontal.length();
+	let (forward_travel, _) = sweep(self_, world, raised, horizontal);
+	if forward_travel < B2_LINEAR_SLOP {
+		return None;
+	}
+	let forward = raised + (forward_travel / horizontal_length) * horizontal;
+
+	let drop = up_travel;
+	let (down_travel, hit) = sweep(self_, world, forward, -drop * up);
+	match hit {
+		Some(hit) if is_walkable(self_, hit.normal) => {
+			return Some(forward - down_travel * up);
+		}
+		_ => {
+			return None;
+		}
+	}
+}
+
+fn probe_ground<D: UserDataType>(
+	self_: &B2characterController<D>,
+	world: &B2world<D>,
+	position: B2vec2,
+) -> Option<B2characterGround<D>> {
+	let probe = -self_.m_ground_probe_distance * self_.m_up;
+	let (_, hit) = sweep(self_, world, position, probe);
+	let hit = hit?;
+	if is_walkable(self_, hit.normal) == false {
+		return None;
+	}
+
+	let velocity = hit.fixture.borrow().get_body().borrow().get_linear_velocity_from_world_point(hit.point);
+	return Some(B2characterGround {
+		fixture: hit.fixture,
+		point: hit.point,
+		normal: hit.normal,
+		velocity,
+	});
+}
+
+pub(crate) fn move_and_slide<D: UserDataType>(
+	self_: &mut B2characterController<D>,
+	displacement: B2vec2,
+	dt: f32,
+) -> B2vec2 {
+	let world = upgrade(&self_.m_world);
+	let world = world.borrow();
+	b2_assert(world.is_locked() == false);
+
+	let start: B2vec2 = self_.m_body.borrow().get_position();
+	let up = self_.m_up;
+	let was_grounded = self_.m_ground.is_some();
+
+	// Moving platform carry.
+	let mut remaining = displacement;
+	if let Some(ref ground) = self_.m_ground {
+		remaining += dt * ground.velocity;
+	}
+
+	let mut position = start;
+	for _ in 0..self_.m_max_iterations {
+		let length = remaining.length();
+		if length < B2_EPSILON {
+			break;
+		}
+
+		let (travel, hit) = sweep(self_, &world, position, remaining);
+		let hit = match hit {
+			Some(hit) => hit,
+			None => {
+				position += remaining;
+				break;
+			}
+		};
+
+		position += (travel / length) * remaining;
+		remaining = (1.0 - travel / length) * remaining;
+
+		let mut normal = hit.normal;
+		if is_walkable(self_, normal) {
+			// Gravity does not slide the character down walkable ground.
+			let vertical = b2_dot(remaining, up);
+			if vertical < 0.0 {
+				remaining -= vertical * up;
+			}
+		} else {
+			// Step onto low ledges while walking on the ground.
+			let horizontal = remaining - b2_dot(remaining, up) * up;
+			if was_grounded && horizontal.length_squared() > B2_EPSILON * B2_EPSILON {
+				if let Some(landing) = try_step_up(self_, &world, position, horizontal) {
+					position = landing;
+					remaining = b2_dot(remaining, up) * up;
+					continue;
+				}
+			}
+
+			// Steep slopes act as walls, so they cannot be climbed. Airborne characters
+			// falling onto them slide down.
+			if b2_dot(normal, up) > 0.0 && (was_grounded || b2_dot(remaining, up) >= 0.0) {
+				normal -= b2_dot(normal, up) * up;
+				if normal.normalize() < B2_EPSILON {
+					break;
+				}
+			}
+		}
+
+		// Slide along the surface.
+		remaining -= b2_dot(remaining, normal) * normal;
+	}
+
+	self_.m_ground = probe_ground(self_, &world, position);
+
+	let moved: B2vec2 = position - start;
+	self_.m_velocity = if dt > 0.0 { (1.0 / dt) * moved } else { B2vec2::zero() };
+	{
+		let mut body = self_.m_body.borrow_mut();
+		body.set_linear_velocity(self_.m_velocity);
+		body.set_angular_velocity(0.0);
+	}
+	return moved;
+}
+
+pub(crate) fn depenetrate<D: UserDataType>(self_: &mut B2characterController<D>) -> B2vec2 {
+	let world_ptr = upgrade(&self_.m_world);
+	let world = world_ptr.borrow();
+	b2_assert(world.is_locked() == false);
+
+	let start: B2vec2 = self_.m_body.borrow().get_position();
+	let mut position = start;
+	for _ in 0..self_.m_max_iterations {
+		let xf = character_transform(self_, position);
+
+		// Find the deepest penetration against all touching children.
+		let mut deepest: f32 = 0.0;
+		let mut push = B2vec2::zero();
+		let mut overlaps = Vec::<FixturePtr<D>>::new();
+		world.overlap_shape(&*self_.m_shape, xf, &self_.m_query_filter, |fixture: FixturePtr<D>| -> bool {
+			overlaps.push(fixture);
+			return true;
+		});
+		for fixture in overlaps {
+			let fixture = fixture.borrow();
+			let shape = fixture.get_shape();
+			let xf_b = fixture.get_body().borrow().get_transform();
+			for child_index in 0..shape.get_child_count() {
+				if let Some((manifold, count)) = b2_collide_shapes(&*self_.m_shape, 0, xf, &*shape, child_index, xf_b) {
+					for i in 0..count {
+						if manifold.separations[i] < deepest {
+							deepest = manifold.separations[i];
+							// The normal points from the character to the other shape.
+							push = -manifold.normal;
+						}
+					}
+				}
+			}
+		}
+
+		if deepest >= 0.0 {
+			break;
+		}
+		position += (self_.m_skin_width - deepest) * push;
+	}
+
+	// set_transform needs the world.
+	drop(world);
+
+	let correction = position - start;
+	if correction.length_squared() > 0.0 {
+		let mut body = self_.m_body.borrow_mut();
+		let angle = body.get_angle();
+		body.set_transform(position, angle);
+	}
+	return correction;
+}
@@ -0,0 +1 @@
+pub(crate) mod b2_character_controller;
@@ -10,6 +10,7 @@ pub fn b2_collide_circles(
 	xf_a: &B2Transform,
 	circle_b: &B2circleShape,
 	xf_b: &B2Transform,
+	speculative_distance: f32,
 ) {
 	manifold.point_count = 0;
 
@@ -19,7 +20,8 @@ pub fn b2_collide_circles(
 	let d: B2vec2 = p_b - p_a;
 	let dist_sqr: f32 = b2_dot(d, d);
 	let (r_a, r_b) = (circle_a.base.m_radius, circle_b.base.m_radius);
-	let radius: f32 = r_a + r_b;
+	// Points up to the speculative distance apart are kept.
+	let radius: f32 = r_a + r_b + speculative_distance;
 	if dist_sqr > radius * radius {
 		return;
 	}
@@ -39,6 +41,7 @@ pub fn b2_collide_polygon_and_circle(
 	xf_a: &B2Transform,
 	circle_b: &B2circleShape,
 	xf_b: &B2Transform,
+	speculative_distance: f32,
 ) {
 	manifold.point_count = 0;
 
@@ -49,7 +52,7 @@ pub fn b2_collide_polygon_and_circle(
 	// Find the min separating edge.
 	let mut normal_index: usize = 0;
 	let mut separation: f32 = -B2_MAX_FLOAT;
-	let radius: f32 = polygon_a.base.m_radius + circle_b.base.m_radius;
+	let radius: f32 = polygon_a.base.m_radius + circle_b.base.m_radius + speculative_distance;
 	let vertex_count: usize = polygon_a.m_count;
 	let vertices = &polygon_a.m_vertices;
 	let normals = &polygon_a.m_normals;
@@ -0,0 +1,297 @@
+use crate::b2_collision::*;
+use crate::b2_distance::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_shape::*;
+use crate::private::collision::b2_distance as distance;
+
+// Generic manifold for two convex child shapes given by their distance
+// proxies. Used for the custom shapes, which collide with every other shape
+// through this path.
+//
+// GJK on the core shapes gives the normal. When the cores overlap GJK can't
+// provide one, so the axis of least penetration of the two vertex clouds is
+// used instead. The reference face is the support feature of shape_a along
+// the normal and the incident face is the support feature of shape_b against
+// it. Faces are the vertices within the linear slop of the support plane, so
+// flat sides give two points and curved sides give one. Custom shapes are
+// sampled into a hull, so a face on a curved custom shape may just be a facet
+// of the sampling. It is replaced by the exact support point unless the exact
+// support lies on the face.
+pub(crate) fn b2_collide_convex(
+	manifold: &mut B2manifold,
+	shape_a: &dyn B2shapeDynTrait,
+	index_a: usize,
+	xf_a: B2Transform,
+	shape_b: &dyn B2shapeDynTrait,
+	index_b: usize,
+	xf_b: B2Transform,
+	speculative_distance: f32,
+) {
+	manifold.point_count = 0;
+
+	let mut input = B2distanceInput::default();
+	distance::set_shape(&mut input.proxy_a, shape_a, index_a);
+	distance::set_shape(&mut input.proxy_b, shape_b, index_b);
+	input.transform_a = xf_a;
+	input.transform_b = xf_b;
+	input.use_radii = false;
+
+	let total_radius: f32 = input.proxy_a.m_radius + input.proxy_b.m_radius;
+	// Points up to the speculative distance apart are kept.
+	let cutoff: f32 = total_radius + speculative_distance;
+
+	let mut cache = B2simplexCache::default();
+	let mut output = B2distanceOutput::default();
+	b2_distance_fn(&mut output, &mut cache, &input);
+
+	if output.distance > cutoff {
+		return;
+	}
+
+	let vertices_a: Vec<B2vec2> = input
+		.proxy_a
+		.m_vertices
+		.iter()
+		.map(|v| b2_mul_transform_by_vec2(xf_a, *v))
+		.collect();
+	let vertices_b: Vec<B2vec2> = input
+		.proxy_b
+		.m_vertices
+		.iter()
+		.map(|v| b2_mul_transform_by_vec2(xf_b, *v))
+		.collect();
+
+	let normal: B2vec2 = if output.distance > 0.1 * B2_LINEAR_SLOP {
+		(1.0 / output.distance) * (output.point_b - output.point_a)
+	} else {
+		find_min_penetration_axis(&vertices_a, &vertices_b)
+	};
+
+	let face_a: Face = find_face(shape_a, xf_a, &vertices_a, normal);
+	let face_b: Face = find_face(shape_b, xf_b, &vertices_b, -normal);
+
+	// The reference face is the flat face most aligned with the normal, like
+	// the polygon collider. Two curved sides keep the GJK normal on A.
+	const K_TOL: f32 = 0.1 * B2_LINEAR_SLOP;
+	let flip: bool;
+	let reference_normal: B2vec2;
+	match (face_a.get_normal(normal), face_b.get_normal(-normal)) {
+		(Some(normal_a), Some(normal_b)) => {
+			flip = b2_dot(normal_b, -normal) > b2_dot(normal_a, normal) + K_TOL;
+			reference_normal = if flip { normal_b } else { normal_a };
+		}
+		(Some(normal_a), None) => {
+			flip = false;
+			reference_normal = normal_a;
+		}
+		(None, Some(normal_b)) => {
+			flip = true;
+			reference_normal = normal_b;
+		}
+		(None, None) => {
+			flip = false;
+			reference_normal = normal;
+		}
+	}
+
+	let (reference, incident, xf1, xf2) = if flip {
+		(&face_b, &face_a, xf_b, xf_a)
+	} else {
+		(&face_a, &face_b, xf_a, xf_b)
+	};
+
+	// Clip the incident face to the extent of the reference face.
+	let tangent: B2vec2 = b2_cross_scalar_by_vec(1.0, reference_normal);
+	let r1: f32 = b2_dot(tangent, reference.v1);
+	let r2: f32 = b2_dot(tangent, reference.v2);
+	let (lower_r, upper_r) = (b2_min(r1, r2), b2_max(r1, r2));
+
+	let (mut p1, mut p2, mut i1, mut i2) = (incident.v1, incident.v2, incident.i1, incident.i2);
+	if b2_dot(tangent, p1) > b2_dot(tangent, p2) {
+		std::mem::swap(&mut p1, &mut p2);
+		std::mem::swap(&mut i1, &mut i2);
+	}
+	let t1: f32 = b2_dot(tangent, p1);
+	let t2: f32 = b2_dot(tangent, p2);
+	let incident_point = |t: f32| -> B2vec2 {
+		return p1 + ((t - t1) / (t2 - t1)) * (p2 - p1);
+	};
+
+	let mut clip_points = <[(B2vec2, usize); B2_MAX_MANIFOLD_POINTS]>::default();
+	let clip_count: usize;
+	if t2 - t1 < B2_LINEAR_SLOP {
+		clip_points[0] = (p1, i1);
+		clip_count = 1;
+	} else {
+		let lower: f32 = b2_max(lower_r, t1);
+		let upper: f32 = b2_min(upper_r, t2);
+		if upper - lower < B2_LINEAR_SLOP {
+			// A corner or a curve touches the face, or the faces only touch
+			// at their ends.
+			let t: f32 = b2_clamp(0.5 * (lower_r + upper_r), t1, t2);
+			let index: usize = if t - t1 < t2 - t { i1 } else { i2 };
+			clip_points[0] = (incident_point(t), index);
+			clip_count = 1;
+		} else {
+			clip_points[0] = (incident_point(lower), i1);
+			clip_points[1] = (incident_point(upper), i2);
+			clip_count = 2;
+		}
+	}
+
+	let plane_point: B2vec2 = 0.5 * (reference.v1 + reference.v2);
+	manifold.manifold_type = if flip { B2manifoldType::EFaceB } else { B2manifoldType::EFaceA };
+	manifold.local_normal = b2_mul_t_rot_by_vec2(xf1.q, reference_normal);
+	manifold.local_point = b2_mul_t_transform_by_vec2(xf1, plane_point);
+
+	let mut point_count: usize = 0;
+	for (point, index) in clip_points.iter().take(clip_count) {
+		let separation: f32 = b2_dot(reference_normal, *point - plane_point);
+		if separation <= cutoff {
+			let cp: &mut B2manifoldPoint = &mut manifold.points[point_count];
+			cp.local_point = b2_mul_t_transform_by_vec2(xf2, *point);
+			if flip {
+				cp.id.cf.index_a = *index as u8;
+				cp.id.cf.index_b = reference.i1 as u8;
+				cp.id.cf.type_a = B2contactFeatureType::EVertex as u8;
+				cp.id.cf.type_b = B2contactFeatureType::EFace as u8;
+			} else {
+				cp.id.cf.index_a = reference.i1 as u8;
+				cp.id.cf.index_b = *index as u8;
+				cp.id.cf.type_a = B2contactFeatureType::EFace as u8;
+				cp.id.cf.type_b = B2contactFeatureType::EVertex as u8;
+			}
+			point_count += 1;
+		}
+	}
+
+	manifold.point_count = point_count;
+}
+
+// The support feature of a child shape along a world direction. The end
+// points are equal when the feature is a single point.
+struct Face {
+	v1: B2vec2,
+	v2: B2vec2,
+	i1: usize,
+	i2: usize,
+}
+
+impl Face {
+	// The outward normal of a flat face, None for a point.
+	fn get_normal(&self, d: B2vec2) -> Option<B2vec2> {
+		let mut edge: B2vec2 = self.v2 - self.v1;
+		if edge.normalize() < B2_LINEAR_SLOP {
+			return None;
+		}
+		let normal: B2vec2 = b2_cross_vec_by_scalar(edge, 1.0);
+		return Some(if b2_dot(normal, d) < 0.0 { -normal } else { normal });
+	}
+}
+
+// Faces are the vertices within the linear slop of the support plane.
+fn find_face(shape: &dyn B2shapeDynTrait, xf: B2Transform, vertices: &[B2vec2], d: B2vec2) -> Face {
+	let mut max: f32 = -B2_MAX_FLOAT;
+	for v in vertices {
+		max = b2_max(max, b2_dot(d, *v));
+	}
+
+	let tangent: B2vec2 = b2_cross_scalar_by_vec(1.0, d);
+	let mut face = Face {
+		v1: B2vec2::zero(),
+		v2: B2vec2::zero(),
+		i1: 0,
+		i2: 0,
+	};
+	let mut t_min: f32 = B2_MAX_FLOAT;
+	let mut t_max: f32 = -B2_MAX_FLOAT;
+	for (i, v) in vertices.iter().enumerate() {
+		if b2_dot(d, *v) >= max - B2_LINEAR_SLOP {
+			let t: f32 = b2_dot(tangent, *v);
+			if t < t_min {
+				t_min = t;
+				face.v1 = *v;
+				face.i1 = i;
+			}
+			if t > t_max {
+				t_max = t;
+				face.v2 = *v;
+				face.i2 = i;
+			}
+		}
+	}
+
+	if let Some(v) = exact_support(shape, xf, d, max, t_max - t_min) {
+		face.v1 = v;
+		face.v2 = v;
+		face.i2 = face.i1;
+	}
+	return face;
+}
+
+// The exact support point of a custom shape along the world direction d, or
+// None when the hull face found along d is flat and should be kept. max is
+// the support value of the hull along d and extent is the face length.
+fn exact_support(
+	shape: &dyn B2shapeDynTrait,
+	xf: B2Transform,
+	d: B2vec2,
+	max: f32,
+	extent: f32,
+) -> Option<B2vec2> {
+	const K_FLAT_TOL: f32 = 0.1 * B2_LINEAR_SLOP;
+	let custom = shape.as_custom()?;
+	let v: B2vec2 = b2_mul_transform_by_vec2(xf, custom.m_shape.support(b2_mul_t_rot_by_vec2(xf.q, d)));
+	if extent >= B2_LINEAR_SLOP && b2_dot(d, v) <= max + K_FLAT_TOL {
+		return None;
+	}
+	return Some(v);
+}
+
+// The separating axis with the largest separation, taken from the edge
+// normals of both vertex clouds. The axis points from A to B.
+fn find_min_penetration_axis(vertices_a: &[B2vec2], vertices_b: &[B2vec2]) -> B2vec2 {
+	let separation = |n: B2vec2| -> f32 {
+		let max_a: f32 = vertices_a.iter().fold(-B2_MAX_FLOAT, |m, v| b2_max(m, b2_dot(n, *v)));
+		let min_b: f32 = vertices_b.iter().fold(B2_MAX_FLOAT, |m, v| b2_min(m, b2_dot(n, *v)));
+		return min_b - max_a;
+	};
+
+	let mut best_axis: Option<B2vec2> = None;
+	let mut best_separation: f32 = -B2_MAX_FLOAT;
+	for (vertices, sign) in [(vertices_a, 1.0), (vertices_b, -1.0)] {
+		let count: usize = vertices.len();
+		if count < 2 {
+			continue;
+		}
+		for i in 0..count {
+			let mut edge: B2vec2 = vertices[if i + 1 < count { i + 1 } else { 0 }] - vertices[i];
+			if edge.normalize() < B2_EPSILON {
+				continue;
+			}
+			let outward: B2vec2 = b2_cross_vec_by_scalar(edge, 1.0);
+			// Segments have no inside, so both sides are candidates.
+			let side_count: usize = if count == 2 { 2 } else { 1 };
+			for side in 0..side_count {
+				let axis: B2vec2 = if side == 0 { sign * outward } else { -sign * outward };
+				let s: f32 = separation(axis);
+				if s > best_separation {
+					best_separation = s;
+					best_axis = Some(axis);
+				}
+			}
+		}
+	}
+
+	if let Some(axis) = best_axis {
+		return axis;
+	}
+
+	// Two points on top of each other.
+	let mut d: B2vec2 = vertices_b[0] - vertices_a[0];
+	if d.normalize() < B2_EPSILON {
+		d = B2vec2::new(0.0, 1.0);
+	}
+	return d;
+}
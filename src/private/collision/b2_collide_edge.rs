@@ -14,6 +14,7 @@ pub fn b2_collide_edge_and_circle(
 	xf_a: &B2Transform,
 	circle_b: &B2circleShape,
 	xf_b: &B2Transform,
+	speculative_distance: f32,
 ) {
 	manifold.point_count = 0;
 	// Compute circle in frame of edge
@@ -36,7 +37,8 @@ pub fn b2_collide_edge_and_circle(
 	let u: f32 = b2_dot(e, b - q);
 	let v: f32 = b2_dot(e, q - a);
 
-	let radius: f32 = edge_a.base.m_radius + circle_b.base.m_radius;
+	// Points up to the speculative distance apart are kept.
+	let radius: f32 = edge_a.base.m_radius + circle_b.base.m_radius + speculative_distance;
 	let mut cf = B2contactFeature::default();
 	cf.index_b = 0;
 	cf.type_b = B2contactFeatureType::EVertex as u8;
@@ -233,6 +235,7 @@ pub fn b2_collide_edge_and_polygon(
 	xf_a: &B2Transform,
 	polygon_b: &B2polygonShape,
 	xf_b: &B2Transform,
+	speculative_distance: f32,
 ) {
 	manifold.point_count = 0;
 
@@ -264,14 +267,16 @@ pub fn b2_collide_edge_and_polygon(
 	}
 
 	let radius: f32 = polygon_b.base.m_radius + edge_a.base.m_radius;
+	// Points up to the speculative distance apart are kept.
+	let cutoff: f32 = radius + speculative_distance;
 
 	let edge_axis: B2epaxis = b2_compute_edge_separation(temp_polygon_b, v1, normal1);
-	if edge_axis.separation > radius {
+	if edge_axis.separation > cutoff {
 		return;
 	}
 
 	let polygon_axis: B2epaxis = b2_compute_polygon_separation(temp_polygon_b, v1, v2);
-	if polygon_axis.separation > radius {
+	if polygon_axis.separation > cutoff {
 		return;
 	}
 
@@ -453,7 +458,7 @@ pub fn b2_collide_edge_and_polygon(
 
 		separation = b2_dot(rf.normal, clip_points2[i].v - rf.v1);
 
-		if separation <= radius {
+		if separation <= cutoff {
 			let cp: &mut B2manifoldPoint = &mut manifold.points[point_count];
 
 			if primary_axis.axis_type == B2ePAxisType::EEdgeA {
@@ -105,19 +105,22 @@ pub fn b2_collide_polygons(
 	xf_a: B2Transform,
 	poly_b: B2polygonShape,
 	xf_b: B2Transform,
+	speculative_distance: f32,
 ) {
 	manifold.point_count = 0;
 	let total_radius: f32 = poly_a.base.m_radius + poly_b.base.m_radius;
+	// Points up to the speculative distance apart are kept.
+	let cutoff: f32 = total_radius + speculative_distance;
 
 	let mut edge_a: usize = 0;
 	let separation_a: f32 = b2_find_max_separation(&mut edge_a, poly_a, xf_a, poly_b, xf_b);
-	if separation_a > total_radius {
+	if separation_a > cutoff {
 		return;
 	}
 
 	let mut edge_b: usize = 0;
 	let separation_b: f32 = b2_find_max_separation(&mut edge_b, poly_b, xf_b, poly_a, xf_a);
-	if separation_b > total_radius {
+	if separation_b > cutoff {
 		return;
 	}
 
@@ -204,7 +207,7 @@ pub fn b2_collide_polygons(
 	for i in 0..B2_MAX_MANIFOLD_POINTS {
 		let separation: f32 = b2_dot(normal, clip_points2[i].v) - front_offset;
 
-		if separation <= total_radius {
+		if separation <= cutoff {
 			let cp = &mut manifold.points[point_count];
 			cp.local_point = b2_mul_t_transform_by_vec2(xf2, clip_points2[i].v);
 			cp.id = clip_points2[i].id;
@@ -0,0 +1,97 @@
+use crate::b2_collision::*;
+use crate::b2_math::*;
+use crate::b2_shape::*;
+use crate::shapes::b2_edge_shape::*;
+use crate::shapes::b2rs_to_derived_shape::*;
+use crate::private::collision::b2_collide_custom::b2_collide_convex;
+
+// Convex piece of a child shape that the manifold functions accept.
+enum ChildShape<'a> {
+	Circle(&'a crate::shapes::b2_circle_shape::B2circleShape),
+	Polygon(&'a crate::shapes::b2_polygon_shape::B2polygonShape),
+	Edge(B2edgeShape),
+	// Custom shapes go through the generic convex manifold.
+	Custom,
+}
+
+fn get_child_shape(shape: &dyn B2shapeDynTrait, index: usize) -> ChildShape<'_> {
+	match shape.as_derived() {
+		ShapeAsDerived::AsCircle(circle) => ChildShape::Circle(circle),
+		ShapeAsDerived::AsPolygon(polygon) => ChildShape::Polygon(polygon),
+		ShapeAsDerived::AsEdge(edge) => ChildShape::Edge(*edge),
+		ShapeAsDerived::AsChain(chain) => {
+			let mut edge = B2edgeShape::default();
+			chain.get_child_edge(&mut edge, index);
+			ChildShape::Edge(edge)
+		}
+		ShapeAsDerived::AsCustom(_) => ChildShape::Custom,
+	}
+}
+
+// Compute the contact manifold of two child shapes in world coordinates, like the
+// contact pipeline does. The normal points from shape_a to shape_b.
+// Returns None for pairs that never collide (edge against edge) or when there are no points.
+pub(crate) fn b2_collide_shapes(
+	shape_a: &dyn B2shapeDynTrait,
+	index_a: usize,
+	xf_a: B2Transform,
+	shape_b: &dyn B2shapeDynTrait,
+	index_b: usize,
+	xf_b: B2Transform,
+) -> Option<(B2worldManifold, usize)> {
+	let child_a = get_child_shape(shape_a, index_a);
+	let child_b = get_child_shape(shape_b, index_b);
+	let radius_a = shape_a.get_base().m_radius;
+	let radius_b = shape_b.get_base().m_radius;
+
+	let mut manifold = B2manifold::default();
+	let mut flip = false;
+	match (&child_a, &child_b) {
+		(ChildShape::Custom, _) | (_, ChildShape::Custom) => {
+			b2_collide_convex(&mut manifold, shape_a, index_a, xf_a, shape_b, index_b, xf_b, 0.0);
+		}
+		(ChildShape::Circle(a), ChildShape::Circle(b)) => {
+			b2_collide_circles(&mut manifold, a, &xf_a, b, &xf_b);
+		}
+		(ChildShape::Polygon(a), ChildShape::Circle(b)) => {
+			b2_collide_polygon_and_circle(&mut manifold, a, &xf_a, b, &xf_b);
+		}
+		(ChildShape::Circle(a), ChildShape::Polygon(b)) => {
+			b2_collide_polygon_and_circle(&mut manifold, b, &xf_b, a, &xf_a);
+			flip = true;
+		}
+		(ChildShape::Polygon(a), ChildShape::Polygon(b)) => {
+			b2_collide_polygons(&mut manifold, a, &xf_a, b, &xf_b);
+		}
+		(ChildShape::Edge(a), ChildShape::Circle(b)) => {
+			b2_collide_edge_and_circle(&mut manifold, a, &xf_a, b, &xf_b);
+		}
+		(ChildShape::Edge(a), ChildShape::Polygon(b)) => {
+			b2_collide_edge_and_polygon(&mut manifold, a, &xf_a, b, &xf_b);
+		}
+		(ChildShape::Circle(a), ChildShape::Edge(b)) => {
+			b2_collide_edge_and_circle(&mut manifold, b, &xf_b, a, &xf_a);
+			flip = true;
+		}
+		(ChildShape::Polygon(a), ChildShape::Edge(b)) => {
+			b2_collide_edge_and_polygon(&mut manifold, b, &xf_b, a, &xf_a);
+			flip = true;
+		}
+		(ChildShape::Edge(_), ChildShape::Edge(_)) => {
+			return None;
+		}
+	}
+
+	if manifold.point_count == 0 {
+		return None;
+	}
+
+	let mut world_manifold = B2worldManifold::default();
+	if flip {
+		world_manifold.initialize(&manifold, xf_b, radius_b, xf_a, radius_a);
+		world_manifold.normal = -world_manifold.normal;
+	} else {
+		world_manifold.initialize(&manifold, xf_a, radius_a, xf_b, radius_b);
+	}
+	return Some((world_manifold, manifold.point_count));
+}
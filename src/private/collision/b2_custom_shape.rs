@@ -0,0 +1,56 @@
+use crate::shapes::b2_custom_shape::*;
+use crate::b2_distance::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_shape::*;
+
+// Sample the support function in evenly spaced directions. The samples are
+// ordered by angle, so they form a counter-clockwise convex polygon once
+// coincident samples are merged. Flat cores like segments and boxes reduce
+// to their exact corners. Like polygons, the shape gets a skin of
+// B2_POLYGON_RADIUS on top of the user radius so resting contacts don't
+// overlap the cores. Contact features store hull indices as u8, so the hull
+// is capped at 255 vertices.
+pub(crate) fn new(shape: B2customShapePtr) -> B2customShape {
+	let count: usize = b2_clamp(shape.get_support_count(), 3, u8::MAX as usize);
+	let mut hull: Vec<B2vec2> = Vec::with_capacity(count);
+	for i in 0..count {
+		let angle: f32 = 2.0 * B2_PI * i as f32 / count as f32;
+		let v: B2vec2 = shape.support(B2vec2::new(f32::cos(angle), f32::sin(angle)));
+		if let Some(last) = hull.last() {
+			if b2_distance_vec2_squared(*last, v) < B2_LINEAR_SLOP * B2_LINEAR_SLOP {
+				continue;
+			}
+		}
+		hull.push(v);
+	}
+	while hull.len() > 1
+		&& b2_distance_vec2_squared(hull[0], *hull.last().unwrap()) < B2_LINEAR_SLOP * B2_LINEAR_SLOP
+	{
+		hull.pop();
+	}
+
+	return B2customShape {
+		base: B2Shape {
+			m_type: B2ShapeType::ECustom,
+			m_radius: shape.get_radius() + B2_POLYGON_RADIUS,
+		},
+		m_shape: shape,
+		m_hull: hull,
+	};
+}
+
+pub(crate) fn test_point(self_: &B2customShape, transform: B2Transform, p: B2vec2) -> bool {
+	let mut input = B2distanceInput::default();
+	input.proxy_a.set_vertices(&self_.m_hull, self_.base.m_radius);
+	input.proxy_b.set_vertices(&[p], 0.0);
+	input.transform_a = transform;
+	input.transform_b.set_identity();
+	input.use_radii = false;
+
+	let mut cache = B2simplexCache::default();
+	let mut output = B2distanceOutput::default();
+	b2_distance_fn(&mut output, &mut cache, &input);
+
+	return output.distance <= self_.m_shape.get_radius();
+}
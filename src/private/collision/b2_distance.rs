@@ -12,7 +12,7 @@ use std::sync::atomic::Ordering;
 
 pub fn set_shape(
 	self_: &mut B2distanceProxy,
-	shape: ShapePtr,
+	shape: &dyn B2shapeDynTrait,
 	index: usize,
 ) {
 	match shape.as_derived() {
@@ -41,6 +41,10 @@ pub fn set_shape(
 			self_.m_vertices = [edge.m_vertex1, edge.m_vertex2].to_vec();
 			self_.m_radius = edge.base.m_radius;
 		}
+		ShapeAsDerived::AsCustom(custom)  => {
+			self_.m_vertices = custom.m_hull.clone();
+			self_.m_radius = custom.base.m_radius;
+		}
 	}
 }
 
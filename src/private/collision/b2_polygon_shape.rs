@@ -237,7 +237,7 @@ pub fn b2_shape_dyn_trait_ray_cast(
 	let p2: B2vec2 = b2_mul_t_rot_by_vec2(xf.q, input.p2 - xf.p);
 	let d: B2vec2 = p2 - p1;
 
-	let (mut lower, mut upper) = (0.032, input.max_fraction);
+	let (mut lower, mut upper) = (0.0, input.max_fraction);
 
 	let mut index: i32 = -1;
 
@@ -312,6 +312,14 @@ pub fn b2_shape_dyn_trait_compute_aabb(
 }
 
 pub fn b2_shape_dyn_trait_compute_mass(self_: &B2polygonShape, mass_data: &mut B2massData, density: f32) {
+	b2_assert(self_.m_count >= 3);
+	let area: f32 = b2_compute_polygon_mass(&self_.m_vertices[..self_.m_count], density, mass_data);
+	b2_assert(area > B2_EPSILON);
+}
+
+// Mass, centroid and inertia of a convex counter-clockwise polygon with any number of
+// vertices. Returns the area, the center is only valid when the area is positive.
+pub(crate) fn b2_compute_polygon_mass(vertices: &[B2vec2], density: f32, mass_data: &mut B2massData) -> f32 {
 	// Polygon mass, centroid, and inertia.
 	// Let rho be the polygon density in mass per unit area.
 	// Then:
@@ -336,7 +344,8 @@ pub fn b2_shape_dyn_trait_compute_mass(self_: &B2polygonShape, mass_data: &mut B
 	//
 	// The rest of the derivation is handled by computer algebra.
 
-	b2_assert(self_.m_count >= 3);
+	let count = vertices.len();
+	b2_assert(count >= 3);
 
 	let mut center = B2vec2::zero();
 	let mut area: f32 = 0.0;
@@ -344,17 +353,17 @@ pub fn b2_shape_dyn_trait_compute_mass(self_: &B2polygonShape, mass_data: &mut B
 
 	// Get a reference point for forming triangles.
 	// Use the first vertex to reduce round-off errors.
-	let s: B2vec2 = self_.m_vertices[0];
+	let s: B2vec2 = vertices[0];
 
 	const K_INV3: f32 = 1.0 / 3.0;
 
-	for i in 0..self_.m_count {
+	for i in 0..count {
 		// Triangle vertices.
-		let e1: B2vec2 = self_.m_vertices[i] - s;
-		let e2: B2vec2 = if i + 1 < self_.m_count {
-			self_.m_vertices[i + 1] - s
+		let e1: B2vec2 = vertices[i] - s;
+		let e2: B2vec2 = if i + 1 < count {
+			vertices[i + 1] - s
 		} else {
-			self_.m_vertices[0] - s
+			vertices[0] - s
 		};
 
 		let d: f32 = b2_cross(e1, e2);
@@ -378,8 +387,9 @@ pub fn b2_shape_dyn_trait_compute_mass(self_: &B2polygonShape, mass_data: &mut B
 	mass_data.mass = density * area;
 
 	// Center of mass
-	b2_assert(area > B2_EPSILON);
-	center *= 1.0 / area;
+	if area > B2_EPSILON {
+		center *= 1.0 / area;
+	}
 	mass_data.center = center + s;
 
 	// Inertia tensor relative to the local origin (point s).
@@ -387,6 +397,8 @@ pub fn b2_shape_dyn_trait_compute_mass(self_: &B2polygonShape, mass_data: &mut B
 	// Shift to center of mass then to original body origin.
 	mass_data.i +=
 		mass_data.mass * (b2_dot(mass_data.center, mass_data.center) - b2_dot(center, center));
+
+	return area;
 }
 
 pub fn b2_polygon_shape_validate(self_: B2polygonShape) -> bool {
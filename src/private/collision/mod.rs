@@ -3,10 +3,13 @@ pub mod b2_polygon_shape;
 pub mod b2_edge_shape;
 pub mod b2_chain_shape;
 pub mod b2_circle_shape;
+pub mod b2_custom_shape;
 pub mod b2_collide_circle;
+pub mod b2_collide_custom;
 pub mod b2_collide_edge;
 pub mod b2_collide_polygon;
 pub mod b2_distance;
 pub mod b2_time_of_impact;
 pub mod b2_dynamic_tree;
-pub mod b2_broad_phase;
\ No newline at end of file
+pub mod b2_broad_phase;
+pub mod b2_collide_shapes;
@@ -0,0 +1,210 @@
+use crate::b2_body::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_shape::*;
+use crate::b2_world::*;
+use crate::b2rs_common::*;
+use crate::controllers::b2_buoyancy_controller::*;
+use crate::private::collision::b2_polygon_shape::b2_compute_polygon_mass;
+use crate::shapes::b2rs_to_derived_shape::*;
+
+use std::rc::Rc;
+
+// The number of polygon vertices used to approximate circles.
+const B2_CIRCLE_SEGMENTS: usize = 16;
+
+pub(crate) fn new<D: UserDataType>(
+	world: B2worldPtr<D>,
+	fixture: FixturePtr<D>,
+	def: &B2buoyancyControllerDef,
+) -> B2buoyancyController<D> {
+	{
+		let fixture = fixture.borrow();
+		b2_assert(fixture.is_sensor());
+		b2_assert(world_polygon(&*fixture.get_shape(), B2Transform::new(B2vec2::zero(), B2Rot::new(0.0))).is_some());
+	}
+
+	return B2buoyancyController {
+		m_world: Rc::downgrade(&world),
+		m_fixture: fixture,
+		m_def: *def,
+		m_submerged: Vec::new(),
+	};
+}
+
+// The counter-clockwise world polygon of a circle or polygon shape.
+fn world_polygon(shape: &dyn B2shapeDynTrait, xf: B2Transform) -> Option<Vec<B2vec2>> {
+	match shape.as_derived() {
+		ShapeAsDerived::AsCircle(circle) => {
+			let center = b2_mul_transform_by_vec2(xf, circle.m_p);
+			let radius = circle.base.m_radius;
+			let vertices = (0..B2_CIRCLE_SEGMENTS)
+				.map(|i| {
+					let angle = 2.0 * B2_PI * i as f32 / B2_CIRCLE_SEGMENTS as f32;
+					center + radius * B2vec2::new(f32::cos(angle), f32::sin(angle))
+				})
+				.collect();
+			return Some(vertices);
+		}
+		ShapeAsDerived::AsPolygon(polygon) => {
+			let vertices = polygon.m_vertices[..polygon.m_count]
+				.iter()
+				.map(|v| b2_mul_transform_by_vec2(xf, *v))
+				.collect();
+			return Some(vertices);
+		}
+		_ => {
+			return None;
+		}
+	}
+}
+
+// Sutherland-Hodgman clipping of a polygon against a convex counter-clockwise polygon.
+fn clip_polygon(subject: &[B2vec2], clip: &[B2vec2]) -> Vec<B2vec2> {
+	let mut output: Vec<B2vec2> = subject.to_vec();
+	let clip_count = clip.len();
+	for i in 0..clip_count {
+		if output.is_empty() {
+			break;
+		}
+
+		let a = clip[i];
+		let b = clip[if i + 1 < clip_count { i + 1 } else { 0 }];
+		let edge = b - a;
+
+		let input = std::mem::take(&mut output);
+		let count = input.len();
+		for j in 0..count {
+			let p1 = input[if j == 0 { count - 1 } else { j - 1 }];
+			let p2 = input[j];
+			let d1 = b2_cross(edge, p1 - a);
+			let d2 = b2_cross(edge, p2 - a);
+
+			if d2 >= 0.0 {
+				if d1 < 0.0 {
+					output.push(p1 + (d1 / (d1 - d2)) * (p2 - p1));
+				}
+				output.push(p2);
+			} else if d1 >= 0.0 {
+				output.push(p1 + (d1 / (d1 - d2)) * (p2 - p1));
+			}
+		}
+	}
+	return output;
+}
+
+pub(crate) fn step<D: UserDataType>(self_: &mut B2buoyancyController<D>) {
+	let world = upgrade(&self_.m_world);
+	let world = world.borrow();
+	b2_assert(world.is_locked() == false);
+
+	self_.m_submerged.clear();
+
+	let gravity = world.get_gravity();
+	let fluid_body;
+	let fluid_polygon;
+	let aabb;
+	{
+		let fixture = self_.m_fixture.borrow();
+		fluid_body = fixture.get_body();
+		fluid_polygon = world_polygon(&*fixture.get_shape(), fluid_body.borrow().get_transform()).unwrap();
+		aabb = fixture.get_aabb(0);
+	}
+
+	let mut fixtures = Vec::<FixturePtr<D>>::new();
+	world.query_aabb(|fixture: FixturePtr<D>| -> bool {
+		{
+			let f = fixture.borrow();
+			let body = f.get_body();
+			let body = body.borrow();
+			if f.is_sensor() || body.get_type() != B2bodyType::B2DynamicBody || body.is_awake() == false {
+				return true;
+			}
+		}
+		if Rc::ptr_eq(&fixture.borrow().get_body(), &fluid_body) == false {
+			fixtures.push(fixture);
+		}
+		return true;
+	}, aabb);
+
+	// Accumulate the submerged area of all fixtures of each body. The mass centroid
+	// weights the areas by the fixture density.
+	struct Submerged<D: UserDataType> {
+		body: BodyPtr<D>,
+		area: f32,
+		area_centroid: B2vec2,
+		mass: f32,
+		mass_centroid: B2vec2,
+	}
+	let mut submerged = Vec::<Submerged<D>>::new();
+	for fixture in fixtures {
+		let fixture = fixture.borrow();
+		let body = fixture.get_body();
+		let polygon = match world_polygon(&*fixture.get_shape(), body.borrow().get_transform()) {
+			Some(polygon) => polygon,
+			None => continue,
+		};
+
+		let clipped = clip_polygon(&polygon, &fluid_polygon);
+		if clipped.len() < 3 {
+			continue;
+		}
+
+		let mut mass_data = B2massData::default();
+		let area = b2_compute_polygon_mass(&clipped, 1.0, &mut mass_data);
+		if area <= B2_EPSILON {
+			continue;
+		}
+
+		let index = match submerged.iter().position(|s| Rc::ptr_eq(&s.body, &body)) {
+			Some(index) => index,
+			None => {
+				submerged.push(Submerged {
+					body: body.clone(),
+					area: 0.0,
+					area_centroid: B2vec2::zero(),
+					mass: 0.0,
+					mass_centroid: B2vec2::zero(),
+				});
+				submerged.len() - 1
+			}
+		};
+		let s = &mut submerged[index];
+		let density = fixture.get_density();
+		s.area += area;
+		s.area_centroid += area * mass_data.center;
+		s.mass += area * density;
+		s.mass_centroid += area * density * mass_data.center;
+	}
+
+	let def = self_.m_def;
+	for s in submerged {
+		let area_centroid = (1.0 / s.area) * s.area_centroid;
+		let mass_centroid = if s.mass > B2_EPSILON { (1.0 / s.mass) * s.mass_centroid } else { area_centroid };
+
+		let mut body = s.body.borrow_mut();
+
+		// Buoyancy
+		let buoyancy_force = -def.density * s.area * gravity;
+		body.apply_force(buoyancy_force, mass_centroid, true);
+
+		// Linear drag
+		let relative_velocity = body.get_linear_velocity_from_world_point(area_centroid) - def.flow_velocity;
+		let drag_force = -def.linear_drag * s.area * relative_velocity;
+		body.apply_force(drag_force, area_centroid, true);
+
+		// Angular drag
+		let mass = body.get_mass();
+		if mass > 0.0 {
+			let torque = -body.get_inertia() / mass * s.area * body.get_angular_velocity() * def.angular_drag;
+			body.apply_torque(torque, true);
+		}
+
+		self_.m_submerged.push(B2submergedBody {
+			body: s.body.clone(),
+			area: s.area,
+			centroid: area_centroid,
+		});
+	}
+}
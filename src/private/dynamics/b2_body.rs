@@ -2,6 +2,7 @@ use crate::b2_body::*;
 use crate::b2_fixture::*;
 use crate::b2_math::*;
 use crate::b2_common::*;
+use crate::b2_contact_manager::*;
 use crate::b2rs_common::*;
 use crate::b2_shape::*;
 use crate::b2_world::*;
@@ -19,6 +20,8 @@ pub fn b2_body<D: UserDataType>(bd: &B2bodyDef<D>, world: B2worldPtr<D>) -> B2bo
 	b2_assert(b2_is_valid(bd.angular_velocity));
 	b2_assert(b2_is_valid(bd.angular_damping) && bd.angular_damping >= 0.0);
 	b2_assert(b2_is_valid(bd.linear_damping) && bd.linear_damping >= 0.0);
+	b2_assert(bd.max_linear_speed >= 0.0);
+	b2_assert(bd.max_angular_speed >= 0.0);
 
 	let mut m_flags = BodyFlags::default();
 
@@ -28,6 +31,12 @@ pub fn b2_body<D: UserDataType>(bd: &B2bodyDef<D>, world: B2worldPtr<D>) -> B2bo
 	if bd.fixed_rotation {
 		m_flags.insert(BodyFlags::E_FIXED_ROTATION_FLAG);
 	}
+	if bd.fixed_translation_x {
+		m_flags.insert(BodyFlags::E_FIXED_TRANSLATION_X_FLAG);
+	}
+	if bd.fixed_translation_y {
+		m_flags.insert(BodyFlags::E_FIXED_TRANSLATION_Y_FLAG);
+	}
 	if bd.allow_sleep {
 		m_flags.insert(BodyFlags::E_AUTO_SLEEP_FLAG);
 	}
@@ -66,6 +75,11 @@ pub fn b2_body<D: UserDataType>(bd: &B2bodyDef<D>, world: B2worldPtr<D>) -> B2bo
 		m_angular_damping: bd.angular_damping,
 		m_gravity_scale: bd.gravity_scale,
 
+		m_max_linear_speed: bd.max_linear_speed,
+		m_max_angular_speed: bd.max_angular_speed,
+
+		m_target_sweep: None,
+
 		m_force: B2vec2::zero(),
 		m_torque: 0.0,
 
@@ -153,10 +167,15 @@ pub fn set_type<D: UserDataType>(self_: BodyPtr<D>, body_type: B2bodyType) {
 }
 
 pub fn create_fixture<D: UserDataType>(self_: BodyPtr<D>, def: &B2fixtureDef<D>) -> FixturePtr<D> {
+	let world = upgrade(&self_.borrow().m_world);
+	let mut world = world.borrow_mut();
+	return create_fixture_by_world(self_, &mut world, def);
+}
+
+pub fn create_fixture_by_world<D: UserDataType>(self_: BodyPtr<D>, world: &mut B2world<D>, def: &B2fixtureDef<D>) -> FixturePtr<D> {
 	let mut self_mut = self_.borrow_mut();
-	let world = upgrade(&self_mut.m_world);
-	b2_assert(world.borrow().is_locked() == false);
-	if world.borrow().is_locked() == true {
+	b2_assert(world.is_locked() == false);
+	if world.is_locked() == true {
 		panic!();
 	}
 
@@ -168,7 +187,6 @@ pub fn create_fixture<D: UserDataType>(self_: BodyPtr<D>, def: &B2fixtureDef<D>)
 
 	if self_mut.m_flags.contains(BodyFlags::E_ENABLED_FLAG) {
 		let broad_phase = world
-			.borrow()
 			.m_contact_manager
 			.borrow()
 			.m_broad_phase
@@ -194,7 +212,7 @@ pub fn create_fixture<D: UserDataType>(self_: BodyPtr<D>, def: &B2fixtureDef<D>)
 
 	// Let the world know we have a new fixture. This will cause new contacts
 	// to be created at the beginning of the next time step.
-	world.borrow_mut().m_new_contacts = true;
+	world.m_new_contacts = true;
 
 	return fixture;
 }
@@ -212,21 +230,42 @@ pub fn create_fixture_by_shape<D: UserDataType>(
 }
 
 pub fn destroy_fixture<D: UserDataType>(self_: BodyPtr<D>, fixture: FixturePtr<D>) {
-	let world;
+	let world = upgrade(&self_.borrow().m_world);
+	let m_contact_manager;
+	{
+		let world = world.borrow();
+		b2_assert(world.is_locked() == false);
+		if world.is_locked() == true {
+			return;
+		}
+		m_contact_manager = world.m_contact_manager.clone();
+	}
+
+	destroy_fixture_internal(self_, m_contact_manager, fixture);
+}
+
+pub fn destroy_fixture_by_world<D: UserDataType>(self_: BodyPtr<D>, world: &B2world<D>, fixture: FixturePtr<D>) {
+	b2_assert(world.is_locked() == false);
+	if world.is_locked() == true {
+		return;
+	}
+
+	destroy_fixture_internal(self_, world.m_contact_manager.clone(), fixture);
+}
+
+fn destroy_fixture_internal<D: UserDataType>(
+	self_: BodyPtr<D>,
+	m_contact_manager: B2contactManagerPtr<D>,
+	fixture: FixturePtr<D>,
+) {
 	let m_contact_list;
 	let m_fixture_count;
 	{
 		let self_ = self_.borrow();
-		world = upgrade(&self_.m_world.clone());
 		m_contact_list = self_.m_contact_list.clone();
 		m_fixture_count = self_.m_fixture_count;
 	}
 
-	b2_assert(world.borrow().is_locked() == false);
-	if world.borrow().is_locked() == true {
-		return;
-	}
-
 	b2_assert(ptr::eq(
 		upgrade_opt(&fixture.borrow().m_body).as_ref(),
 		self_.as_ref(),
@@ -239,7 +278,6 @@ pub fn destroy_fixture<D: UserDataType>(self_: BodyPtr<D>, fixture: FixturePtr<D
 
 	// // destroy any contacts associated with the fixture.
 	//let edge = self_mut.m_contact_list;
-	let m_contact_manager = world.borrow().m_contact_manager.clone();
 	for edge in m_contact_list.iter() {
 		let edge_ref = edge.borrow();
 		let edge_ref_contact = edge_ref.contact.upgrade().unwrap();
@@ -430,6 +468,7 @@ pub fn set_transform<D: UserDataType>(self_: &mut B2body<D>, position: B2vec2, a
 
 	self_.m_sweep.c0 = self_.m_sweep.c;
 	self_.m_sweep.a0 = angle;
+	self_.m_target_sweep = None;
 
 	let contact_manager = world.borrow().m_contact_manager.clone();
 	let broad_phase_rc = contact_manager.borrow().m_broad_phase.clone();
@@ -537,4 +576,44 @@ pub fn set_fixed_rotation<D: UserDataType>(self_: &mut B2body<D>, flag: bool) {
 	self_.m_angular_velocity = 0.0;
 
 	self_.reset_mass_data();
-}
\ No newline at end of file
+}
+
+pub fn set_fixed_translation_x<D: UserDataType>(self_: &mut B2body<D>, flag: bool) {
+	self_.m_flags.set(BodyFlags::E_FIXED_TRANSLATION_X_FLAG, flag);
+	if flag {
+		self_.m_linear_velocity.x = 0.0;
+	}
+}
+
+pub fn set_fixed_translation_y<D: UserDataType>(self_: &mut B2body<D>, flag: bool) {
+	self_.m_flags.set(BodyFlags::E_FIXED_TRANSLATION_Y_FLAG, flag);
+	if flag {
+		self_.m_linear_velocity.y = 0.0;
+	}
+}
+
+// The angle in radians wrapped to [-pi, pi].
+pub(crate) fn wrap_angle(angle: f32) -> f32 {
+	let two_pi: f32 = 2.0 * B2_PI;
+	return angle - two_pi * f32::floor((angle + B2_PI) / two_pi);
+}
+
+pub fn set_target_transform<D: UserDataType>(self_: &mut B2body<D>, target: B2Transform, dt: f32) {
+	b2_assert(dt > 0.0);
+	if self_.m_type == B2bodyType::B2StaticBody || dt <= 0.0 {
+		return;
+	}
+
+	let inv_dt = 1.0 / dt;
+	let c = b2_mul_transform_by_vec2(target, self_.m_sweep.local_center);
+	let da = wrap_angle(target.q.get_angle() - self_.m_sweep.a);
+
+	self_.m_target_sweep = Some((c, self_.m_sweep.a + da));
+	self_.set_linear_velocity(inv_dt * (c - self_.m_sweep.c));
+	self_.set_angular_velocity(inv_dt * da);
+
+	if self_.m_flags.contains(BodyFlags::E_SNAP_TO_TARGET_FLAG) {
+		// Make sure the body is solved so the target is not left pending.
+		self_.set_awake(true);
+	}
+}
@@ -3,6 +3,7 @@ use crate::b2_collision::*;
 use crate::b2_contact::*;
 use crate::b2_fixture::*;
 use crate::b2_math::*;
+use crate::b2_common::*;
 use crate::b2rs_common::UserDataType;
 use crate::b2_shape::*;
 use crate::b2_world_callbacks::*;
@@ -92,17 +93,124 @@ pub fn b2_contact_new<D: UserDataType>(
 
 		m_tangent_speed: 0.0,
 
+		m_speculative_distance: 0.0,
+
 		m_toi: 0.0,
 		//s_initialized: false,
 		//s_registers: Default::default(),
 	};
 }
 
+// Normals more than 60 degrees away from the one-way direction never collide.
+const B2_ONE_WAY_MIN_NORMAL_DOT: f32 = 0.5;
+
+// The contacts found by the TOI solver start at about -3 slops of separation.
+const B2_ONE_WAY_SEPARATION_TOLERANCE: f32 = 4.0 * B2_LINEAR_SLOP;
+
+// Where a world point attached to the body was at the start of the last step.
+fn b2_previous_point<D: UserDataType>(body: &B2body<D>, point: B2vec2) -> B2vec2 {
+	let mut xf0 = B2Transform::default();
+	body.m_sweep.get_transform(&mut xf0, 0.0);
+	return b2_mul_transform_by_vec2(xf0, b2_mul_t_transform_by_vec2(body.m_xf, point));
+}
+
+// Decide if a touching contact with a one-way fixture is solid. The normal must face the
+// one-way direction. A new contact must also not move away along that direction, and the other
+// fixture must have been on the solid side at the start of the last step. Once solid, the
+// contact stays solid while it touches.
+fn b2_one_way_solid<D: UserDataType>(
+	self_: &B2contact<D>,
+	body_a: &BodyPtr<D>,
+	body_b: &BodyPtr<D>,
+	xf_a: B2Transform,
+	xf_b: B2Transform,
+	was_solid: bool,
+) -> bool {
+	let fixture_a = self_.m_fixture_a.borrow();
+	let fixture_b = self_.m_fixture_b.borrow();
+	if fixture_a.m_one_way_direction.is_none() && fixture_b.m_one_way_direction.is_none() {
+		return true;
+	}
+
+	let mut world_manifold = B2worldManifold::default();
+	world_manifold.initialize(
+		&self_.m_manifold,
+		xf_a,
+		fixture_a.get_shape().get_base().m_radius,
+		xf_b,
+		fixture_b.get_shape().get_base().m_radius,
+	);
+
+	let body_a = body_a.borrow();
+	let body_b = body_b.borrow();
+	let sides = [
+		(fixture_a.m_one_way_direction, xf_a, &body_a, &body_b, 1.0),
+		(fixture_b.m_one_way_direction, xf_b, &body_b, &body_a, -1.0),
+	];
+	for (direction, xf, body, other, sign) in sides {
+		let direction = match direction {
+			Some(direction) => b2_mul_rot_by_vec2(xf.q, direction),
+			None => continue,
+		};
+
+		// The normal pointing from the one-way fixture to the other fixture.
+		let normal: B2vec2 = sign * world_manifold.normal;
+		if b2_dot(normal, direction) < B2_ONE_WAY_MIN_NORMAL_DOT {
+			return false;
+		}
+
+		if was_solid {
+			continue;
+		}
+
+		for i in 0..self_.m_manifold.point_count {
+			let point: B2vec2 = world_manifold.points[i];
+			let relative_velocity: B2vec2 = other.get_linear_velocity_from_world_point(point)
+				- body.get_linear_velocity_from_world_point(point);
+			if b2_dot(relative_velocity, direction) > B2_LINEAR_SLOP {
+				return false;
+			}
+
+			// Undo the relative motion of the last step along the direction.
+			let relative_motion: B2vec2 = (point - b2_previous_point(other, point))
+				- (point - b2_previous_point(body, point));
+			let separation: f32 = world_manifold.separations[i] - b2_dot(relative_motion, direction);
+			if separation < -B2_ONE_WAY_SEPARATION_TOLERANCE {
+				return false;
+			}
+		}
+	}
+
+	return true;
+}
+
+// How far the two fixtures can close within the speculative time, from the relative velocity
+// of the bodies and how far the fixtures reach out from the centers of mass.
+fn b2_speculative_distance<D: UserDataType>(
+	self_: &B2contact<D>,
+	body_a: &B2body<D>,
+	body_b: &B2body<D>,
+	speculative_time: f32,
+) -> f32 {
+	let reach = |fixture: &FixturePtr<D>, index: i32, body: &B2body<D>| -> f32 {
+		let aabb: B2AABB = fixture.borrow().get_aabb(index);
+		let center: B2vec2 = body.get_world_center();
+		let d = b2_max_vec2(b2_abs_vec2(aabb.lower_bound - center), b2_abs_vec2(aabb.upper_bound - center));
+		return d.length();
+	};
+
+	let speed: f32 = (body_b.m_linear_velocity - body_a.m_linear_velocity).length()
+		+ b2_abs(body_a.m_angular_velocity) * reach(&self_.m_fixture_a, self_.m_index_a, body_a)
+		+ b2_abs(body_b.m_angular_velocity) * reach(&self_.m_fixture_b, self_.m_index_b, body_b);
+	return speculative_time * speed;
+}
+
 // update the contact manifold and touching status.
 // Note: do not assume the fixture AABBs are overlapping or are valid.
 pub fn b2_contact_update<D: UserDataType>(
 	self_: &mut dyn B2contactDynTrait<D>,
-	listener: Option<B2contactListenerPtr<D>>
+	listener: Option<B2contactListenerPtr<D>>,
+	speculative_time: f32,
 ) {
 	{
 		let self_ = self_.get_base_mut();
@@ -162,6 +270,15 @@ pub fn b2_contact_update<D: UserDataType>(
 		// Sensors don't generate manifolds.
 		self_.m_manifold.point_count = 0;
 	} else {
+		{
+			let self_ = self_.get_base_mut();
+			self_.m_speculative_distance = if speculative_time > 0.0 {
+				b2_speculative_distance(self_, &body_a.borrow(), &body_b.borrow(), speculative_time)
+			} else {
+				0.0
+			};
+		}
+
 		let mut new_manifold = B2manifold::default();
 		self_.evaluate(&mut new_manifold, &xf_a, &xf_b);
 		let self_ = self_.get_base_mut();
@@ -191,6 +308,15 @@ pub fn b2_contact_update<D: UserDataType>(
 			body_a.borrow_mut().set_awake(true);
 			body_b.borrow_mut().set_awake(true);
 		}
+
+		if touching {
+			let was_solid = was_touching && self_.m_flags.contains(ContactFlags::E_ONE_WAY_SOLID_FLAG);
+			let solid = b2_one_way_solid(self_, &body_a, &body_b, xf_a, xf_b, was_solid);
+			self_.m_flags.set(ContactFlags::E_ONE_WAY_SOLID_FLAG, solid);
+			if solid == false {
+				self_.m_flags.remove(ContactFlags::E_ENABLED_FLAG);
+			}
+		}
 	}
 
 	{
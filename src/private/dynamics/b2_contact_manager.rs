@@ -83,10 +83,10 @@ pub fn b2_contact_manager_destroy<D: UserDataType>(
 pub fn b2_contact_manager_collide<D: UserDataType>(self_: B2contactManagerPtr<D>) {
 	let mut contacts_to_destroy = Vec::<ContactPtr<D>>::new();
 
-	let (m_contact_list, m_broad_phase, m_contact_filter,m_contact_listener) = {
+	let (m_contact_list, m_broad_phase, m_contact_filter,m_contact_listener,speculative_time) = {
 		let self_ = self_.borrow();
 		//assert!(self_.m_contact_count==self_.m_contact_list.len());
-		(self_.m_contact_list.clone(),self_.m_broad_phase.clone(),self_.m_contact_filter.clone(),self_.m_contact_listener.clone())
+		(self_.m_contact_list.clone(),self_.m_broad_phase.clone(),self_.m_contact_filter.clone(),self_.m_contact_listener.clone(),self_.m_speculative_time)
 	};
 
 	// update awake contacts.
@@ -158,6 +158,7 @@ pub fn b2_contact_manager_collide<D: UserDataType>(self_: B2contactManagerPtr<D>
 		B2contact::update(
 			&mut *c.borrow_mut(),
 			m_contact_listener.clone(),
+			speculative_time,
 		);
 	}
 
@@ -7,6 +7,7 @@ use crate::b2_shape::*;
 use super::contacts::b2_chain_circle_contact::*;
 use super::contacts::b2_chain_polygon_contact::*;
 use super::contacts::b2_circle_contact::*;
+use super::contacts::b2_custom_contact::*;
 use super::contacts::b2_edge_circle_contact::*;
 use super::contacts::b2_edge_polygon_contact::*;
 use super::contacts::b2_polygon_circle_contact::*;
@@ -33,7 +34,7 @@ impl<D: UserDataType> Default for ContactRegisters<D> {
 		let def_value = || <[B2contactRegister<D>; B2ShapeType::ETypeCount as usize]>::default();
 		let mut result = Self
 		{
-			s_registers: [def_value(),def_value(),def_value(),def_value()]
+			s_registers: [def_value(),def_value(),def_value(),def_value(),def_value()]
 		};
 		ContactRegisters::initialize_registers(&mut result);
 		return result;
@@ -100,5 +101,30 @@ impl<D: UserDataType> ContactRegisters<D>
 			B2ShapeType::EChain,
 			B2ShapeType::EPolygon,
 		);
+		Self::add_type(self_,
+			B2customContact::create,
+			B2ShapeType::ECustom,
+			B2ShapeType::ECircle,
+		);
+		Self::add_type(self_,
+			B2customContact::create,
+			B2ShapeType::ECustom,
+			B2ShapeType::EPolygon,
+		);
+		Self::add_type(self_,
+			B2customContact::create,
+			B2ShapeType::ECustom,
+			B2ShapeType::ECustom,
+		);
+		Self::add_type(self_,
+			B2customContact::create,
+			B2ShapeType::EEdge,
+			B2ShapeType::ECustom,
+		);
+		Self::add_type(self_,
+			B2customContact::create,
+			B2ShapeType::EChain,
+			B2ShapeType::ECustom,
+		);
 	}
 }
\ No newline at end of file
@@ -28,8 +28,8 @@ pub(crate) struct B2contactVelocityConstraint
 	pub(crate) k: B2Mat22,
 	pub(crate) index_a: i32,
 	pub(crate) index_b: i32,
-	pub(crate) inv_mass_a: f32,
-	pub(crate) inv_mass_b: f32,
+	pub(crate) inv_mass_a: B2vec2,
+	pub(crate) inv_mass_b: B2vec2,
 	pub(crate) inv_ia: f32,
 	pub(crate) inv_ib: f32,
 	pub(crate) friction: f32,
@@ -106,8 +106,8 @@ pub(crate) struct B2contactPositionConstraint
 	pub(crate) local_point: B2vec2,
 	pub(crate) index_a: i32,
 	pub(crate) index_b: i32,
-	pub(crate) inv_mass_a: f32, 
-	pub(crate) inv_mass_b: f32,
+	pub(crate) inv_mass_a: B2vec2, 
+	pub(crate) inv_mass_b: B2vec2,
 	pub(crate) local_center_a: B2vec2, 
 	pub(crate) local_center_b: B2vec2,
 	pub(crate) inv_ia: f32, 
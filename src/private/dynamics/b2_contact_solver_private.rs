@@ -5,6 +5,7 @@ use crate::b2_common::*;
 use crate::b2rs_common::*;
 use crate::b2_time_step::*;
 use crate::b2_collision::*;
+use crate::b2_body::*;
 
 use std::sync::atomic::Ordering;
 
@@ -17,6 +18,27 @@ const B2_DEBUG_SOLVER: bool = false;
 //struct B2contactPositionConstraint
 //moved to header
 
+// Per-axis inverse mass of a body. A locked axis has no inverse mass, so contacts
+// treat the body as immovable along it.
+fn b2_axis_inv_mass<D: UserDataType>(body: &B2body<D>) -> B2vec2
+{
+	let inv_mass_x = if body.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_X_FLAG) { 0.0 } else { body.m_inv_mass };
+	let inv_mass_y = if body.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_Y_FLAG) { 0.0 } else { body.m_inv_mass };
+	return B2vec2::new(inv_mass_x, inv_mass_y);
+}
+
+// Linear part of the effective mass along the direction d.
+fn b2_axis_mass(m: B2vec2, d: B2vec2) -> f32
+{
+	return m.x * d.x * d.x + m.y * d.y * d.y;
+}
+
+// Scales the impulse p by the per-axis inverse mass m.
+fn b2_axis_scale(m: B2vec2, p: B2vec2) -> B2vec2
+{
+	return B2vec2::new(m.x * p.x, m.y * p.y);
+}
+
 pub(crate) fn new<D: UserDataType>(def: &B2contactSolverDef, contacts: &Vec<ContactPtr<D>>) -> B2contactSolver
 {
 	let mut result = B2contactSolver::default();
@@ -55,8 +77,8 @@ pub(crate) fn new<D: UserDataType>(def: &B2contactSolverDef, contacts: &Vec<Cont
 		vc.tangent_speed = contact.m_tangent_speed;
 		vc.index_a = body_a.m_island_index;
 		vc.index_b = body_b.m_island_index;
-		vc.inv_mass_a = body_a.m_inv_mass;
-		vc.inv_mass_b = body_b.m_inv_mass;
+		vc.inv_mass_a = b2_axis_inv_mass(&body_a);
+		vc.inv_mass_b = b2_axis_inv_mass(&body_b);
 		vc.inv_ia = body_a.m_inv_i;
 		vc.inv_ib = body_b.m_inv_i;
 		vc.contact_index = i as i32;
@@ -67,8 +89,8 @@ pub(crate) fn new<D: UserDataType>(def: &B2contactSolverDef, contacts: &Vec<Cont
 		let mut pc = &mut result.m_position_constraints[i];
 		pc.index_a = body_a.m_island_index;
 		pc.index_b = body_b.m_island_index;
-		pc.inv_mass_a = body_a.m_inv_mass;
-		pc.inv_mass_b = body_b.m_inv_mass;
+		pc.inv_mass_a = b2_axis_inv_mass(&body_a);
+		pc.inv_mass_b = b2_axis_inv_mass(&body_b);
 		pc.local_center_a = body_a.m_sweep.local_center;
 		pc.local_center_b = body_b.m_sweep.local_center;
 		pc.inv_ia = body_a.m_inv_i;
@@ -126,8 +148,8 @@ pub(crate) fn initialize_velocity_constraints<D: UserDataType>(self_: &mut B2con
 		let index_a: i32 =vc.index_a;
 		let index_b: i32 =vc.index_b;
 
-		let m_a: f32 =vc.inv_mass_a;
-		let m_b: f32 =vc.inv_mass_b;
+		let m_a: B2vec2 =vc.inv_mass_a;
+		let m_b: B2vec2 =vc.inv_mass_b;
 		let i_a: f32 =vc.inv_ia;
 		let i_b: f32 =vc.inv_ib;
 		let local_center_a: B2vec2 =pc.local_center_a;
@@ -168,7 +190,7 @@ pub(crate) fn initialize_velocity_constraints<D: UserDataType>(self_: &mut B2con
 			let rn_a: f32 =b2_cross(vcp.r_a, vc.normal);
 			let rn_b: f32 =b2_cross(vcp.r_b, vc.normal);
 
-			let k_normal: f32 =m_a + m_b + i_a * rn_a * rn_a + i_b * rn_b * rn_b;
+			let k_normal: f32 =b2_axis_mass(m_a + m_b, vc.normal) + i_a * rn_a * rn_a + i_b * rn_b * rn_b;
 
 			vcp.normal_mass = if k_normal > 0.0 { 1.0 / k_normal }else{ 0.0};
 
@@ -177,7 +199,7 @@ pub(crate) fn initialize_velocity_constraints<D: UserDataType>(self_: &mut B2con
 			let rt_a: f32 =b2_cross(vcp.r_a, tangent);
 			let rt_b: f32 =b2_cross(vcp.r_b, tangent);
 
-			let k_tangent: f32 =m_a + m_b + i_a * rt_a * rt_a + i_b * rt_b * rt_b;
+			let k_tangent: f32 =b2_axis_mass(m_a + m_b, tangent) + i_a * rt_a * rt_a + i_b * rt_b * rt_b;
 
 			vcp.tangent_mass = if k_tangent > 0.0 { 1.0 /  k_tangent} else {0.0};
 
@@ -188,6 +210,14 @@ pub(crate) fn initialize_velocity_constraints<D: UserDataType>(self_: &mut B2con
 			{
 				vcp.velocity_bias = -vc.restitution * v_rel;
 			}
+
+			// A speculative point is still apart. Only stop the approach that would close
+			// the gap within this step.
+			let separation: f32 = world_manifold.separations[j];
+			if self_.m_step.speculative_contacts && separation > 0.0
+			{
+				vcp.velocity_bias = -separation * self_.m_step.inv_dt;
+			}
 		}
 
 		// If we have two points, then prepare the block solver.
@@ -202,9 +232,9 @@ pub(crate) fn initialize_velocity_constraints<D: UserDataType>(self_: &mut B2con
 			let rn2_a: f32 =b2_cross(vcp2.r_a, vc.normal);
 			let rn2_b: f32 =b2_cross(vcp2.r_b, vc.normal);
 
-			let k11: f32 =m_a + m_b + i_a * rn1_a * rn1_a + i_b * rn1_b * rn1_b;
-			let k22: f32 =m_a + m_b + i_a * rn2_a * rn2_a + i_b * rn2_b * rn2_b;
-			let k12: f32 =m_a + m_b + i_a * rn1_a * rn2_a + i_b * rn1_b * rn2_b;
+			let k11: f32 =b2_axis_mass(m_a + m_b, vc.normal) + i_a * rn1_a * rn1_a + i_b * rn1_b * rn1_b;
+			let k22: f32 =b2_axis_mass(m_a + m_b, vc.normal) + i_a * rn2_a * rn2_a + i_b * rn2_b * rn2_b;
+			let k12: f32 =b2_axis_mass(m_a + m_b, vc.normal) + i_a * rn1_a * rn2_a + i_b * rn1_b * rn2_b;
 
 			// Ensure a reasonable condition number.
 			const K_MAX_CONDITION_NUMBER: f32 = 1000.0;
@@ -234,9 +264,9 @@ pub(crate) fn warm_start(self_: &mut B2contactSolver, m_velocities: &mut [B2velo
 
 		let index_a: i32 =vc.index_a;
 		let index_b: i32 =vc.index_b;
-		let m_a: f32 =vc.inv_mass_a;
+		let m_a: B2vec2 =vc.inv_mass_a;
 		let i_a: f32 =vc.inv_ia;
-		let m_b: f32 =vc.inv_mass_b;
+		let m_b: B2vec2 =vc.inv_mass_b;
 		let i_b: f32 =vc.inv_ib;
 		let point_count =vc.point_count as usize;
 
@@ -253,9 +283,9 @@ pub(crate) fn warm_start(self_: &mut B2contactSolver, m_velocities: &mut [B2velo
 			let vcp = &vc.points[j];
 			let p: B2vec2 =vcp.normal_impulse * normal + vcp.tangent_impulse * tangent;
 			w_a -= i_a * b2_cross(vcp.r_a, p);
-			v_a -= m_a * p;
+			v_a -= b2_axis_scale(m_a, p);
 			w_b += i_b * b2_cross(vcp.r_b, p);
-			v_b += m_b * p;
+			v_b += b2_axis_scale(m_b, p);
 		}
 
 		m_velocities[index_a as usize].v = v_a;
@@ -273,9 +303,9 @@ pub(crate) fn solve_velocity_constraints(self_: &mut B2contactSolver, m_velociti
 
 		let index_a: i32 =vc.index_a;
 		let index_b: i32 =vc.index_b;
-		let m_a: f32 =vc.inv_mass_a;
+		let m_a: B2vec2 =vc.inv_mass_a;
 		let i_a: f32 =vc.inv_ia;
-		let m_b: f32 =vc.inv_mass_b;
+		let m_b: B2vec2 =vc.inv_mass_b;
 		let i_b: f32 =vc.inv_ib;
 		let point_count =vc.point_count as usize;
 
@@ -312,10 +342,10 @@ pub(crate) fn solve_velocity_constraints(self_: &mut B2contactSolver, m_velociti
 			// Apply contact impulse
 			let p: B2vec2 =lambda * tangent;
 
-			v_a -= m_a * p;
+			v_a -= b2_axis_scale(m_a, p);
 			w_a -= i_a * b2_cross(vcp.r_a, p);
 
-			v_b += m_b * p;
+			v_b += b2_axis_scale(m_b, p);
 			w_b += i_b * b2_cross(vcp.r_b, p);
 		}
 
@@ -341,10 +371,10 @@ pub(crate) fn solve_velocity_constraints(self_: &mut B2contactSolver, m_velociti
 
 				// Apply contact impulse
 				let p: B2vec2 =lambda * normal;
-				v_a -= m_a * p;
+				v_a -= b2_axis_scale(m_a, p);
 				w_a -= i_a * b2_cross(vcp.r_a, p);
 
-				v_b += m_b * p;
+				v_b += b2_axis_scale(m_b, p);
 				w_b += i_b * b2_cross(vcp.r_b, p);
 			}
 		}
@@ -429,10 +459,10 @@ pub(crate) fn solve_velocity_constraints(self_: &mut B2contactSolver, m_velociti
 					// Apply incremental impulse
 					let p1: B2vec2 =d.x * normal;
 					let p2: B2vec2 =d.y * normal;
-					v_a -= m_a * (p1 + p2);
+					v_a -= b2_axis_scale(m_a, p1 + p2);
 					w_a -= i_a * (b2_cross(cp1.r_a, p1) + b2_cross(cp2.r_a, p2));
 
-					v_b += m_b * (p1 + p2);
+					v_b += b2_axis_scale(m_b, p1 + p2);
 					w_b += i_b * (b2_cross(cp1.r_b, p1) + b2_cross(cp2.r_b, p2));
 
 					// Accumulate
@@ -473,10 +503,10 @@ pub(crate) fn solve_velocity_constraints(self_: &mut B2contactSolver, m_velociti
 					// Apply incremental impulse
 					let p1: B2vec2 =d.x * normal;
 					let p2: B2vec2 =d.y * normal;
-					v_a -= m_a * (p1 + p2);
+					v_a -= b2_axis_scale(m_a, p1 + p2);
 					w_a -= i_a * (b2_cross(cp1.r_a, p1) + b2_cross(cp2.r_a, p2));
 
-					v_b += m_b * (p1 + p2);
+					v_b += b2_axis_scale(m_b, p1 + p2);
 					w_b += i_b * (b2_cross(cp1.r_b, p1) + b2_cross(cp2.r_b, p2));
 
 					// Accumulate
@@ -516,10 +546,10 @@ if B2_DEBUG_SOLVER
 					// Apply incremental impulse
 					let p1: B2vec2 =d.x * normal;
 					let p2: B2vec2 =d.y * normal;
-					v_a -= m_a * (p1 + p2);
+					v_a -= b2_axis_scale(m_a, p1 + p2);
 					w_a -= i_a * (b2_cross(cp1.r_a, p1) + b2_cross(cp2.r_a, p2));
 
-					v_b += m_b * (p1 + p2);
+					v_b += b2_axis_scale(m_b, p1 + p2);
 					w_b += i_b * (b2_cross(cp1.r_b, p1) + b2_cross(cp2.r_b, p2));
 
 					// Accumulate
@@ -557,10 +587,10 @@ if B2_DEBUG_SOLVER
 					// Apply incremental impulse
 					let p1: B2vec2 =d.x * normal;
 					let p2: B2vec2 =d.y * normal;
-					v_a -= m_a * (p1 + p2);
+					v_a -= b2_axis_scale(m_a, p1 + p2);
 					w_a -= i_a * (b2_cross(cp1.r_a, p1) + b2_cross(cp2.r_a, p2));
 
-					v_b += m_b * (p1 + p2);
+					v_b += b2_axis_scale(m_b, p1 + p2);
 					w_b += i_b * (b2_cross(cp1.r_b, p1) + b2_cross(cp2.r_b, p2));
 
 					// Accumulate
@@ -661,10 +691,10 @@ pub(crate) fn  solve_position_constraints(self_: &mut B2contactSolver, m_positio
 		let index_a: i32 =pc.index_a;
 		let index_b: i32 =pc.index_b;
 		let local_center_a: B2vec2 =pc.local_center_a;
-		let m_a: f32 =pc.inv_mass_a;
+		let m_a: B2vec2 =pc.inv_mass_a;
 		let i_a: f32 =pc.inv_ia;
 		let local_center_b: B2vec2 =pc.local_center_b;
-		let m_b: f32 =pc.inv_mass_b;
+		let m_b: B2vec2 =pc.inv_mass_b;
 		let i_b: f32 =pc.inv_ib;
 		let point_count =pc.point_count as usize;
 
@@ -703,17 +733,17 @@ pub(crate) fn  solve_position_constraints(self_: &mut B2contactSolver, m_positio
 			// Compute the effective mass.
 			let rn_a: f32 =b2_cross(r_a, normal);
 			let rn_b: f32 =b2_cross(r_b, normal);
-			let k: f32 =m_a + m_b + i_a * rn_a * rn_a + i_b * rn_b * rn_b;
+			let k: f32 =b2_axis_mass(m_a + m_b, normal) + i_a * rn_a * rn_a + i_b * rn_b * rn_b;
 
 			// Compute normal impulse
 			let impulse: f32 =if k > 0.0 {- c / k} else {0.0};
 
 			let p: B2vec2 =impulse * normal;
 
-			c_a -= m_a * p;
+			c_a -= b2_axis_scale(m_a, p);
 			a_a -= i_a * b2_cross(r_a, p);
 
-			c_b += m_b * p;
+			c_b += b2_axis_scale(m_b, p);
 			a_b += i_b * b2_cross(r_b, p);
 		}
 
@@ -745,7 +775,7 @@ pub(crate) fn solve_toiposition_constraints(self_: &mut B2contactSolver, toi_ind
 		let local_center_b: B2vec2 =pc.local_center_b;
 		let point_count =pc.point_count as usize;
 
-		let mut m_a: f32 =0.0;
+		let mut m_a: B2vec2 =B2vec2::zero();
 		let mut i_a: f32 =0.0;
 		if index_a == toi_index_a || index_a == toi_index_b
 		{
@@ -753,7 +783,7 @@ pub(crate) fn solve_toiposition_constraints(self_: &mut B2contactSolver, toi_ind
 			i_a = pc.inv_ia;
 		}
 
-		let mut m_b: f32 =0.0;
+		let mut m_b: B2vec2 =B2vec2::zero();
 		let mut i_b: f32 =0.0;
 		if index_b == toi_index_a || index_b == toi_index_b
 		{
@@ -796,17 +826,17 @@ pub(crate) fn solve_toiposition_constraints(self_: &mut B2contactSolver, toi_ind
 			// Compute the effective mass.
 			let rn_a: f32 =b2_cross(r_a, normal);
 			let rn_b: f32 =b2_cross(r_b, normal);
-			let k: f32 =m_a + m_b + i_a * rn_a * rn_a + i_b * rn_b * rn_b;
+			let k: f32 =b2_axis_mass(m_a + m_b, normal) + i_a * rn_a * rn_a + i_b * rn_b * rn_b;
 
 			// Compute normal impulse
 			let impulse: f32 = if k > 0.0 {- c / k} else {0.0};
 
 			let p: B2vec2 =impulse * normal;
 
-			c_a -= m_a * p;
+			c_a -= b2_axis_scale(m_a, p);
 			a_a -= i_a * b2_cross(r_a, p);
 
-			c_b += m_b * p;
+			c_b += b2_axis_scale(m_b, p);
 			a_b += i_b * b2_cross(r_b, p);
 		}
 
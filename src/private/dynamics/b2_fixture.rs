@@ -1,6 +1,7 @@
 use crate::b2_body::*;
 use crate::b2_broad_phase::*;
 use crate::b2_collision::*;
+use crate::b2_distance::*;
 use crate::b2_fixture::*;
 use crate::b2_math::*;
 use std::rc::Rc;
@@ -20,11 +21,22 @@ pub fn b2_fixture_default<T:UserDataType>() -> B2fixture<T> {
 		m_filter: B2filter::default(),
 		m_friction: 0.0,
 		m_is_sensor: false,
+		m_one_way_direction: None,
+		m_toughness: 0.0,
 		m_restitution: 0.0,
 		m_restitution_threshold: 0.0
 	};
 }
 
+pub fn b2_fixture_set_one_way_direction<T:UserDataType>(self_: &mut B2fixture<T>, direction: Option<B2vec2>)
+{
+	self_.m_one_way_direction = direction.map(|mut direction| {
+		let length: f32 = direction.normalize();
+		b2_assert(length > B2_EPSILON);
+		direction
+	});
+}
+
 pub fn b2_fixture_create<T:UserDataType>(
 	self_: &mut B2fixture<T>,
 	body: BodyPtr<T>,
@@ -42,6 +54,11 @@ pub fn b2_fixture_create<T:UserDataType>(
 
 	self_.m_is_sensor = def.is_sensor;
 
+	b2_fixture_set_one_way_direction(self_, def.one_way_direction);
+
+	b2_assert(def.toughness >= 0.0);
+	self_.m_toughness = def.toughness;
+
 	self_.m_shape = Some(def.shape.as_ref().unwrap().borrow().clone_rc());
 
 	// Reserve proxy space
@@ -216,4 +233,114 @@ pub fn b2_fixture_set_sensor<T:UserDataType>(self_: &mut B2fixture<T>, sensor: b
 		self_.m_body.as_ref().unwrap().upgrade().unwrap().borrow_mut().set_awake(true);
 		self_.m_is_sensor = sensor;
 	}
-}
\ No newline at end of file
+}
+// Lower bound of the distance between the shapes bounded by two AABBs.
+pub(crate) fn aabb_distance(a: B2AABB, b: B2AABB) -> f32 {
+	let d = b2_max_vec2(a.lower_bound - b.upper_bound, b.lower_bound - a.upper_bound);
+	return b2_max_vec2(d, B2vec2::zero()).length();
+}
+
+pub(crate) fn b2_fixture_child_distance<T:UserDataType>(
+	self_: &B2fixture<T>,
+	child_index: usize,
+	proxy_b: &B2distanceProxy,
+	transform_b: B2Transform,
+) -> B2distanceOutput {
+	let mut input = B2distanceInput::default();
+	crate::private::collision::b2_distance::set_shape(&mut input.proxy_a, &**self_.m_shape.as_ref().unwrap(), child_index);
+	input.proxy_b = proxy_b.clone();
+	input.transform_a = upgrade_opt(&self_.m_body).borrow().get_transform();
+	input.transform_b = transform_b;
+	input.use_radii = true;
+
+	let mut cache = B2simplexCache::default();
+	let mut output = B2distanceOutput::default();
+	b2_distance_fn(&mut output, &mut cache, &input);
+	return output;
+}
+
+pub(crate) fn make_distance_output(output: &B2distanceOutput, child_index_a: usize, child_index_b: usize) -> B2fixtureDistanceOutput {
+	let mut normal = output.point_b - output.point_a;
+	if output.distance > 0.0 {
+		normal.normalize();
+	} else {
+		normal.set_zero();
+	}
+	return B2fixtureDistanceOutput {
+		point_a: output.point_a,
+		point_b: output.point_b,
+		normal,
+		distance: output.distance,
+		child_index_a,
+		child_index_b,
+	};
+}
+
+pub fn b2_fixture_closest_point<T:UserDataType>(self_: &B2fixture<T>, p: B2vec2) -> B2fixtureDistanceOutput {
+	let mut proxy_b = B2distanceProxy::default();
+	proxy_b.set_vertices(&[p], 0.0);
+	let transform_b = B2Transform::new(B2vec2::zero(), B2Rot::new(0.0));
+
+	let shape = self_.m_shape.as_ref().unwrap();
+	let xf = upgrade_opt(&self_.m_body).borrow().get_transform();
+	let point_aabb = B2AABB { lower_bound: p, upper_bound: p };
+
+	let mut best = B2fixtureDistanceOutput {
+		distance: B2_MAX_FLOAT,
+		..Default::default()
+	};
+	for child_index in 0..shape.get_child_count()
+	{
+		let mut aabb = B2AABB::default();
+		shape.compute_aabb(&mut aabb, xf, child_index);
+		if aabb_distance(aabb, point_aabb) >= best.distance
+		{
+			continue;
+		}
+
+		let output = b2_fixture_child_distance(self_, child_index, &proxy_b, transform_b);
+		if output.distance < best.distance
+		{
+			best = make_distance_output(&output, child_index, 0);
+		}
+	}
+	return best;
+}
+
+pub fn b2_fixture_distance<T:UserDataType>(self_: &B2fixture<T>, other: &B2fixture<T>) -> B2fixtureDistanceOutput {
+	let shape_a = self_.m_shape.as_ref().unwrap();
+	let shape_b = other.m_shape.as_ref().unwrap();
+	let xf_a = upgrade_opt(&self_.m_body).borrow().get_transform();
+	let xf_b = upgrade_opt(&other.m_body).borrow().get_transform();
+
+	let mut best = B2fixtureDistanceOutput {
+		distance: B2_MAX_FLOAT,
+		..Default::default()
+	};
+	for child_index_b in 0..shape_b.get_child_count()
+	{
+		let mut aabb_b = B2AABB::default();
+		shape_b.compute_aabb(&mut aabb_b, xf_b, child_index_b);
+
+		let mut proxy_b = B2distanceProxy::default();
+		crate::private::collision::b2_distance::set_shape(&mut proxy_b, &**shape_b, child_index_b);
+
+		for child_index_a in 0..shape_a.get_child_count()
+		{
+			// Skip child pairs that cannot be closer than the best pair so far.
+			let mut aabb_a = B2AABB::default();
+			shape_a.compute_aabb(&mut aabb_a, xf_a, child_index_a);
+			if aabb_distance(aabb_a, aabb_b) >= best.distance
+			{
+				continue;
+			}
+
+			let output = b2_fixture_child_distance(self_, child_index_a, &proxy_b, xf_b);
+			if output.distance < best.distance
+			{
+				best = make_distance_output(&output, child_index_a, child_index_b);
+			}
+		}
+	}
+	return best;
+}
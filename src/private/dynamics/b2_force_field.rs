@@ -0,0 +1,201 @@
+use crate::b2_body::*;
+use crate::b2_collision::*;
+use crate::b2_fixture::*;
+use crate::b2_force_field::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_shape::*;
+use crate::b2_world::*;
+use crate::b2rs_common::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub(crate) fn create_force_field<D: UserDataType>(
+	self_: &mut B2world<D>,
+	def: &B2forceFieldDef<D>,
+) -> B2forceFieldPtr<D> {
+	b2_assert(self_.is_locked() == false);
+	b2_assert(def.area.is_some());
+
+	let field = Rc::new(RefCell::new(B2forceField {
+		m_area: def.area.clone().unwrap(),
+		m_type: def.field_type,
+		m_mask_bits: def.mask_bits,
+		m_enabled: def.enabled,
+	}));
+	self_.m_force_field_list.push(field.clone());
+	return field;
+}
+
+pub(crate) fn destroy_force_field<D: UserDataType>(self_: &mut B2world<D>, field: B2forceFieldPtr<D>) {
+	b2_assert(self_.is_locked() == false);
+	self_.m_force_field_list.retain(|f| Rc::ptr_eq(f, &field) == false);
+}
+
+pub(crate) fn falloff_scale(falloff: B2forceFieldFalloff, radius: f32, distance: f32) -> f32 {
+	match falloff {
+		B2forceFieldFalloff::Constant => {
+			return 1.0;
+		}
+		B2forceFieldFalloff::Linear => {
+			if radius <= 0.0 {
+				return 0.0;
+			}
+			return b2_max(1.0 - distance / radius, 0.0);
+		}
+		B2forceFieldFalloff::Quadratic => {
+			if radius <= 0.0 {
+				return 0.0;
+			}
+			let scale = b2_max(1.0 - distance / radius, 0.0);
+			return scale * scale;
+		}
+		B2forceFieldFalloff::InverseSquare => {
+			let ratio = radius / b2_max(distance, B2_LINEAR_SLOP);
+			return ratio * ratio;
+		}
+	}
+}
+
+// The field acceleration at the center of mass of a body.
+fn field_acceleration<D: UserDataType>(
+	field_type: B2forceFieldType,
+	xf: B2Transform,
+	gravity: B2vec2,
+	body: &B2body<D>,
+) -> B2vec2 {
+	let p = body.get_world_center();
+	match field_type {
+		B2forceFieldType::Directional { acceleration } => {
+			return acceleration;
+		}
+		B2forceFieldType::Radial {
+			center,
+			strength,
+			falloff,
+			radius,
+		} => {
+			let mut d = b2_mul_transform_by_vec2(xf, center) - p;
+			let distance = d.normalize();
+			if distance < B2_EPSILON {
+				return B2vec2::zero();
+			}
+			return (strength * falloff_scale(falloff, radius, distance)) * d;
+		}
+		B2forceFieldType::Vortex {
+			center,
+			strength,
+			inward_strength,
+			falloff,
+			radius,
+		} => {
+			let mut d = b2_mul_transform_by_vec2(xf, center) - p;
+			let distance = d.normalize();
+			if distance < B2_EPSILON {
+				return B2vec2::zero();
+			}
+			// Counter-clockwise tangent around the center.
+			let tangent = B2vec2::new(d.y, -d.x);
+			return falloff_scale(falloff, radius, distance) * (strength * tangent + inward_strength * d);
+		}
+		B2forceFieldType::GravityOverride { gravity: field_gravity } => {
+			// The island adds the world gravity, so only add the difference.
+			return body.get_gravity_scale() * (field_gravity - gravity);
+		}
+	}
+}
+
+fn apply_force_field<D: UserDataType>(
+	self_: &B2world<D>,
+	field: &B2forceField<D>,
+	shape: &dyn B2shapeDynTrait,
+	xf: B2Transform,
+	owner: Option<BodyPtr<D>>,
+	applied: &mut Vec<(BodyPtr<D>, B2vec2)>,
+) {
+	// Find the bodies with the center of mass inside the field.
+	let mut bodies = Vec::<BodyPtr<D>>::new();
+	for child_index in 0..shape.get_child_count() {
+		let mut aabb = B2AABB::default();
+		shape.compute_aabb(&mut aabb, xf, child_index);
+		self_.query_aabb(|fixture: FixturePtr<D>| -> bool {
+			let body_ptr = fixture.borrow().get_body();
+			if bodies.iter().any(|b| Rc::ptr_eq(b, &body_ptr)) {
+				return true;
+			}
+			// A field never affects the body it is attached to.
+			if owner.as_ref().map_or(false, |owner| Rc::ptr_eq(owner, &body_ptr)) {
+				return true;
+			}
+
+			let body = body_ptr.borrow();
+			if body.get_type() != B2bodyType::B2DynamicBody || body.is_awake() == false {
+				return true;
+			}
+			if body.fixtures().any(|f| f.borrow().get_filter_data().category_bits & field.m_mask_bits != 0) == false {
+				return true;
+			}
+
+			let center = body.get_world_center();
+			if (0..shape.get_child_count()).any(|i| shape_test_point(shape, xf, center, i)) {
+				drop(body);
+				bodies.push(body_ptr);
+			}
+			return true;
+		}, aabb);
+	}
+
+	let gravity = self_.m_gravity;
+	for body_ptr in bodies {
+		let mut body = body_ptr.borrow_mut();
+		let force = body.get_mass() * field_acceleration(field.m_type, xf, gravity, &body);
+		body.m_force += force;
+		applied.push((body_ptr.clone(), force));
+	}
+}
+
+// Chains and edges have no inside, test them as thin areas like the AABB.
+fn shape_test_point(shape: &dyn B2shapeDynTrait, xf: B2Transform, p: B2vec2, child_index: usize) -> bool {
+	if shape.get_type() == B2ShapeType::EChain || shape.get_type() == B2ShapeType::EEdge {
+		let mut aabb = B2AABB::default();
+		shape.compute_aabb(&mut aabb, xf, child_index);
+		return aabb.contains(&B2AABB { lower_bound: p, upper_bound: p });
+	}
+	return shape.test_point(xf, p);
+}
+
+// Add the field forces to the bodies before velocity integration.
+// Returns the applied forces so they can be removed when forces are not cleared.
+pub(crate) fn apply_force_fields<D: UserDataType>(self_: &B2world<D>) -> Vec<(BodyPtr<D>, B2vec2)> {
+	let mut applied = Vec::new();
+	for field in &self_.m_force_field_list {
+		let field = field.borrow();
+		if field.m_enabled == false {
+			continue;
+		}
+
+		match field.m_area {
+			B2forceFieldArea::Shape { ref shape, transform } => {
+				apply_force_field(self_, &field, &*shape.borrow(), transform, None, &mut applied);
+			}
+			B2forceFieldArea::Fixture(ref fixture) => {
+				let fixture = fixture.borrow();
+				if fixture.m_proxy_count == 0 {
+					// The fixture was destroyed.
+					continue;
+				}
+				let body = fixture.get_body();
+				let xf = body.borrow().get_transform();
+				apply_force_field(self_, &field, &*fixture.get_shape(), xf, Some(body), &mut applied);
+			}
+		}
+	}
+	return applied;
+}
+
+pub(crate) fn remove_field_forces<D: UserDataType>(applied: Vec<(BodyPtr<D>, B2vec2)>) {
+	for (body, force) in applied {
+		body.borrow_mut().m_force -= force;
+	}
+}
@@ -0,0 +1,324 @@
+use crate::b2_body::*;
+use crate::b2_collision::*;
+use crate::b2_common::*;
+use crate::b2_contact::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_settings::*;
+use crate::b2_world::*;
+use crate::b2_world_callbacks::*;
+use crate::b2rs_common::*;
+use crate::shapes::b2_polygon_shape::*;
+use crate::shapes::b2rs_to_derived_shape::*;
+
+use crate::private::dynamics::b2_body as private_body;
+use crate::private::dynamics::b2_world as private_world;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// The strongest hit on a breakable fixture in a time step.
+pub(crate) struct B2fractureImpact<D: UserDataType> {
+	pub(crate) fixture: FixturePtr<D>,
+	// The contact point in world coordinates.
+	pub(crate) point: B2vec2,
+	pub(crate) impulse: f32,
+}
+
+// The number of Voronoi seeds. The seeds that fall outside of the polygon
+// don't make pieces, so a fixture usually breaks into fewer pieces.
+const K_CELL_COUNT: usize = 8;
+
+// Pieces smaller than this part of the broken polygon are dropped.
+const K_MIN_AREA_FRACTION: f32 = 0.001;
+
+pub(crate) fn set_fracture_listener<D: UserDataType>(
+	self_: &mut B2world<D>,
+	listener: B2fractureListenerPtr<D>,
+) {
+	self_.m_fracture_listener = Some(listener);
+}
+
+// Record the impacts of a solved contact on its breakable fixtures.
+pub(crate) fn add_contact_impacts<D: UserDataType>(
+	impacts: &mut Vec<B2fractureImpact<D>>,
+	contact: &dyn B2contactDynTrait<D>,
+	impulse: &B2contactImpulse,
+) {
+	let contact = contact.get_base();
+	let fixture_a = contact.get_fixture_a();
+	let fixture_b = contact.get_fixture_b();
+	let toughness_a: f32 = fixture_a.borrow().m_toughness;
+	let toughness_b: f32 = fixture_b.borrow().m_toughness;
+	if toughness_a == 0.0 && toughness_b == 0.0 {
+		return;
+	}
+
+	// The strongest point of the manifold.
+	let mut max_impulse: f32 = 0.0;
+	let mut index: usize = 0;
+	for j in 0..impulse.count as usize {
+		if impulse.normal_impulses[j] > max_impulse {
+			max_impulse = impulse.normal_impulses[j];
+			index = j;
+		}
+	}
+
+	let mut world_manifold = B2worldManifold::default();
+	contact.get_world_manifold(&mut world_manifold);
+	let point: B2vec2 = world_manifold.points[index];
+
+	for (fixture, toughness) in [(fixture_a, toughness_a), (fixture_b, toughness_b)] {
+		if toughness > 0.0 && max_impulse > toughness {
+			add_impact(
+				impacts,
+				B2fractureImpact {
+					fixture,
+					point,
+					impulse: max_impulse,
+				},
+			);
+		}
+	}
+}
+
+// Keep the strongest impact of every fixture.
+pub(crate) fn add_impact<D: UserDataType>(impacts: &mut Vec<B2fractureImpact<D>>, impact: B2fractureImpact<D>) {
+	if let Some(other) = impacts.iter_mut().find(|i| Rc::ptr_eq(&i.fixture, &impact.fixture)) {
+		if impact.impulse > other.impulse {
+			*other = impact;
+		}
+		return;
+	}
+	impacts.push(impact);
+}
+
+// Break the fixtures hit during the step. This runs at the end of the step,
+// after the world is unlocked. The world is still borrowed by the step, so the
+// pieces are created without going through the world pointer.
+pub(crate) fn fracture_fixtures<D: UserDataType>(self_: &mut B2world<D>) {
+	if self_.m_fracture_list.is_empty() {
+		return;
+	}
+
+	let listener: B2fractureListenerPtr<D> = match self_.m_fracture_listener {
+		Some(ref listener) => listener.clone(),
+		None => Rc::new(RefCell::new(B2fractureListenerDefault)),
+	};
+
+	let impacts: Vec<B2fractureImpact<D>> = std::mem::take(&mut self_.m_fracture_list);
+	for impact in impacts {
+		fracture_fixture(self_, &listener, &impact);
+	}
+}
+
+fn fracture_fixture<D: UserDataType>(
+	self_: &mut B2world<D>,
+	listener: &B2fractureListenerPtr<D>,
+	impact: &B2fractureImpact<D>,
+) {
+	let fixture = impact.fixture.clone();
+	let polygon: B2polygonShape = match fixture.borrow().get_shape().as_derived() {
+		ShapeAsDerived::AsPolygon(shape) => *shape,
+		_ => return,
+	};
+
+	let body: BodyPtr<D> = fixture.borrow().get_body();
+	let xf: B2Transform = body.borrow().get_transform();
+
+	let vertices: &[B2vec2] = &polygon.m_vertices[..polygon.m_count];
+	let local_point: B2vec2 = b2_mul_t_transform_by_vec2(xf, impact.point);
+	let pieces: Vec<Vec<B2vec2>> = compute_pieces(vertices, local_point);
+	if pieces.len() < 2 {
+		return;
+	}
+
+	let world: B2worldPtr<D> = upgrade(&body.borrow().m_world);
+	let center: B2vec2;
+	let v: B2vec2;
+	let w: f32;
+	let mut body_def: B2bodyDef<D>;
+	{
+		let body = body.borrow();
+		center = body.get_world_center();
+		v = body.get_linear_velocity();
+		w = body.get_angular_velocity();
+		body_def = B2bodyDef {
+			body_type: body.m_type,
+			position: xf.p,
+			angle: body.m_sweep.a,
+			angular_velocity: w,
+			linear_damping: body.m_linear_damping,
+			angular_damping: body.m_angular_damping,
+			allow_sleep: body.m_flags.contains(BodyFlags::E_AUTO_SLEEP_FLAG),
+			awake: true,
+			fixed_rotation: body.m_flags.contains(BodyFlags::E_FIXED_ROTATION_FLAG),
+			fixed_translation_x: body.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_X_FLAG),
+			fixed_translation_y: body.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_Y_FLAG),
+			max_linear_speed: body.m_max_linear_speed,
+			max_angular_speed: body.m_max_angular_speed,
+			bullet: body.m_flags.contains(BodyFlags::E_BULLET_FLAG),
+			enabled: body.m_flags.contains(BodyFlags::E_ENABLED_FLAG),
+			gravity_scale: body.m_gravity_scale,
+			..Default::default()
+		};
+	}
+
+	let mut fixture_def: B2fixtureDef<D>;
+	{
+		let f = fixture.borrow();
+		fixture_def = B2fixtureDef {
+			friction: f.m_friction,
+			restitution: f.m_restitution,
+			restitution_threshold: f.m_restitution_threshold,
+			density: f.m_density,
+			is_sensor: f.m_is_sensor,
+			filter: f.m_filter,
+			one_way_direction: f.m_one_way_direction,
+			toughness: f.m_toughness,
+			..Default::default()
+		};
+	}
+
+	// The pieces share the transform of the broken body and the velocity of its
+	// origin. The mass update then gives each piece the velocity its centroid
+	// had in the broken body.
+	body_def.linear_velocity = v + b2_cross_scalar_by_vec(w, xf.p - center);
+
+	let mut piece_fixtures = Vec::<FixturePtr<D>>::with_capacity(pieces.len());
+	for piece in &pieces {
+		let mut shape = B2polygonShape::default();
+		shape.set(piece);
+		shape.base.m_radius = polygon.base.m_radius;
+
+		body_def.user_data = listener.borrow_mut().copy_body_user_data(body.clone());
+		let piece_body = private_world::create_body_by_world(self_, world.clone(), &body_def);
+
+		fixture_def.shape = Some(Rc::new(RefCell::new(shape)));
+		fixture_def.user_data = listener.borrow_mut().copy_fixture_user_data(fixture.clone());
+		piece_fixtures.push(private_body::create_fixture_by_world(piece_body, self_, &fixture_def));
+	}
+
+	listener.borrow_mut().fractured(fixture.clone(), &piece_fixtures);
+
+	private_body::destroy_fixture_by_world(body.clone(), self_, fixture);
+	if body.borrow().m_fixture_count == 0 {
+		private_world::destroy_body(self_, body);
+	}
+}
+
+// Cut a convex polygon into the Voronoi cells of seeds spread around the
+// impact point. The seeds get sparser away from the impact, so the pieces
+// near it are the smallest. Every piece fits in a polygon shape.
+fn compute_pieces(vertices: &[B2vec2], point: B2vec2) -> Vec<Vec<B2vec2>> {
+	// Turning by the golden angle spreads the seeds evenly around the impact.
+	const K_GOLDEN_ANGLE: f32 = 2.399_963;
+
+	let mut extent: f32 = 0.0;
+	for v in vertices {
+		extent = b2_max(extent, b2_distance_vec2(*v, point));
+	}
+
+	let mut seeds = Vec::<B2vec2>::with_capacity(K_CELL_COUNT);
+	for i in 0..K_CELL_COUNT {
+		let radius: f32 = extent * (i as f32) / (K_CELL_COUNT as f32);
+		let angle: f32 = K_GOLDEN_ANGLE * (i as f32);
+		seeds.push(point + radius * B2vec2::new(f32::cos(angle), f32::sin(angle)));
+	}
+
+	let min_area: f32 = b2_max(K_MIN_AREA_FRACTION * polygon_area(vertices), B2_LINEAR_SLOP * B2_LINEAR_SLOP);
+
+	let mut pieces = Vec::<Vec<B2vec2>>::new();
+	for (i, seed) in seeds.iter().enumerate() {
+		// The cell is on the side of the seed of every bisector.
+		let mut cell: Vec<B2vec2> = vertices.to_vec();
+		for (j, other) in seeds.iter().enumerate() {
+			if i == j || cell.len() < 3 {
+				continue;
+			}
+			cell = clip(&cell, 0.5 * (*seed + *other), *other - *seed);
+		}
+
+		let cell: Vec<B2vec2> = weld(cell);
+		if cell.len() < 3 {
+			continue;
+		}
+
+		let mut parts = Vec::<Vec<B2vec2>>::new();
+		split(cell, &mut parts);
+		for part in parts {
+			if polygon_area(&part) > min_area {
+				pieces.push(part);
+			}
+		}
+	}
+
+	return pieces;
+}
+
+// Keep the part of a convex polygon behind the plane through the point. This
+// is one Sutherland-Hodgman step, the result is convex too.
+fn clip(polygon: &[B2vec2], point: B2vec2, normal: B2vec2) -> Vec<B2vec2> {
+	let count: usize = polygon.len();
+	let mut result = Vec::<B2vec2>::with_capacity(count + 1);
+	for i in 0..count {
+		let a: B2vec2 = polygon[i];
+		let b: B2vec2 = polygon[if i + 1 < count { i + 1 } else { 0 }];
+		let da: f32 = b2_dot(normal, a - point);
+		let db: f32 = b2_dot(normal, b - point);
+
+		if da <= 0.0 {
+			result.push(a);
+		}
+
+		if (da < 0.0 && db > 0.0) || (da > 0.0 && db < 0.0) {
+			result.push(a + (da / (da - db)) * (b - a));
+		}
+	}
+	return result;
+}
+
+// Merge the neighbouring vertices that are too close for a polygon shape.
+fn weld(polygon: Vec<B2vec2>) -> Vec<B2vec2> {
+	let mut result = Vec::<B2vec2>::with_capacity(polygon.len());
+	for v in polygon {
+		if let Some(last) = result.last() {
+			if b2_distance_vec2_squared(v, *last) < B2_LINEAR_SLOP * B2_LINEAR_SLOP {
+				continue;
+			}
+		}
+		result.push(v);
+	}
+
+	while result.len() > 1
+		&& b2_distance_vec2_squared(result[0], *result.last().unwrap()) < B2_LINEAR_SLOP * B2_LINEAR_SLOP
+	{
+		result.pop();
+	}
+	return result;
+}
+
+// Split a convex polygon along diagonals until every part has at most
+// B2_MAX_POLYGON_VERTICES vertices.
+fn split(polygon: Vec<B2vec2>, parts: &mut Vec<Vec<B2vec2>>) {
+	let count: usize = polygon.len();
+	if count <= B2_MAX_POLYGON_VERTICES {
+		parts.push(polygon);
+		return;
+	}
+
+	let half: usize = count / 2;
+	split(polygon[..=half].to_vec(), parts);
+	let mut rest: Vec<B2vec2> = polygon[half..].to_vec();
+	rest.push(polygon[0]);
+	split(rest, parts);
+}
+
+fn polygon_area(vertices: &[B2vec2]) -> f32 {
+	let count: usize = vertices.len();
+	let mut area: f32 = 0.0;
+	for i in 1..count - 1 {
+		area += b2_cross(vertices[i] - vertices[0], vertices[i + 1] - vertices[0]);
+	}
+	return 0.5 * area;
+}
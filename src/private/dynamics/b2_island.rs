@@ -6,6 +6,7 @@ use crate::b2rs_common::UserDataType;
 use crate::b2_time_step::*;
 use crate::b2_world_callbacks::*;
 use super::b2_contact_solver::*;
+use super::b2_fracture::*;
 
 use crate::private::dynamics::b2_island_private as private;
 
@@ -30,6 +31,8 @@ impl<D: UserDataType> B2island<D> {
 		
 			m_positions,
 			m_velocities,
+
+			m_impacts: Vec::new(),
 		}
 	}
 
@@ -62,7 +65,7 @@ impl<D: UserDataType> B2island<D> {
 		self.m_joints.push(joint);
 	}
 
-	pub fn report(&self, constraints: &[B2contactVelocityConstraint])
+	pub fn report(&mut self, constraints: &[B2contactVelocityConstraint])
 	{
 		private::report(self, constraints);
 	}
@@ -77,4 +80,7 @@ pub(crate) struct B2island<D: UserDataType> {
 
 	pub(crate) m_positions: Vec<B2position>,
 	pub(crate) m_velocities: Vec<B2velocity>,
+
+	// The impacts on breakable fixtures, collected by report.
+	pub(crate) m_impacts: Vec<B2fractureImpact<D>>,
 }
@@ -7,6 +7,8 @@ use crate::b2_common::*;
 use crate::b2rs_common::UserDataType;
 use super::b2_island::*;
 use crate::private::dynamics::b2_contact_solver::*;
+use crate::private::dynamics::b2_body::wrap_angle;
+use crate::private::dynamics::b2_fracture as private_fracture;
 
 /*
 Position Correction Notes
@@ -126,6 +128,98 @@ This might be faster than computing sin+cos.
 However, we can compute sin+cos of the same angle fast.
 */
 
+// Per-body motion locks and speed limits, gathered once per solve.
+#[derive(Clone, Copy)]
+struct B2motionLimits
+{
+	lock_x: bool,
+	lock_y: bool,
+	max_linear_speed: f32,
+	max_angular_speed: f32,
+	// The locked coordinates of the center of mass.
+	c: B2vec2,
+}
+
+// Returns None when no body in the island is locked or speed limited, which is the common case.
+fn collect_motion_limits<D: UserDataType>(self_: &B2island<D>) -> Option<Vec<B2motionLimits>>
+{
+	let mut any = false;
+	let mut limits = Vec::with_capacity(self_.m_bodies.len());
+	for (i, b) in self_.m_bodies.iter().enumerate()
+	{
+		let b = b.borrow();
+		let limit = B2motionLimits{
+			lock_x: b.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_X_FLAG),
+			lock_y: b.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_Y_FLAG),
+			max_linear_speed: b.m_max_linear_speed,
+			max_angular_speed: b.m_max_angular_speed,
+			c: self_.m_positions[i].c,
+		};
+		any = any || limit.lock_x || limit.lock_y ||
+			limit.max_linear_speed < B2_MAX_FLOAT || limit.max_angular_speed < B2_MAX_FLOAT;
+		limits.push(limit);
+	}
+
+	if any == false
+	{
+		return None;
+	}
+	return Some(limits);
+}
+
+// Remove the velocity along the locked axes. Contacts already give a locked body no
+// inverse mass along the axis, so this only catches what joints and gravity put there.
+fn apply_velocity_locks(limits: &Option<Vec<B2motionLimits>>, velocities: &mut [B2velocity])
+{
+	if let Some(limits) = limits
+	{
+		for (limit, velocity) in limits.iter().zip(velocities.iter_mut())
+		{
+			if limit.lock_x
+			{
+				velocity.v.x = 0.0;
+			}
+			if limit.lock_y
+			{
+				velocity.v.y = 0.0;
+			}
+		}
+	}
+}
+
+// Clamp the velocity of a body to its own speed limits.
+fn apply_speed_limits(limits: &Option<Vec<B2motionLimits>>, i: usize, v: &mut B2vec2, w: &mut f32)
+{
+	if let Some(limits) = limits
+	{
+		let limit = limits[i];
+		if b2_dot(*v, *v) > limit.max_linear_speed * limit.max_linear_speed
+		{
+			*v *= limit.max_linear_speed / v.length();
+		}
+		*w = b2_clamp(*w, -limit.max_angular_speed, limit.max_angular_speed);
+	}
+}
+
+// Undo the position corrections the joints made along the locked axes.
+fn apply_position_locks(limits: &Option<Vec<B2motionLimits>>, positions: &mut [B2position])
+{
+	if let Some(limits) = limits
+	{
+		for (limit, position) in limits.iter().zip(positions.iter_mut())
+		{
+			if limit.lock_x
+			{
+				position.c.x = limit.c.x;
+			}
+			if limit.lock_y
+			{
+				position.c.y = limit.c.y;
+			}
+		}
+	}
+}
+
 pub(crate) fn solve<D: UserDataType>(self_: &mut B2island<D>, profile: &mut B2Profile, step: &B2timeStep, gravity: B2vec2, allow_sleep: bool)
 {
 	let mut timer = B2timer::default();
@@ -169,6 +263,9 @@ pub(crate) fn solve<D: UserDataType>(self_: &mut B2island<D>, profile: &mut B2Pr
 		self_.m_velocities[i].w = w;
 	}
 
+	let motion_limits = collect_motion_limits(self_);
+	apply_velocity_locks(&motion_limits, &mut self_.m_velocities);
+
 	timer.reset();
 
 	// Solver data
@@ -212,6 +309,7 @@ pub(crate) fn solve<D: UserDataType>(self_: &mut B2island<D>, profile: &mut B2Pr
 		}
 
 		contact_solver.solve_velocity_constraints(&mut self_.m_velocities);
+		apply_velocity_locks(&motion_limits, &mut self_.m_velocities);
 	}
 
 	// Store impulses for warm starting
@@ -226,6 +324,8 @@ pub(crate) fn solve<D: UserDataType>(self_: &mut B2island<D>, profile: &mut B2Pr
 		let mut v:B2vec2 = self_.m_velocities[i].v;
 		let mut w:f32 = self_.m_velocities[i].w;
 
+		apply_speed_limits(&motion_limits, i, &mut v, &mut w);
+
 		// Check for large velocities
 		let translation:B2vec2 = h * v;
 		if b2_dot(translation, translation) > B2_MAX_TRANSLATION_SQUARED
@@ -273,10 +373,24 @@ pub(crate) fn solve<D: UserDataType>(self_: &mut B2island<D>, profile: &mut B2Pr
 		}
 	}
 
+	apply_position_locks(&motion_limits, &mut self_.m_positions);
+
 	// Copy state buffers back to the bodies
 	for (i,body) in (&self_.m_bodies).iter().enumerate()
 	{
 		let mut body = body.borrow_mut();
+		if let Some((c, a)) = body.m_target_sweep.take()
+		{
+			// Remove the integration drift once the body reached its target.
+			let da: f32 = wrap_angle(a - self_.m_positions[i].a);
+			if body.m_flags.contains(BodyFlags::E_SNAP_TO_TARGET_FLAG) &&
+				b2_distance_vec2_squared(c, self_.m_positions[i].c) < B2_LINEAR_SLOP * B2_LINEAR_SLOP &&
+				b2_abs(da) < B2_ANGULAR_SLOP
+			{
+				self_.m_positions[i].c = c;
+				self_.m_positions[i].a += da;
+			}
+		}
 		body.m_sweep.c = self_.m_positions[i].c;
 		body.m_sweep.a = self_.m_positions[i].a;
 		body.m_linear_velocity = self_.m_velocities[i].v;
@@ -349,6 +463,8 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2island<D>, sub_step: &B2t
 	};
 	let mut contact_solver = B2contactSolver::new::<D>(&contact_solver_def, &self_.m_contacts);
 
+	let motion_limits = collect_motion_limits(self_);
+
 	// solve position constraints.
 	for _i in 0..sub_step.position_iterations
 	{
@@ -359,6 +475,8 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2island<D>, sub_step: &B2t
 		}
 	}
 
+	apply_position_locks(&motion_limits, &mut self_.m_positions);
+
 // #if 0
 // 	// Is the new position really safe?
 // 	for (i32 i = 0; i < m_contact_count; ++i)
@@ -406,6 +524,7 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2island<D>, sub_step: &B2t
 	for _i in 0..sub_step.velocity_iterations
 	{
 		contact_solver.solve_velocity_constraints(&mut self_.m_velocities);
+		apply_velocity_locks(&motion_limits, &mut self_.m_velocities);
 	}
 
 	// Don't store the TOI contact forces for warm starting
@@ -421,6 +540,8 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2island<D>, sub_step: &B2t
 		let mut v:B2vec2 = self_.m_velocities[i].v;
 		let mut w:f32 = self_.m_velocities[i].w;
 
+		apply_speed_limits(&motion_limits, i, &mut v, &mut w);
+
 		// Check for large velocities
 		let translation:B2vec2 = h * v;
 		if b2_dot(translation, translation) > B2_MAX_TRANSLATION_SQUARED
@@ -457,15 +578,8 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2island<D>, sub_step: &B2t
 	self_.report(&contact_solver.m_velocity_constraints);
 }
 
-pub(crate) fn report<D: UserDataType>(self_: &B2island<D>, constraints: &[B2contactVelocityConstraint])
+pub(crate) fn report<D: UserDataType>(self_: &mut B2island<D>, constraints: &[B2contactVelocityConstraint])
 {
-	if self_.m_listener.is_none()
-	{
-		return;
-	}
-
-	let listener = self_.m_listener.as_ref().unwrap();
-
 	assert_eq!(self_.m_contacts.len(), constraints.len());
 
 	for (i,c) in (&self_.m_contacts).iter().enumerate()
@@ -482,6 +596,12 @@ pub(crate) fn report<D: UserDataType>(self_: &B2island<D>, constraints: &[B2cont
 			impulse.tangent_impulses[j as usize] = vc.points[j as usize].tangent_impulse;
 		}
 
-		listener.borrow_mut().post_solve(&mut *c, &impulse);
+		// Breakable fixtures are found even without a listener.
+		private_fracture::add_contact_impacts(&mut self_.m_impacts, &*c, &impulse);
+
+		if let Some(ref listener) = self_.m_listener
+		{
+			listener.borrow_mut().post_solve(&mut *c, &impulse);
+		}
 	}
 }
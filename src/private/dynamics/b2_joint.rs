@@ -5,7 +5,10 @@ use crate::b2rs_common::UserDataType;
 use crate::b2_math::*;
 use crate::b2_body::*;
 
+use crate::joints::b2_angle_joint::*;
+use crate::joints::b2_custom_joint::*;
 use crate::joints::b2_distance_joint::*;
+use crate::joints::b2_filter_joint::*;
 use crate::joints::b2_friction_joint::*;
 use crate::joints::b2_gear_joint::*;
 use crate::joints::b2_motor_joint::*;
@@ -83,7 +86,10 @@ pub(crate) fn create<D: UserDataType>(def: &B2JointDefEnum<D>) -> B2jointPtr<D>
 		B2JointDefEnum::RevoluteJoint(ref def)=> Rc::new(RefCell::new(B2revoluteJoint::new(def))),
 		B2JointDefEnum::PrismaticJoint(ref def)=> Rc::new(RefCell::new(B2prismaticJoint::new(def))),
 		B2JointDefEnum::WeldJoint(ref def)=> Rc::new(RefCell::new(B2weldJoint::new(def))),
-		B2JointDefEnum::WheelJoint(ref def)=> Rc::new(RefCell::new(B2wheelJoint::new(def))),	
+		B2JointDefEnum::WheelJoint(ref def)=> Rc::new(RefCell::new(B2wheelJoint::new(def))),
+		B2JointDefEnum::AngleJoint(ref def)=> Rc::new(RefCell::new(B2angleJoint::new(def))),
+		B2JointDefEnum::FilterJoint(ref def)=> Rc::new(RefCell::new(B2filterJoint::new(def))),
+		B2JointDefEnum::CustomJoint(ref def)=> Rc::new(RefCell::new(B2customJointHost::new(def))),
 	}
 }
 
@@ -143,6 +149,13 @@ pub(crate) fn  draw<D: UserDataType, T: B2jointTraitDyn<D>+?Sized>(_self: &T, dr
 			let c = B2color::new(0.8, 0.8, 0.8);
 			draw.draw_segment(p1, p2, c);
 		},
+		JointAsDerived::EAngleJoint(ref _def)=>{
+			draw.draw_segment(x1, x2, color);
+		},
+		JointAsDerived::EFilterJoint(ref _def)=>{
+			let c = B2color::new(0.8, 0.3, 0.3);
+			draw.draw_segment(x1, x2, c);
+		},
 		_=>{
 			draw.draw_segment(x1, p1, color);
 			draw.draw_segment(p1, p2, color);
@@ -20,6 +20,12 @@ use crate::b2_contact_manager::*;
 
 use crate::shapes::b2rs_to_derived_shape::ShapeAsDerived;
 
+use crate::private::dynamics::b2_force_field as private_force_field;
+use crate::private::dynamics::b2_fracture as private_fracture;
+use crate::private::rope::b2_rope_world as private_rope;
+use crate::private::rope::b2_soft_body as private_soft_body;
+use crate::private::particle::b2_particle_system as private_particle;
+
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -36,6 +42,7 @@ pub(crate) fn b2_world_new<D: UserDataType>(gravity: B2vec2) -> B2worldPtr<D> {
 
 		m_warm_starting: true,
 		m_continuous_physics: true,
+		m_speculative_contacts: false,
 		m_sub_stepping: false,
 
 		m_step_complete: true,
@@ -52,6 +59,17 @@ pub(crate) fn b2_world_new<D: UserDataType>(gravity: B2vec2) -> B2worldPtr<D> {
 		m_contact_manager: Rc::new(RefCell::new(B2contactManager::new())),
 
 		m_profile: Default::default(),
+
+		m_force_field_list: Vec::new(),
+
+		m_rope_list: Vec::new(),
+
+		m_soft_body_list: Vec::new(),
+
+		m_particle_system_list: Vec::new(),
+
+		m_fracture_listener: None,
+		m_fracture_list: Vec::new(),
 	}));
 }
 
@@ -81,18 +99,26 @@ pub(crate) fn set_debug_draw<D: UserDataType>(self_: &mut B2world<D>, debug_draw
 }
 
 pub(crate) fn create_body<D: UserDataType>(self_: B2worldPtr<D>, def: &B2bodyDef<D>) -> BodyPtr<D> {
-	b2_assert(self_.borrow().is_locked() == false);
-	if self_.borrow().is_locked() {
+	let world = self_.clone();
+	return create_body_by_world(&mut self_.borrow_mut(), world, def);
+}
+
+// The body only keeps a weak pointer to the world, so this works while the
+// world is borrowed.
+pub(crate) fn create_body_by_world<D: UserDataType>(
+	self_: &mut B2world<D>,
+	world: B2worldPtr<D>,
+	def: &B2bodyDef<D>,
+) -> BodyPtr<D> {
+	b2_assert(self_.is_locked() == false);
+	if self_.is_locked() {
 		panic!();
 	}
-	let b = Rc::new(RefCell::new(B2body::new(def, self_.clone())));
+	let b = Rc::new(RefCell::new(B2body::new(def, world)));
 
 	// Add to world doubly linked list.
-	{
-		let mut self_ = self_.borrow_mut();
-		self_.m_body_list.push_front(b.clone());
-		self_.m_body_count += 1;
-	}
+	self_.m_body_list.push_front(b.clone());
+	self_.m_body_count += 1;
 
 	return b;
 }
@@ -118,6 +144,9 @@ pub(crate) fn destroy_body<D: UserDataType>(self_: &mut B2world<D>, b: BodyPtr<D
 	}
 	b.borrow_mut().m_joint_list.clear();
 
+	// Detach the ropes.
+	private_rope::remove_attachments(self_, &b);
+
 	// Delete the attached contacts.
 	let m_contact_list = b.borrow().m_contact_list.clone();
 	for ce in m_contact_list.iter() {
@@ -252,6 +281,22 @@ pub(crate) fn create_joint<D: UserDataType>(
 			val.base.body_a.clone().unwrap(),
 			val.base.body_b.clone().unwrap(),
 			val.base.collide_connected,
+		),
+		B2JointDefEnum::AngleJoint(ref val)=>(
+			val.base.body_a.clone().unwrap(),
+			val.base.body_b.clone().unwrap(),
+			val.base.collide_connected,
+		),
+		// A filter joint never lets its bodies collide.
+		B2JointDefEnum::FilterJoint(ref val)=>(
+			val.base.body_a.clone().unwrap(),
+			val.base.body_b.clone().unwrap(),
+			false,
+		),
+		B2JointDefEnum::CustomJoint(ref val)=>(
+			val.base.body_a.clone().unwrap(),
+			val.base.body_b.clone().unwrap(),
+			val.base.collide_connected,
 		)
 	};
 
@@ -492,6 +537,9 @@ pub(crate) fn solve<D: UserDataType>(self_: &mut B2world<D>, step: B2timeStep) {
 
 		let mut profile = B2Profile::default();
 		island.solve(&mut profile, &step, self_.m_gravity, self_.m_allow_sleep);
+		for impact in island.m_impacts.drain(..) {
+			private_fracture::add_impact(&mut self_.m_fracture_list, impact);
+		}
 		self_.m_profile.solve_init += profile.solve_init;
 		self_.m_profile.solve_velocity += profile.solve_velocity;
 		self_.m_profile.solve_position += profile.solve_position;
@@ -700,6 +748,7 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2world<D>, step: B2timeSte
 				.m_contact_manager
 				.borrow()
 				.m_contact_listener.clone(),
+			0.0,
 		);
 		min_contact
 			.borrow_mut()
@@ -803,7 +852,8 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2world<D>, step: B2timeSte
 							.m_contact_manager
 							.borrow()
 							.m_contact_listener
-							.clone()
+							.clone(),
+						0.0,
 					);
 
 					//let mut contact = contact_ptr.borrow_mut();
@@ -855,6 +905,7 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2world<D>, step: B2timeSte
 			position_iterations: 20,
 			velocity_iterations: step.velocity_iterations,
 			warm_starting: false,
+			speculative_contacts: false,
 		};
 
 		{
@@ -866,6 +917,9 @@ pub(crate) fn solve_toi<D: UserDataType>(self_: &mut B2world<D>, step: B2timeSte
 				index_b,
 			);
 		}
+		for impact in island.m_impacts.drain(..) {
+			private_fracture::add_impact(&mut self_.m_fracture_list, impact);
+		}
 
 		// reset island flags and synchronize broad-phase proxies.
 		for body in island.m_bodies.clone() {
@@ -923,17 +977,22 @@ pub(crate) fn step<D: UserDataType>(
 		inv_dt: if dt > 0.0 { 1.0 / dt } else { 0.0 },
 		dt_ratio: self_.m_inv_dt0 * dt,
 		warm_starting: self_.m_warm_starting,
+		speculative_contacts: self_.m_speculative_contacts,
 	};
 	// update contacts. This is where some contacts are destroyed.
 	{
+		self_.m_contact_manager.borrow_mut().m_speculative_time = if step.speculative_contacts { dt } else { 0.0 };
 		let timer = B2timer::default();
 		B2contactManager::collide(self_.m_contact_manager.clone());
 		self_.m_profile.collide = timer.get_milliseconds();
 	}
 
 	// Integrate velocities, solve velocity constraints, and integrate positions.
+	let mut field_forces = Vec::new();
 	if self_.m_step_complete && step.dt > 0.0 {
 		let timer = B2timer::default();
+		// Force fields feed the velocity integration.
+		field_forces = private_force_field::apply_force_fields(self_);
 		self_.solve(step);
 		self_.m_profile.solve = timer.get_milliseconds();
 	}
@@ -945,16 +1004,26 @@ pub(crate) fn step<D: UserDataType>(
 		self_.m_profile.solve_toi = timer.get_milliseconds();
 	}
 
+	// Ropes follow the solved bodies and pull on them.
+	private_rope::step_ropes(self_, step);
+	private_soft_body::step_soft_bodies(self_, step);
+	private_particle::step_particle_systems(self_, step);
+
 	if step.dt > 0.0 {
 		self_.m_inv_dt0 = step.inv_dt;
 	}
 
 	if self_.m_clear_forces {
 		self_.clear_forces();
+	} else {
+		private_force_field::remove_field_forces(field_forces);
 	}
 
 	self_.m_locked = false;
 
+	// Break the fixtures hit in this step, now that bodies can be created.
+	private_fracture::fracture_fixtures(self_);
+
 	self_.m_profile.step = step_timer.get_milliseconds();
 }
 
@@ -1112,6 +1181,25 @@ pub(crate) fn draw_shape<D: UserDataType>(
 
 			m_debug_draw.draw_solid_polygon(&vertices[..vertex_count], *color);
 		}
+		ShapeAsDerived::AsCustom(custom) =>
+		{
+			let vertices: Vec<B2vec2> = custom.m_hull.iter().map(|v| b2_mul_transform_by_vec2(*xf, *v)).collect();
+			if vertices.len() > 2
+			{
+				m_debug_draw.draw_solid_polygon(&vertices, *color);
+			}
+			else
+			{
+				// A point or segment core is drawn as its rounded outline.
+				let radius: f32 = custom.base.m_radius;
+				let v1: B2vec2 = vertices[0];
+				let v2: B2vec2 = *vertices.last().unwrap();
+				let axis: B2vec2 = b2_mul_rot_by_vec2(xf.q, B2vec2::new(1.0, 0.0));
+				m_debug_draw.draw_solid_circle(v1, radius, axis, *color);
+				m_debug_draw.draw_solid_circle(v2, radius, axis, *color);
+				m_debug_draw.draw_segment(v1, v2, *color);
+			}
+		}
 	}
 }
 
@@ -1168,6 +1256,24 @@ pub(crate) fn debug_draw<D: UserDataType>(self_: &B2world<D>) {
 	let m_debug_draw_ptr = self_.m_debug_draw.as_ref().unwrap().clone();
 	let mut m_debug_draw = m_debug_draw_ptr.borrow_mut();
 
+	if flags.contains(B2drawShapeFlags::SHAPE_BIT)
+	{
+		for rope in &self_.m_rope_list
+		{
+			rope.borrow().get_rope().draw(&mut *m_debug_draw);
+		}
+
+		for soft_body in &self_.m_soft_body_list
+		{
+			soft_body.borrow().draw(&mut *m_debug_draw);
+		}
+
+		for system in &self_.m_particle_system_list
+		{
+			system.borrow().draw(&mut *m_debug_draw);
+		}
+	}
+
 	if flags.contains(B2drawShapeFlags::JOINT_BIT)
 	{
 
@@ -1290,6 +1396,9 @@ pub(crate) fn shift_origin<D: UserDataType>(self_: &B2world<D>, new_origin: B2ve
 	for j in self_.m_joint_list.iter() {
 		j.borrow_mut().shift_origin(new_origin);
 	}
+	private_rope::shift_origin(self_, new_origin);
+	private_soft_body::shift_origin(self_, new_origin);
+	private_particle::shift_origin(self_, new_origin);
 
 	self_.m_contact_manager
 		.borrow()
@@ -1,5 +1,6 @@
 use crate::b2_fixture::*;
 use crate::b2rs_common::UserDataType;
+use crate::b2_world_callbacks::*;
 
 // Return true if contact calculations should be performed between these two shapes.
 // If you implement your own collision filter you may want to build from this implementation.
@@ -16,3 +17,36 @@ pub fn should_collide<D:UserDataType>(fixture_a: FixturePtr<D>, fixture_b: Fixtu
 	let collide:bool = (filter_a.mask_bits & filter_b.category_bits) != 0 && (filter_a.category_bits & filter_b.mask_bits) != 0;
 	return collide;
 }
+
+
+// Return true if a query should test the fixture. The predicate is only called for
+// fixtures that pass the bits and sensor tests.
+pub fn should_query<D:UserDataType>(filter: &B2queryFilter<D>, fixture: FixturePtr<D>) -> bool
+{
+	{
+		let fixture = fixture.borrow();
+		if fixture.is_sensor() && filter.include_sensors == false
+		{
+			return false;
+		}
+
+		let filter_b = fixture.get_filter_data();
+		if filter.group_index == filter_b.group_index && filter.group_index != 0
+		{
+			if filter.group_index < 0
+			{
+				return false;
+			}
+		}
+		else if (filter.mask_bits & filter_b.category_bits) == 0 || (filter.category_bits & filter_b.mask_bits) == 0
+		{
+			return false;
+		}
+	}
+
+	if let Some(ref predicate) = filter.predicate
+	{
+		return predicate(fixture);
+	}
+	return true;
+}
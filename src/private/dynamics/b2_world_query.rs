@@ -0,0 +1,526 @@
+use crate::b2_body::*;
+use crate::b2_collision::*;
+use crate::b2_distance::*;
+use crate::b2_fixture::*;
+use crate::b2_force_field::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_shape::*;
+use crate::b2rs_common::*;
+use crate::b2_world::*;
+use crate::b2_world_callbacks::*;
+use crate::shapes::b2_circle_shape::*;
+use crate::shapes::b2_polygon_shape::*;
+
+use std::rc::Rc;
+
+use crate::private::collision::b2_distance as private_distance;
+use crate::private::dynamics::b2_fixture as private_fixture;
+use crate::private::dynamics::b2_force_field as private_force_field;
+
+// Exact overlap test between a prepared query proxy and one child of a fixture.
+fn test_overlap_fixture_child<D: UserDataType>(
+	proxy_b: &B2distanceProxy,
+	transform_b: B2Transform,
+	fixture: &B2fixture<D>,
+	child_index: usize,
+) -> bool {
+	let mut input = B2distanceInput::default();
+	private_distance::set_shape(&mut input.proxy_a, &*fixture.get_shape(), child_index);
+	input.proxy_b = proxy_b.clone();
+	input.transform_a = fixture.get_body().borrow().get_transform();
+	input.transform_b = transform_b;
+	input.use_radii = true;
+
+	let mut cache = B2simplexCache::default();
+	let mut output = B2distanceOutput::default();
+	b2_distance_fn(&mut output, &mut cache, &input);
+
+	return output.distance < 10.0 * B2_EPSILON;
+}
+
+pub(crate) fn overlap_shape<D: UserDataType, F: B2queryCallback<D>>(
+	self_: &B2world<D>,
+	shape: &dyn B2shapeDynTrait,
+	transform: B2Transform,
+	filter: &B2queryFilter<D>,
+	mut callback: F,
+) {
+	let broad_phase_ptr = self_.m_contact_manager.borrow().m_broad_phase.clone();
+	let broad_phase = broad_phase_ptr.borrow();
+
+	// A fixture is reported once even when several of its children overlap.
+	let mut reported = Vec::<FixturePtr<D>>::new();
+
+	for shape_child in 0..shape.get_child_count() {
+		let mut aabb = B2AABB::default();
+		shape.compute_aabb(&mut aabb, transform, shape_child);
+
+		let mut proxy_b = B2distanceProxy::default();
+		private_distance::set_shape(&mut proxy_b, shape, shape_child);
+
+		let mut proceed = true;
+		broad_phase.query(|proxy_id: i32| -> bool {
+			let proxy = broad_phase.get_user_data(proxy_id);
+			let fixture;
+			let index;
+			{
+				let proxy = proxy.as_ref().unwrap().borrow();
+				fixture = upgrade_opt(&proxy.fixture);
+				index = proxy.child_index;
+			}
+
+			if reported.iter().any(|f| Rc::ptr_eq(f, &fixture)) {
+				return true;
+			}
+
+			if filter.should_query(fixture.clone()) == false {
+				return true;
+			}
+
+			if test_overlap_fixture_child(&proxy_b, transform, &fixture.borrow(), index as usize) == false {
+				return true;
+			}
+
+			reported.push(fixture.clone());
+			proceed = callback(fixture);
+			return proceed;
+		}, aabb);
+
+		if proceed == false {
+			return;
+		}
+	}
+}
+
+pub(crate) fn overlap_circle<D: UserDataType, F: B2queryCallback<D>>(
+	self_: &B2world<D>,
+	center: B2vec2,
+	radius: f32,
+	filter: &B2queryFilter<D>,
+	callback: F,
+) {
+	let mut circle = B2circleShape::default();
+	circle.base.m_radius = radius;
+	circle.m_p = center;
+	overlap_shape(self_, &circle, B2Transform::new(B2vec2::zero(), B2Rot::new(0.0)), filter, callback);
+}
+
+pub(crate) fn overlap_polygon<D: UserDataType, F: B2queryCallback<D>>(
+	self_: &B2world<D>,
+	vertices: &[B2vec2],
+	filter: &B2queryFilter<D>,
+	callback: F,
+) {
+	let mut polygon = B2polygonShape::default();
+	polygon.set(vertices);
+	overlap_shape(self_, &polygon, B2Transform::new(B2vec2::zero(), B2Rot::new(0.0)), filter, callback);
+}
+
+pub(crate) fn overlap_point<D: UserDataType, F: B2queryCallback<D>>(
+	self_: &B2world<D>,
+	point: B2vec2,
+	filter: &B2queryFilter<D>,
+	mut callback: F,
+) {
+	let broad_phase_ptr = self_.m_contact_manager.borrow().m_broad_phase.clone();
+	let broad_phase = broad_phase_ptr.borrow();
+
+	let d = B2vec2::new(B2_EPSILON, B2_EPSILON);
+	let aabb = B2AABB {
+		lower_bound: point - d,
+		upper_bound: point + d,
+	};
+
+	let mut reported = Vec::<FixturePtr<D>>::new();
+	broad_phase.query(|proxy_id: i32| -> bool {
+		let proxy = broad_phase.get_user_data(proxy_id);
+		let fixture = upgrade_opt(&proxy.as_ref().unwrap().borrow().fixture);
+
+		if reported.iter().any(|f| Rc::ptr_eq(f, &fixture)) {
+			return true;
+		}
+
+		if filter.should_query(fixture.clone()) == false {
+			return true;
+		}
+
+		if fixture.borrow().test_point(point) == false {
+			return true;
+		}
+
+		reported.push(fixture.clone());
+		return callback(fixture);
+	}, aabb);
+}
+
+pub(crate) fn cast_shape<D: UserDataType, F: B2shapeCastCallback<D>>(
+	self_: &B2world<D>,
+	shape: &dyn B2shapeDynTrait,
+	transform: B2Transform,
+	translation: B2vec2,
+	filter: &B2queryFilter<D>,
+	mut callback: F,
+) {
+	let child_count = shape.get_child_count();
+
+	// Sweep the shape AABB along the translation.
+	let mut swept_aabb = B2AABB::default();
+	let mut proxies_b = Vec::<B2distanceProxy>::with_capacity(child_count);
+	for child_index in 0..child_count {
+		let mut aabb1 = B2AABB::default();
+		shape.compute_aabb(&mut aabb1, transform, child_index);
+		let aabb2 = B2AABB {
+			lower_bound: aabb1.lower_bound + translation,
+			upper_bound: aabb1.upper_bound + translation,
+		};
+		if child_index == 0 {
+			swept_aabb.combine_two(aabb1, aabb2);
+		} else {
+			swept_aabb.combine(aabb1);
+			swept_aabb.combine(aabb2);
+		}
+
+		let mut proxy_b = B2distanceProxy::default();
+		private_distance::set_shape(&mut proxy_b, shape, child_index);
+		proxies_b.push(proxy_b);
+	}
+
+	let broad_phase_ptr = self_.m_contact_manager.borrow().m_broad_phase.clone();
+	let broad_phase = broad_phase_ptr.borrow();
+
+	let mut max_fraction: f32 = 1.0;
+	broad_phase.query(|proxy_id: i32| -> bool {
+		let proxy = broad_phase.get_user_data(proxy_id);
+		let fixture;
+		let index;
+		{
+			let proxy = proxy.as_ref().unwrap().borrow();
+			fixture = upgrade_opt(&proxy.fixture);
+			index = proxy.child_index;
+		}
+
+		if filter.should_query(fixture.clone()) == false {
+			return true;
+		}
+
+		let mut input = B2shapeCastInput::default();
+		{
+			let fixture = fixture.borrow();
+			private_distance::set_shape(&mut input.proxy_a, &*fixture.get_shape(), index as usize);
+			input.transform_a = fixture.get_body().borrow().get_transform();
+		}
+		input.transform_b = transform;
+		input.translation_b = max_fraction * translation;
+
+		// Keep the earliest hit over all children of the cast shape.
+		let mut best: Option<B2shapeCastOutput> = None;
+		for proxy_b in &proxies_b {
+			input.proxy_b = proxy_b.clone();
+			let mut output = B2shapeCastOutput::default();
+			if b2_shape_cast(&mut output, input.clone()) && best.map_or(true, |b| output.lambda < b.lambda) {
+				best = Some(output);
+			}
+		}
+
+		if let Some(output) = best {
+			let fraction: f32 = output.lambda * max_fraction;
+			let value: f32 = callback(fixture, output.point, output.normal, fraction);
+			if value == 0.0 {
+				// The client has terminated the cast.
+				return false;
+			}
+
+			if 0.0 < value && value < max_fraction {
+				// Clip the cast to the reported fraction.
+				max_fraction = value;
+			}
+		}
+
+		return true;
+	}, swept_aabb);
+}
+
+pub(crate) fn cast_shape_closest<D: UserDataType>(
+	self_: &B2world<D>,
+	shape: &dyn B2shapeDynTrait,
+	transform: B2Transform,
+	translation: B2vec2,
+	filter: &B2queryFilter<D>,
+) -> Option<B2castHit<D>> {
+	let mut result: Option<B2castHit<D>> = None;
+	cast_shape(self_, shape, transform, translation, filter,
+		|fixture: FixturePtr<D>, point: B2vec2, normal: B2vec2, fraction: f32| -> f32 {
+			result = Some(B2castHit { fixture, point, normal, fraction });
+			return fraction;
+		});
+	return result;
+}
+
+pub(crate) fn query_aabb_filtered<D: UserDataType, F: B2queryCallback<D>>(
+	self_: &B2world<D>,
+	mut callback: F,
+	aabb: B2AABB,
+	filter: &B2queryFilter<D>,
+) {
+	let broad_phase_ptr = self_.m_contact_manager.borrow().m_broad_phase.clone();
+	let broad_phase = broad_phase_ptr.borrow();
+	broad_phase.query(|proxy_id: i32| -> bool {
+		let proxy = broad_phase.get_user_data(proxy_id);
+		let fixture = upgrade_opt(&proxy.unwrap().borrow().fixture);
+		if filter.should_query(fixture.clone()) == false {
+			return true;
+		}
+		return callback(fixture);
+	}, aabb);
+}
+
+pub(crate) fn ray_cast_filtered<D: UserDataType, F: B2rayCastCallback<D>>(
+	self_: &B2world<D>,
+	mut callback: F,
+	point1: B2vec2,
+	point2: B2vec2,
+	filter: &B2queryFilter<D>,
+) {
+	let broad_phase_ptr = self_.m_contact_manager.borrow().m_broad_phase.clone();
+	let broad_phase = broad_phase_ptr.borrow();
+	let input = B2rayCastInput {
+		max_fraction: 1.0,
+		p1: point1,
+		p2: point2,
+	};
+	broad_phase.ray_cast(|input: &B2rayCastInput, proxy_id: i32| -> f32 {
+		let proxy = broad_phase.get_user_data(proxy_id);
+		let fixture;
+		let index;
+		{
+			let proxy = proxy.as_ref().unwrap().borrow();
+			fixture = upgrade_opt(&proxy.fixture);
+			index = proxy.child_index;
+		}
+
+		// Filter before the exact ray test.
+		if filter.should_query(fixture.clone()) == false {
+			return input.max_fraction;
+		}
+
+		let mut output = B2rayCastOutput::default();
+		let hit: bool = fixture.borrow().ray_cast(&mut output, input, index);
+
+		if hit {
+			let fraction: f32 = output.fraction;
+			let point: B2vec2 = (1.0 - fraction) * input.p1 + fraction * input.p2;
+			return callback(fixture, point, output.normal, fraction);
+		}
+
+		return input.max_fraction;
+	}, &input);
+}
+
+pub(crate) fn ray_cast_closest<D: UserDataType>(
+	self_: &B2world<D>,
+	point1: B2vec2,
+	point2: B2vec2,
+	filter: &B2queryFilter<D>,
+) -> Option<B2castHit<D>> {
+	let mut result: Option<B2castHit<D>> = None;
+	ray_cast_filtered(self_,
+		|fixture: FixturePtr<D>, point: B2vec2, normal: B2vec2, fraction: f32| -> f32 {
+			result = Some(B2castHit { fixture, point, normal, fraction });
+			return fraction;
+		}, point1, point2, filter);
+	return result;
+}
+
+pub(crate) fn ray_cast_all<D: UserDataType>(
+	self_: &B2world<D>,
+	point1: B2vec2,
+	point2: B2vec2,
+	filter: &B2queryFilter<D>,
+) -> Vec<B2castHit<D>> {
+	let mut result = Vec::<B2castHit<D>>::new();
+	ray_cast_filtered(self_,
+		|fixture: FixturePtr<D>, point: B2vec2, normal: B2vec2, fraction: f32| -> f32 {
+			result.push(B2castHit { fixture, point, normal, fraction });
+			return 1.0;
+		}, point1, point2, filter);
+	result.sort_by(|a, b| a.fraction.total_cmp(&b.fraction));
+	return result;
+}
+
+pub(crate) fn distance<D: UserDataType>(
+	fixture_a: FixturePtr<D>,
+	fixture_b: FixturePtr<D>,
+) -> B2fixtureDistanceOutput {
+	return fixture_a.borrow().distance(&fixture_b.borrow());
+}
+
+pub(crate) fn query_nearest<D: UserDataType>(
+	self_: &B2world<D>,
+	point: B2vec2,
+	max_distance: f32,
+	filter: &B2queryFilter<D>,
+) -> Option<(FixturePtr<D>, B2fixtureDistanceOutput)> {
+	let broad_phase_ptr = self_.m_contact_manager.borrow().m_broad_phase.clone();
+	let broad_phase = broad_phase_ptr.borrow();
+
+	let r = B2vec2::new(max_distance, max_distance);
+	let aabb = B2AABB {
+		lower_bound: point - r,
+		upper_bound: point + r,
+	};
+	let point_aabb = B2AABB {
+		lower_bound: point,
+		upper_bound: point,
+	};
+
+	let mut proxy_b = B2distanceProxy::default();
+	proxy_b.set_vertices(&[point], 0.0);
+	let transform_b = B2Transform::new(B2vec2::zero(), B2Rot::new(0.0));
+
+	let mut best_distance: f32 = max_distance;
+	let mut result: Option<(FixturePtr<D>, B2fixtureDistanceOutput)> = None;
+	broad_phase.query(|proxy_id: i32| -> bool {
+		// Prune proxies whose fat AABB is already farther than the best hit.
+		if private_fixture::aabb_distance(broad_phase.get_fat_aabb(proxy_id), point_aabb) > best_distance {
+			return true;
+		}
+
+		let proxy = broad_phase.get_user_data(proxy_id);
+		let fixture;
+		let index;
+		{
+			let proxy = proxy.as_ref().unwrap().borrow();
+			fixture = upgrade_opt(&proxy.fixture);
+			index = proxy.child_index as usize;
+		}
+
+		if filter.should_query(fixture.clone()) == false {
+			return true;
+		}
+
+		let output = private_fixture::b2_fixture_child_distance(&fixture.borrow(), index, &proxy_b, transform_b);
+		if output.distance <= best_distance {
+			best_distance = output.distance;
+			result = Some((fixture.clone(), private_fixture::make_distance_output(&output, index, 0)));
+		}
+		return true;
+	}, aabb);
+
+	return result;
+}
+
+// Is the segment from the explosion center to the point free of static fixtures?
+fn is_visible<D: UserDataType>(
+	self_: &B2world<D>,
+	filter: &B2queryFilter<D>,
+	center: B2vec2,
+	point: B2vec2,
+) -> bool {
+	let mut direction = point - center;
+	if direction.normalize() < B2_LINEAR_SLOP {
+		return true;
+	}
+	// Allow a hit right at the target, the surface itself may be static geometry.
+	let end = point - B2_LINEAR_SLOP * direction;
+	return ray_cast_closest(self_, center, end, filter).is_none();
+}
+
+pub(crate) fn explode<D: UserDataType>(
+	self_: &B2world<D>,
+	center: B2vec2,
+	radius: f32,
+	impulse: f32,
+	falloff: B2forceFieldFalloff,
+	occlusion: bool,
+) -> Vec<B2explosionHit<D>> {
+	b2_assert(self_.is_locked() == false);
+	b2_assert(radius > 0.0);
+
+	let aabb = B2AABB {
+		lower_bound: center - B2vec2::new(radius, radius),
+		upper_bound: center + B2vec2::new(radius, radius),
+	};
+
+	let mut fixtures = Vec::<FixturePtr<D>>::new();
+	query_aabb_filtered(self_, |fixture: FixturePtr<D>| -> bool {
+		let dynamic = fixture.borrow().get_body().borrow().get_type() == B2bodyType::B2DynamicBody;
+		if dynamic {
+			fixtures.push(fixture);
+		}
+		return true;
+	}, aabb, &B2queryFilter {
+		include_sensors: false,
+		..Default::default()
+	});
+
+	// Only static geometry blocks the blast.
+	let occluder_filter = B2queryFilter::<D> {
+		include_sensors: false,
+		predicate: Some(Rc::new(|fixture: FixturePtr<D>| -> bool {
+			return fixture.borrow().get_body().borrow().get_type() == B2bodyType::B2StaticBody;
+		})),
+		..Default::default()
+	};
+
+	// Each body is pushed once, at the point of its fixtures closest to the center.
+	let mut closest_points = Vec::<(BodyPtr<D>, B2vec2, f32)>::new();
+	for fixture in fixtures {
+		let body_ptr = fixture.borrow().get_body();
+		let closest = fixture.borrow().closest_point(center);
+		if closest.distance > radius {
+			continue;
+		}
+
+		match closest_points.iter_mut().find(|entry| Rc::ptr_eq(&entry.0, &body_ptr)) {
+			Some(entry) => {
+				if closest.distance < entry.2 {
+					entry.1 = closest.point_a;
+					entry.2 = closest.distance;
+				}
+			}
+			None => {
+				closest_points.push((body_ptr, closest.point_a, closest.distance));
+			}
+		}
+	}
+
+	let mut hits = Vec::<B2explosionHit<D>>::new();
+	for (body_ptr, point, distance) in closest_points {
+		let body_center = body_ptr.borrow().get_world_center();
+		let mut direction = point - center;
+		if direction.normalize() < B2_EPSILON {
+			// The center is inside the body, push it away from the center.
+			direction = body_center - center;
+			if direction.normalize() < B2_EPSILON {
+				continue;
+			}
+		}
+
+		let mut visibility: f32 = 1.0;
+		if occlusion {
+			let samples = [point, body_center];
+			let visible = samples.iter().filter(|p| is_visible(self_, &occluder_filter, center, **p)).count();
+			visibility = visible as f32 / samples.len() as f32;
+		}
+
+		// The inverse square falloff grows without bound towards the center, no body
+		// receives more than the given impulse.
+		let scale: f32 = b2_min(private_force_field::falloff_scale(falloff, radius, distance), 1.0);
+		let magnitude = impulse * scale * visibility;
+		if magnitude <= 0.0 {
+			continue;
+		}
+
+		let body_impulse = magnitude * direction;
+		body_ptr.borrow_mut().apply_linear_impulse(body_impulse, point, true);
+
+		hits.push(B2explosionHit {
+			body: body_ptr,
+			impulse: body_impulse,
+			point,
+			distance,
+		});
+	}
+	return hits;
+}
@@ -2,6 +2,7 @@ use crate::b2_contact::*;
 use crate::b2_common::*;
 use crate::b2rs_common::UserDataType;
 use crate::b2_collision::*;
+use crate::private::collision::b2_collide_edge;
 use crate::b2_math::*;
 use crate::b2_fixture::*;
 use crate::b2_shape::*;
@@ -51,6 +52,6 @@ impl<D: UserDataType> B2contactDynTrait<D> for B2chainAndPolygonContact<D>
 		 chain.get_child_edge(&mut edge, self.base.m_index_a as usize);
 		 let polygon_shape = self.base.m_fixture_b.as_ref().borrow().get_shape();
 		 let polygon = polygon_shape.as_polygon().unwrap();
-		 b2_collide_edge_and_polygon(manifold, &edge, xf_a, polygon, xf_b);
+		 b2_collide_edge::b2_collide_edge_and_polygon(manifold, &edge, xf_a, polygon, xf_b, self.base.m_speculative_distance);
 	}	
 }
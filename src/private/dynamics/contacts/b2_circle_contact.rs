@@ -2,6 +2,7 @@ use crate::b2_contact::*;
 use crate::b2_common::*;
 use crate::b2rs_common::UserDataType;
 use crate::b2_collision::*;
+use crate::private::collision::b2_collide_circle;
 use crate::b2_math::*;
 use crate::b2_fixture::*;
 use crate::b2_shape::*;
@@ -48,6 +49,6 @@ impl<D: UserDataType> B2contactDynTrait<D> for B2circleContact<D>
 		 let circle1 = circle_shape1.as_circle().unwrap();
 		 let circle_shape2 = self.base.m_fixture_b.as_ref().borrow().get_shape();
 		 let circle2 = circle_shape2.as_circle().unwrap();
-		 b2_collide_circles(manifold, circle1, xf_a, circle2, xf_b);
+		 b2_collide_circle::b2_collide_circles(manifold, circle1, xf_a, circle2, xf_b, self.base.m_speculative_distance);
 	}	
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+use crate::b2_contact::*;
+use crate::b2_common::*;
+use crate::b2rs_common::UserDataType;
+use crate::b2_collision::*;
+use crate::private::collision::b2_collide_custom;
+use crate::b2_math::*;
+use crate::b2_fixture::*;
+use crate::b2_shape::*;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+// Contact of a custom shape with any shape. The custom shape may be on either
+// side, edges and chains are always fixture A.
+pub(crate) struct B2customContact<D: UserDataType>
+{
+	base: B2contact<D>,
+}
+
+impl<D: UserDataType> B2customContact<D>
+{
+	pub fn create(fixture_a: FixturePtr<D>, index_a: i32, fixture_b: FixturePtr<D>, index_b: i32) -> ContactPtr<D>
+	 {
+		return Rc::new(RefCell::new(B2customContact::new(fixture_a,index_a, fixture_b, index_b)));
+	}
+
+	pub fn new(fixture_a: FixturePtr<D>, index_a: i32, fixture_b: FixturePtr<D>, index_b: i32) -> Self
+	{
+		b2_assert(fixture_a.as_ref().borrow().get_type() == B2ShapeType::ECustom
+			|| fixture_b.as_ref().borrow().get_type() == B2ShapeType::ECustom);
+
+		return Self{
+			base: B2contact::new(fixture_a, index_a, fixture_b, index_b)
+		};
+	}
+}
+
+impl<D: UserDataType> B2contactDynTrait<D> for B2customContact<D>
+{
+	fn get_base<'a>(&'a self) -> &'a B2contact<D>
+	{
+		return &self.base;
+	}
+	fn get_base_mut<'a>(&'a mut self) -> &'a mut B2contact<D>
+	{
+		return &mut self.base;
+	}
+
+	fn evaluate(&self, manifold: &mut B2manifold, xf_a: &B2Transform, xf_b: &B2Transform)
+	{
+		let shape_a = self.base.m_fixture_a.as_ref().borrow().get_shape();
+		let shape_b = self.base.m_fixture_b.as_ref().borrow().get_shape();
+		b2_collide_custom::b2_collide_convex(manifold,
+			&*shape_a, self.base.m_index_a as usize, *xf_a,
+			&*shape_b, self.base.m_index_b as usize, *xf_b,
+			self.base.m_speculative_distance);
+	}	
+}
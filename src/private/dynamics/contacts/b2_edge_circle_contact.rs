@@ -2,6 +2,7 @@ use crate::b2_contact::*;
 use crate::b2_common::*;
 use crate::b2rs_common::UserDataType;
 use crate::b2_collision::*;
+use crate::private::collision::b2_collide_edge;
 use crate::b2_math::*;
 use crate::b2_fixture::*;
 use crate::b2_shape::*;
@@ -48,6 +49,6 @@ impl<D: UserDataType> B2contactDynTrait<D> for B2edgeAndCircleContact<D>
 		 let edge = edge_shape.as_edge().unwrap();
 		 let circle_shape = self.base.m_fixture_b.as_ref().borrow().get_shape();
 		 let circle = circle_shape.as_circle().unwrap();
-		 b2_collide_edge_and_circle(manifold, edge, xf_a, circle, xf_b);
+		 b2_collide_edge::b2_collide_edge_and_circle(manifold, edge, xf_a, circle, xf_b, self.base.m_speculative_distance);
 	}	
 }
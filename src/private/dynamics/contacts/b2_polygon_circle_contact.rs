@@ -2,6 +2,7 @@ use crate::b2_contact::*;
 use crate::b2_common::*;
 use crate::b2rs_common::UserDataType;
 use crate::b2_collision::*;
+use crate::private::collision::b2_collide_circle;
 use crate::b2_math::*;
 use crate::b2_fixture::*;
 use crate::b2_shape::*;
@@ -48,6 +49,6 @@ impl<D: UserDataType> B2contactDynTrait<D> for B2polygonAndCircleContact<D>
 		 let polygon = polygon_shape.as_polygon().unwrap();
 		 let circle_shape = self.base.m_fixture_b.as_ref().borrow().get_shape();
 		 let circle = circle_shape.as_circle().unwrap();
-		 b2_collide_polygon_and_circle(manifold, polygon, xf_a, circle, xf_b);
+		 b2_collide_circle::b2_collide_polygon_and_circle(manifold, polygon, xf_a, circle, xf_b, self.base.m_speculative_distance);
 	}	
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use crate::b2_contact::*;
 use crate::b2_common::*;
 use crate::b2rs_common::UserDataType;
 use crate::b2_collision::*;
+use crate::private::collision::b2_collide_polygon;
 use crate::b2_math::*;
 use crate::b2_fixture::*;
 use crate::b2_shape::*;
@@ -48,6 +49,6 @@ impl<D: UserDataType> B2contactDynTrait<D> for B2polygonContact<D>
 		 let polygon1 = polygon_shape1.as_polygon().unwrap();
 		 let polygon_shape2 = self.base.m_fixture_b.as_ref().borrow().get_shape();
 		 let polygon2 = polygon_shape2.as_polygon().unwrap();
-		 b2_collide_polygons(manifold, polygon1, xf_a, polygon2, xf_b);
+		 b2_collide_polygon::b2_collide_polygons(manifold, *polygon1, *xf_a, *polygon2, *xf_b, self.base.m_speculative_distance);
 	}	
 }
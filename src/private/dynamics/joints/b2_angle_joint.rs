@@ -0,0 +1,225 @@
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2rs_common::UserDataType;
+use crate::b2_time_step::*;
+use crate::joints::b2_angle_joint::*;
+
+// Angle constraint
+// c = a2 - a1 - reference_angle
+// cdot = w2 - w1
+// J = [0 0 -1 0 0 1]
+// k = invI1 + invI2
+
+// The spring, motor and limits all act on the same axis, so they share the
+// axial mass. The spring is soft and uses the implicit formulation of the
+// wheel joint.
+
+pub(crate) fn init_velocity_constraints<D: UserDataType>(
+	self_: &mut B2angleJoint<D>,
+	data: &B2solverData,
+	positions: &[B2position],
+	velocities: &mut [B2velocity],
+) {
+	{
+		let m_body_a = self_.base.m_body_a.borrow();
+		let m_body_b = self_.base.m_body_b.borrow();
+		self_.m_index_a = m_body_a.m_island_index;
+		self_.m_index_b = m_body_b.m_island_index;
+		self_.m_inv_ia = m_body_a.m_inv_i;
+		self_.m_inv_ib = m_body_b.m_inv_i;
+	}
+
+	let a_a: f32 = positions[self_.m_index_a as usize].a;
+	let a_b: f32 = positions[self_.m_index_b as usize].a;
+
+	let mut w_a: f32 = velocities[self_.m_index_a as usize].w;
+	let mut w_b: f32 = velocities[self_.m_index_b as usize].w;
+
+	let i_a: f32 = self_.m_inv_ia;
+	let i_b: f32 = self_.m_inv_ib;
+
+	self_.m_axial_mass = i_a + i_b;
+	let fixed_rotation: bool;
+	if self_.m_axial_mass > 0.0 {
+		self_.m_axial_mass = 1.0 / self_.m_axial_mass;
+		fixed_rotation = false;
+	} else {
+		fixed_rotation = true;
+	}
+
+	self_.m_angle = a_b - a_a - self_.m_reference_angle;
+
+	self_.m_spring_mass = 0.0;
+	self_.m_bias = 0.0;
+	self_.m_gamma = 0.0;
+	if self_.m_enable_spring && self_.m_stiffness > 0.0 && fixed_rotation == false {
+		// magic formulas
+		let h: f32 = data.step.dt;
+		self_.m_gamma = h * (self_.m_damping + h * self_.m_stiffness);
+		if self_.m_gamma > 0.0 {
+			self_.m_gamma = 1.0 / self_.m_gamma;
+		}
+
+		self_.m_bias = self_.m_angle * h * self_.m_stiffness * self_.m_gamma;
+
+		self_.m_spring_mass = i_a + i_b + self_.m_gamma;
+		if self_.m_spring_mass > 0.0 {
+			self_.m_spring_mass = 1.0 / self_.m_spring_mass;
+		}
+	} else {
+		self_.m_spring_impulse = 0.0;
+	}
+
+	if self_.m_enable_limit == false || fixed_rotation {
+		self_.m_lower_impulse = 0.0;
+		self_.m_upper_impulse = 0.0;
+	}
+
+	if self_.m_enable_motor == false || fixed_rotation {
+		self_.m_motor_impulse = 0.0;
+	}
+
+	if data.step.warm_starting {
+		// Scale impulses to support a variable time step.
+		self_.m_spring_impulse *= data.step.dt_ratio;
+		self_.m_motor_impulse *= data.step.dt_ratio;
+		self_.m_lower_impulse *= data.step.dt_ratio;
+		self_.m_upper_impulse *= data.step.dt_ratio;
+
+		let axial_impulse: f32 = self_.m_spring_impulse + self_.m_motor_impulse + self_.m_lower_impulse
+			- self_.m_upper_impulse;
+
+		w_a -= i_a * axial_impulse;
+		w_b += i_b * axial_impulse;
+	} else {
+		self_.m_spring_impulse = 0.0;
+		self_.m_motor_impulse = 0.0;
+		self_.m_lower_impulse = 0.0;
+		self_.m_upper_impulse = 0.0;
+	}
+
+	velocities[self_.m_index_a as usize].w = w_a;
+	velocities[self_.m_index_b as usize].w = w_b;
+}
+
+pub(crate) fn solve_velocity_constraints<D: UserDataType>(
+	self_: &mut B2angleJoint<D>,
+	data: &B2solverData,
+	velocities: &mut [B2velocity],
+) {
+	let mut w_a: f32 = velocities[self_.m_index_a as usize].w;
+	let mut w_b: f32 = velocities[self_.m_index_b as usize].w;
+
+	let i_a: f32 = self_.m_inv_ia;
+	let i_b: f32 = self_.m_inv_ib;
+
+	let fixed_rotation: bool = i_a + i_b == 0.0;
+
+	// solve spring constraint
+	if self_.m_enable_spring && self_.m_stiffness > 0.0 && fixed_rotation == false {
+		let cdot: f32 = w_b - w_a;
+		let impulse: f32 =
+			-self_.m_spring_mass * (cdot + self_.m_bias + self_.m_gamma * self_.m_spring_impulse);
+		self_.m_spring_impulse += impulse;
+
+		w_a -= i_a * impulse;
+		w_b += i_b * impulse;
+	}
+
+	// solve motor constraint.
+	if self_.m_enable_motor && fixed_rotation == false {
+		let cdot: f32 = w_b - w_a - self_.m_motor_speed;
+		let mut impulse: f32 = -self_.m_axial_mass * cdot;
+		let old_impulse: f32 = self_.m_motor_impulse;
+		let max_impulse: f32 = data.step.dt * self_.m_max_motor_torque;
+		self_.m_motor_impulse = b2_clamp(self_.m_motor_impulse + impulse, -max_impulse, max_impulse);
+		impulse = self_.m_motor_impulse - old_impulse;
+
+		w_a -= i_a * impulse;
+		w_b += i_b * impulse;
+	}
+
+	if self_.m_enable_limit && fixed_rotation == false {
+		// Lower limit
+		{
+			let c: f32 = self_.m_angle - self_.m_lower_angle;
+			let cdot: f32 = w_b - w_a;
+			let mut impulse: f32 = -self_.m_axial_mass * (cdot + b2_max(c, 0.0) * data.step.inv_dt);
+			let old_impulse: f32 = self_.m_lower_impulse;
+			self_.m_lower_impulse = b2_max(self_.m_lower_impulse + impulse, 0.0);
+			impulse = self_.m_lower_impulse - old_impulse;
+
+			w_a -= i_a * impulse;
+			w_b += i_b * impulse;
+		}
+
+		// Upper limit
+		// Note: signs are flipped to keep c positive when the constraint is satisfied.
+		// This also keeps the impulse positive when the limit is active.
+		{
+			let c: f32 = self_.m_upper_angle - self_.m_angle;
+			let cdot: f32 = w_a - w_b;
+			let mut impulse: f32 = -self_.m_axial_mass * (cdot + b2_max(c, 0.0) * data.step.inv_dt);
+			let old_impulse: f32 = self_.m_upper_impulse;
+			self_.m_upper_impulse = b2_max(self_.m_upper_impulse + impulse, 0.0);
+			impulse = self_.m_upper_impulse - old_impulse;
+
+			w_a += i_a * impulse;
+			w_b -= i_b * impulse;
+		}
+	}
+
+	velocities[self_.m_index_a as usize].w = w_a;
+	velocities[self_.m_index_b as usize].w = w_b;
+}
+
+pub(crate) fn solve_position_constraints<D: UserDataType>(
+	self_: &B2angleJoint<D>,
+	_data: &B2solverData,
+	positions: &mut [B2position],
+) -> bool {
+	let mut a_a: f32 = positions[self_.m_index_a as usize].a;
+	let mut a_b: f32 = positions[self_.m_index_b as usize].a;
+
+	let mut angular_error: f32 = 0.0;
+
+	let fixed_rotation: bool = self_.m_inv_ia + self_.m_inv_ib == 0.0;
+
+	// Solve angular limit constraint
+	if self_.m_enable_limit && fixed_rotation == false {
+		let angle: f32 = a_b - a_a - self_.m_reference_angle;
+		let mut c: f32 = 0.0;
+
+		if b2_abs(self_.m_upper_angle - self_.m_lower_angle) < 2.0 * B2_ANGULAR_SLOP {
+			// Prevent large angular corrections
+			c = b2_clamp(
+				angle - self_.m_lower_angle,
+				-B2_MAX_ANGULAR_CORRECTION,
+				B2_MAX_ANGULAR_CORRECTION,
+			);
+		} else if angle <= self_.m_lower_angle {
+			// Prevent large angular corrections and allow some slop.
+			c = b2_clamp(
+				angle - self_.m_lower_angle + B2_ANGULAR_SLOP,
+				-B2_MAX_ANGULAR_CORRECTION,
+				0.0,
+			);
+		} else if angle >= self_.m_upper_angle {
+			// Prevent large angular corrections and allow some slop.
+			c = b2_clamp(
+				angle - self_.m_upper_angle - B2_ANGULAR_SLOP,
+				0.0,
+				B2_MAX_ANGULAR_CORRECTION,
+			);
+		}
+
+		let limit_impulse: f32 = -self_.m_axial_mass * c;
+		a_a -= self_.m_inv_ia * limit_impulse;
+		a_b += self_.m_inv_ib * limit_impulse;
+		angular_error = b2_abs(c);
+	}
+
+	positions[self_.m_index_a as usize].a = a_a;
+	positions[self_.m_index_b as usize].a = a_b;
+	return angular_error <= B2_ANGULAR_SLOP;
+}
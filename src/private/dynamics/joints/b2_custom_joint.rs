@@ -0,0 +1,49 @@
+use crate::b2_draw::*;
+use crate::b2_joint::*;
+use crate::b2_math::*;
+use crate::b2rs_common::UserDataType;
+use crate::b2_time_step::*;
+use crate::joints::b2_custom_joint::*;
+
+pub(crate) fn init_velocity_constraints<D: UserDataType>(
+	self_: &mut B2customJointHost<D>,
+	data: &B2solverData,
+	positions: &[B2position],
+	velocities: &mut [B2velocity],
+) {
+	{
+		let m_body_a = self_.base.m_body_a.borrow();
+		let m_body_b = self_.base.m_body_b.borrow();
+		self_.m_bodies = B2customJointBodies {
+			index_a: m_body_a.m_island_index as usize,
+			index_b: m_body_b.m_island_index as usize,
+			local_center_a: m_body_a.m_sweep.local_center,
+			local_center_b: m_body_b.m_sweep.local_center,
+			inv_mass_a: m_body_a.m_inv_mass,
+			inv_mass_b: m_body_b.m_inv_mass,
+			inv_i_a: m_body_a.m_inv_i,
+			inv_i_b: m_body_b.m_inv_i,
+		};
+	}
+
+	self_
+		.m_joint
+		.borrow_mut()
+		.init_velocity_constraints(&self_.m_bodies, data, positions, velocities);
+}
+
+pub(crate) fn draw_default<D: UserDataType, T: B2customJoint<D> + ?Sized>(
+	self_: &T,
+	base: &B2joint<D>,
+	draw: &mut dyn B2drawTrait,
+) {
+	let x1: B2vec2 = base.m_body_a.borrow().get_transform().p;
+	let x2: B2vec2 = base.m_body_b.borrow().get_transform().p;
+	let p1: B2vec2 = self_.get_anchor_a(base);
+	let p2: B2vec2 = self_.get_anchor_b(base);
+
+	let color = B2color::new(0.5, 0.8, 0.8);
+	draw.draw_segment(x1, p1, color);
+	draw.draw_segment(p1, p2, color);
+	draw.draw_segment(x2, p2, color);
+}
@@ -40,6 +40,15 @@ use crate::joints::b2_prismatic_joint::*;
 // So:
 // cdot + max(c1, 0)/h >= 0
 
+// The spring and the servo act on the motor axis. The spring is soft and uses
+// the implicit formulation of the wheel joint. The servo is a motor whose speed
+// closes a Baumgarte fraction of the error to the target translation each step,
+// clamped to the maximum servo speed. Closing the whole error in one step rings
+// with few velocity iterations.
+
+// Friction constraint
+// The motor constraint with a zero speed and a force bound of max_friction_force.
+
 // Block Solver
 // We develop a block solver that includes the angular and linear constraints. This makes the limit stiffer.
 //
@@ -96,6 +105,18 @@ pub(crate) fn new<D: UserDataType>(def: &B2prismaticJointDef<D>) -> B2prismaticJ
 		m_motor_speed,
 		m_enable_limit,
 		m_enable_motor,
+		m_spring_impulse: 0.0,
+		m_servo_impulse: 0.0,
+		m_friction_impulse: 0.0,
+		m_enable_spring: def.enable_spring,
+		m_stiffness: def.stiffness,
+		m_damping: def.damping,
+		m_rest_translation: def.rest_translation,
+		m_enable_servo: def.enable_servo,
+		m_target_translation: def.target_translation,
+		m_max_servo_speed: def.max_servo_speed,
+		m_max_servo_force: def.max_servo_force,
+		m_max_friction_force: def.max_friction_force,
 
 		m_index_a: 0,
 		m_index_b: 0,
@@ -114,6 +135,9 @@ pub(crate) fn new<D: UserDataType>(def: &B2prismaticJointDef<D>) -> B2prismaticJ
 		m_k: B2Mat22::default(),
 		m_translation: 0.0,
 		m_axial_mass: 0.0,
+		m_spring_mass: 0.0,
+		m_bias: 0.0,
+		m_gamma: 0.0,
 	};
 }
 
@@ -187,9 +211,8 @@ pub(crate) fn init_velocity_constraints<D: UserDataType>(
 		self_.m_k.ey.set(k12, k22);
 	}
 
-	if self_.m_enable_limit {
-		self_.m_translation = b2_dot(self_.m_axis, d);
-	} else {
+	self_.m_translation = b2_dot(self_.m_axis, d);
+	if self_.m_enable_limit == false {
 		self_.m_lower_impulse = 0.0;
 		self_.m_upper_impulse = 0.0;
 	}
@@ -198,14 +221,49 @@ pub(crate) fn init_velocity_constraints<D: UserDataType>(
 		self_.m_motor_impulse = 0.0;
 	}
 
+	self_.m_spring_mass = 0.0;
+	self_.m_bias = 0.0;
+	self_.m_gamma = 0.0;
+	if self_.m_enable_spring && self_.m_stiffness > 0.0 && self_.m_axial_mass > 0.0 {
+		let c: f32 = self_.m_translation - self_.m_rest_translation;
+
+		// magic formulas
+		let h: f32 = data.step.dt;
+		self_.m_gamma = h * (self_.m_damping + h * self_.m_stiffness);
+		if self_.m_gamma > 0.0 {
+			self_.m_gamma = 1.0 / self_.m_gamma;
+		}
+
+		self_.m_bias = c * h * self_.m_stiffness * self_.m_gamma;
+
+		self_.m_spring_mass = 1.0 / self_.m_axial_mass + self_.m_gamma;
+		if self_.m_spring_mass > 0.0 {
+			self_.m_spring_mass = 1.0 / self_.m_spring_mass;
+		}
+	} else {
+		self_.m_spring_impulse = 0.0;
+	}
+
+	if self_.m_enable_servo == false {
+		self_.m_servo_impulse = 0.0;
+	}
+
+	if self_.m_max_friction_force == 0.0 {
+		self_.m_friction_impulse = 0.0;
+	}
+
 	if data.step.warm_starting {
 		// Account for variable time step.
 		self_.m_impulse *= data.step.dt_ratio;
 		self_.m_motor_impulse *= data.step.dt_ratio;
+		self_.m_spring_impulse *= data.step.dt_ratio;
+		self_.m_servo_impulse *= data.step.dt_ratio;
+		self_.m_friction_impulse *= data.step.dt_ratio;
 		self_.m_lower_impulse *= data.step.dt_ratio;
 		self_.m_upper_impulse *= data.step.dt_ratio;
 
-		let axial_impulse: f32 = self_.m_motor_impulse + self_.m_lower_impulse - self_.m_upper_impulse;
+		let axial_impulse: f32 = self_.m_spring_impulse + self_.m_friction_impulse + self_.m_servo_impulse
+			+ self_.m_motor_impulse + self_.m_lower_impulse - self_.m_upper_impulse;
 		let p: B2vec2 = self_.m_impulse.x * self_.m_perp + axial_impulse * self_.m_axis;
 		let la: f32 = self_.m_impulse.x * self_.m_s1 + self_.m_impulse.y + axial_impulse * self_.m_a1;
 		let lb: f32 = self_.m_impulse.x * self_.m_s2 + self_.m_impulse.y + axial_impulse * self_.m_a2;
@@ -218,6 +276,9 @@ pub(crate) fn init_velocity_constraints<D: UserDataType>(
 	} else {
 		self_.m_impulse.set_zero();
 		self_.m_motor_impulse = 0.0;
+		self_.m_spring_impulse = 0.0;
+		self_.m_servo_impulse = 0.0;
+		self_.m_friction_impulse = 0.0;
 		self_.m_lower_impulse = 0.0;
 		self_.m_upper_impulse = 0.0;
 	}
@@ -243,6 +304,66 @@ pub(crate) fn solve_velocity_constraints<D: UserDataType>(
 	let i_a: f32 = self_.m_inv_ia;
 	let i_b: f32 = self_.m_inv_ib;
 
+	// solve spring constraint
+	if self_.m_enable_spring && self_.m_stiffness > 0.0 && self_.m_axial_mass > 0.0 {
+		let cdot: f32 = b2_dot(self_.m_axis, v_b - v_a) + self_.m_a2 * w_b - self_.m_a1 * w_a;
+		let impulse: f32 =
+			-self_.m_spring_mass * (cdot + self_.m_bias + self_.m_gamma * self_.m_spring_impulse);
+		self_.m_spring_impulse += impulse;
+
+		let p: B2vec2 = impulse * self_.m_axis;
+		let la: f32 = impulse * self_.m_a1;
+		let lb: f32 = impulse * self_.m_a2;
+
+		v_a -= m_a * p;
+		w_a -= i_a * la;
+		v_b += m_b * p;
+		w_b += i_b * lb;
+	}
+
+	// solve friction constraint
+	if self_.m_max_friction_force > 0.0 {
+		let cdot: f32 = b2_dot(self_.m_axis, v_b - v_a) + self_.m_a2 * w_b - self_.m_a1 * w_a;
+		let mut impulse: f32 = -self_.m_axial_mass * cdot;
+		let old_impulse: f32 = self_.m_friction_impulse;
+		let max_impulse: f32 = data.step.dt * self_.m_max_friction_force;
+		self_.m_friction_impulse = b2_clamp(self_.m_friction_impulse + impulse, -max_impulse, max_impulse);
+		impulse = self_.m_friction_impulse - old_impulse;
+
+		let p: B2vec2 = impulse * self_.m_axis;
+		let la: f32 = impulse * self_.m_a1;
+		let lb: f32 = impulse * self_.m_a2;
+
+		v_a -= m_a * p;
+		w_a -= i_a * la;
+		v_b += m_b * p;
+		w_b += i_b * lb;
+	}
+
+	// solve servo constraint
+	if self_.m_enable_servo {
+		let servo_speed: f32 = b2_clamp(
+			B2_BAUMGARTE * (self_.m_target_translation - self_.m_translation) * data.step.inv_dt,
+			-self_.m_max_servo_speed,
+			self_.m_max_servo_speed,
+		);
+		let cdot: f32 = b2_dot(self_.m_axis, v_b - v_a) + self_.m_a2 * w_b - self_.m_a1 * w_a;
+		let mut impulse: f32 = self_.m_axial_mass * (servo_speed - cdot);
+		let old_impulse: f32 = self_.m_servo_impulse;
+		let max_impulse: f32 = data.step.dt * self_.m_max_servo_force;
+		self_.m_servo_impulse = b2_clamp(self_.m_servo_impulse + impulse, -max_impulse, max_impulse);
+		impulse = self_.m_servo_impulse - old_impulse;
+
+		let p: B2vec2 = impulse * self_.m_axis;
+		let la: f32 = impulse * self_.m_a1;
+		let lb: f32 = impulse * self_.m_a2;
+
+		v_a -= m_a * p;
+		w_a -= i_a * la;
+		v_b += m_b * p;
+		w_b += i_b * lb;
+	}
+
 	// solve linear motor constraint
 	if self_.m_enable_motor {
 		let cdot: f32 = b2_dot(self_.m_axis, v_b - v_a) + self_.m_a2 * w_b - self_.m_a1 * w_a;
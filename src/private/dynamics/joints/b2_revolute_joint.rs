@@ -18,6 +18,15 @@ use crate::joints::b2_revolute_joint::*;
 // J = [0 0 -1 0 0 1]
 // k = invI1 + invI2
 
+// The spring and the servo act on the motor axis. The spring is soft and uses
+// the implicit formulation of the wheel joint. The servo is a motor whose speed
+// closes a Baumgarte fraction of the error to the target angle each step,
+// clamped to the maximum servo speed. Closing the whole error in one step rings
+// with few velocity iterations.
+
+// Friction constraint
+// The motor constraint with a zero speed and a torque bound of max_friction_torque.
+
 pub(crate) fn init_velocity_constraints<D: UserDataType>(
 	self_: &mut B2revoluteJoint<D>,
 	data: &B2solverData,
@@ -88,15 +97,50 @@ pub(crate) fn init_velocity_constraints<D: UserDataType>(
 		self_.m_motor_impulse = 0.0;
 	}
 
+	self_.m_spring_mass = 0.0;
+	self_.m_bias = 0.0;
+	self_.m_gamma = 0.0;
+	if self_.m_enable_spring && self_.m_stiffness > 0.0 && fixed_rotation == false {
+		let c: f32 = self_.m_angle - self_.m_rest_angle;
+
+		// magic formulas
+		let h: f32 = data.step.dt;
+		self_.m_gamma = h * (self_.m_damping + h * self_.m_stiffness);
+		if self_.m_gamma > 0.0 {
+			self_.m_gamma = 1.0 / self_.m_gamma;
+		}
+
+		self_.m_bias = c * h * self_.m_stiffness * self_.m_gamma;
+
+		self_.m_spring_mass = i_a + i_b + self_.m_gamma;
+		if self_.m_spring_mass > 0.0 {
+			self_.m_spring_mass = 1.0 / self_.m_spring_mass;
+		}
+	} else {
+		self_.m_spring_impulse = 0.0;
+	}
+
+	if self_.m_enable_servo == false || fixed_rotation {
+		self_.m_servo_impulse = 0.0;
+	}
+
+	if self_.m_max_friction_torque == 0.0 || fixed_rotation {
+		self_.m_friction_impulse = 0.0;
+	}
+
 	if data.step.warm_starting {
 		// Scale impulses to support a variable time step.
 		self_.m_impulse *= data.step.dt_ratio;
 		self_.m_motor_impulse *= data.step.dt_ratio;
+		self_.m_spring_impulse *= data.step.dt_ratio;
+		self_.m_servo_impulse *= data.step.dt_ratio;
+		self_.m_friction_impulse *= data.step.dt_ratio;
 
 		self_.m_lower_impulse *= data.step.dt_ratio;
 		self_.m_upper_impulse *= data.step.dt_ratio;
 
-		let axial_impulse: f32 = self_.m_motor_impulse + self_.m_lower_impulse - self_.m_upper_impulse;
+		let axial_impulse: f32 = self_.m_spring_impulse + self_.m_friction_impulse + self_.m_servo_impulse
+			+ self_.m_motor_impulse + self_.m_lower_impulse - self_.m_upper_impulse;
 		let p = B2vec2::new(self_.m_impulse.x, self_.m_impulse.y);
 
 		v_a -= m_a * p;
@@ -107,6 +151,9 @@ pub(crate) fn init_velocity_constraints<D: UserDataType>(
 	} else {
 		self_.m_impulse.set_zero();
 		self_.m_motor_impulse = 0.0;
+		self_.m_spring_impulse = 0.0;
+		self_.m_servo_impulse = 0.0;
+		self_.m_friction_impulse = 0.0;
 
 		self_.m_lower_impulse = 0.0;
 		self_.m_upper_impulse = 0.0;
@@ -141,6 +188,48 @@ pub(crate) fn solve_velocity_constraints<D: UserDataType>(
 
 	let fixed_rotation: bool = i_a + i_b == 0.0;
 
+	// solve spring constraint
+	if self_.m_enable_spring && self_.m_stiffness > 0.0 && fixed_rotation == false {
+		let cdot: f32 = w_b - w_a;
+		let impulse: f32 =
+			-self_.m_spring_mass * (cdot + self_.m_bias + self_.m_gamma * self_.m_spring_impulse);
+		self_.m_spring_impulse += impulse;
+
+		w_a -= i_a * impulse;
+		w_b += i_b * impulse;
+	}
+
+	// solve friction constraint
+	if self_.m_max_friction_torque > 0.0 && fixed_rotation == false {
+		let cdot: f32 = w_b - w_a;
+		let mut impulse: f32 = -self_.m_axial_mass * cdot;
+		let old_impulse: f32 = self_.m_friction_impulse;
+		let max_impulse: f32 = data.step.dt * self_.m_max_friction_torque;
+		self_.m_friction_impulse = b2_clamp(self_.m_friction_impulse + impulse, -max_impulse, max_impulse);
+		impulse = self_.m_friction_impulse - old_impulse;
+
+		w_a -= i_a * impulse;
+		w_b += i_b * impulse;
+	}
+
+	// solve servo constraint
+	if self_.m_enable_servo && fixed_rotation == false {
+		let servo_speed: f32 = b2_clamp(
+			B2_BAUMGARTE * (self_.m_target_angle - self_.m_angle) * data.step.inv_dt,
+			-self_.m_max_servo_speed,
+			self_.m_max_servo_speed,
+		);
+		let cdot: f32 = w_b - w_a - servo_speed;
+		let mut impulse: f32 = -self_.m_axial_mass * cdot;
+		let old_impulse: f32 = self_.m_servo_impulse;
+		let max_impulse: f32 = data.step.dt * self_.m_max_servo_torque;
+		self_.m_servo_impulse = b2_clamp(self_.m_servo_impulse + impulse, -max_impulse, max_impulse);
+		impulse = self_.m_servo_impulse - old_impulse;
+
+		w_a -= i_a * impulse;
+		w_b += i_b * impulse;
+	}
+
 	// solve motor constraint.
 	if self_.m_enable_motor && fixed_rotation == false {
 		let cdot: f32 = w_b - w_a - self_.m_motor_speed;
@@ -21,6 +21,9 @@ use crate::joints::b2_wheel_joint::*;
 // cdot = w_b - w_a
 // J = [0 0 -1 0 0 1]
 
+// Friction rotational constraint
+// The motor constraint with a zero speed and a torque bound of max_friction_torque.
+
 pub(crate) fn init_velocity_constraints<D: UserDataType>(
 	self_: &mut B2wheelJoint<D>,
 	data: &B2solverData,
@@ -133,16 +136,28 @@ pub(crate) fn init_velocity_constraints<D: UserDataType>(
 		self_.m_motor_impulse = 0.0;
 	}
 
+	if self_.m_max_friction_torque > 0.0 {
+		self_.m_friction_mass = i_a + i_b;
+		if self_.m_friction_mass > 0.0 {
+			self_.m_friction_mass = 1.0 / self_.m_friction_mass;
+		}
+	} else {
+		self_.m_friction_mass = 0.0;
+		self_.m_friction_impulse = 0.0;
+	}
+
 	if data.step.warm_starting {
 		// Account for variable time step.
 		self_.m_impulse *= data.step.dt_ratio;
 		self_.m_spring_impulse *= data.step.dt_ratio;
 		self_.m_motor_impulse *= data.step.dt_ratio;
+		self_.m_friction_impulse *= data.step.dt_ratio;
 
 		let axial_impulse: f32 = self_.m_spring_impulse + self_.m_lower_impulse - self_.m_upper_impulse;
 		let p: B2vec2 = self_.m_impulse * self_.m_ay + axial_impulse * self_.m_ax;
-		let la: f32 = self_.m_impulse * self_.m_s_ay + axial_impulse * self_.m_s_ax + self_.m_motor_impulse;
-		let lb: f32 = self_.m_impulse * self_.m_s_by + axial_impulse * self_.m_s_bx + self_.m_motor_impulse;
+		let angular_impulse: f32 = self_.m_motor_impulse + self_.m_friction_impulse;
+		let la: f32 = self_.m_impulse * self_.m_s_ay + axial_impulse * self_.m_s_ax + angular_impulse;
+		let lb: f32 = self_.m_impulse * self_.m_s_by + axial_impulse * self_.m_s_bx + angular_impulse;
 
 		v_a -= self_.m_inv_mass_a * p;
 		w_a -= self_.m_inv_ia * la;
@@ -153,6 +168,7 @@ pub(crate) fn init_velocity_constraints<D: UserDataType>(
 		self_.m_impulse = 0.0;
 		self_.m_spring_impulse = 0.0;
 		self_.m_motor_impulse = 0.0;
+		self_.m_friction_impulse = 0.0;
 		self_.m_lower_impulse = 0.0;
 		self_.m_upper_impulse = 0.0;
 	}
@@ -194,6 +210,20 @@ pub(crate) fn solve_velocity_constraints<D: UserDataType>(
 		w_b += i_b * lb;
 	}
 
+	// solve rotational friction constraint
+	if self_.m_max_friction_torque > 0.0 {
+		let cdot: f32 = w_b - w_a;
+		let mut impulse: f32 = -self_.m_friction_mass * cdot;
+
+		let old_impulse: f32 = self_.m_friction_impulse;
+		let max_impulse: f32 = data.step.dt * self_.m_max_friction_torque;
+		self_.m_friction_impulse = b2_clamp(self_.m_friction_impulse + impulse, -max_impulse, max_impulse);
+		impulse = self_.m_friction_impulse - old_impulse;
+
+		w_a -= i_a * impulse;
+		w_b += i_b * impulse;
+	}
+
 	// solve rotational motor constraint
 	{
 		let cdot: f32 = w_b - w_a - self_.m_motor_speed;
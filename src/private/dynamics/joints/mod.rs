@@ -1,3 +1,5 @@
+pub mod b2_angle_joint;
+pub mod b2_custom_joint;
 pub mod b2_distance_joint;
 pub mod b2_friction_joint;
 pub mod b2_gear_joint;
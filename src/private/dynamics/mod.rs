@@ -5,6 +5,9 @@ pub mod b2_contact_manager;
 pub mod b2_world_callbacks;
 pub mod b2_contact;
 pub mod b2_world;
+pub mod b2_world_query;
+pub mod b2_force_field;
+pub mod b2_fracture;
 pub mod b2_joint;
 pub mod b2_island;
 pub mod b2_island_private;
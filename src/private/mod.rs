@@ -1,4 +1,8 @@
 pub(crate) mod common;
 pub(crate) mod collision;
 pub(crate) mod dynamics;
-pub(crate) mod rope;
\ No newline at end of file
+pub(crate) mod rope;
+pub(crate) mod particle;
+pub(crate) mod character;
+pub(crate) mod vehicle;
+pub(crate) mod controllers;
\ No newline at end of file
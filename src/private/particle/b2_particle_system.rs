@@ -0,0 +1,761 @@
+use crate::b2_body::*;
+use crate::b2_collision::*;
+use crate::b2_common::*;
+use crate::b2_distance::*;
+use crate::b2_draw::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_particle::*;
+use crate::b2_particle_system::*;
+use crate::b2_time_step::*;
+use crate::b2_world::*;
+use crate::b2_world_callbacks::*;
+use crate::b2rs_common::*;
+
+use crate::private::collision::b2_distance as private_distance;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// The spacing of the particles of a resting fluid, in diameters.
+const B2_PARTICLE_STRIDE: f32 = 0.75;
+
+// The weight of a particle in a resting fluid. There is no pressure below it.
+const B2_MIN_PARTICLE_WEIGHT: f32 = 1.0;
+
+// The largest pressure, relative to the critical pressure.
+const B2_MAX_PARTICLE_PRESSURE: f32 = 0.25;
+
+pub(crate) fn create_particle_system<D: UserDataType>(
+	self_: &mut B2world<D>,
+	def: &B2particleSystemDef,
+) -> B2particleSystemPtr {
+	b2_assert(self_.is_locked() == false);
+	b2_assert(def.radius > 0.0 && def.density > 0.0);
+
+	let system = Rc::new(RefCell::new(B2particleSystem {
+		m_def: *def,
+		m_positions: Vec::new(),
+		m_velocities: Vec::new(),
+		m_colors: Vec::new(),
+		m_weights: Vec::new(),
+		m_groups: Vec::new(),
+		m_rest_positions: Vec::new(),
+		m_group_list: Vec::new(),
+		m_springs: Vec::new(),
+	}));
+	self_.m_particle_system_list.push(system.clone());
+	return system;
+}
+
+pub(crate) fn destroy_particle_system<D: UserDataType>(self_: &mut B2world<D>, system: B2particleSystemPtr) {
+	b2_assert(self_.is_locked() == false);
+	self_.m_particle_system_list.retain(|s| Rc::ptr_eq(s, &system) == false);
+}
+
+pub(crate) fn shift_origin<D: UserDataType>(self_: &B2world<D>, new_origin: B2vec2) {
+	for system in &self_.m_particle_system_list {
+		let mut system = system.borrow_mut();
+		for p in &mut system.m_positions {
+			*p -= new_origin;
+		}
+		for group in &system.m_group_list {
+			group.borrow_mut().m_center -= new_origin;
+		}
+	}
+}
+
+// A particle fills a square of the stride.
+pub(crate) fn get_particle_mass(self_: &B2particleSystem) -> f32 {
+	let stride: f32 = B2_PARTICLE_STRIDE * 2.0 * self_.m_def.radius;
+	return self_.m_def.density * stride * stride;
+}
+
+pub(crate) fn create_particle(
+	self_: &mut B2particleSystem,
+	def: &B2particleDef,
+	group: Option<B2particleGroupPtr>,
+) -> usize {
+	self_.m_positions.push(def.position);
+	self_.m_velocities.push(def.velocity);
+	self_.m_colors.push(def.color);
+	self_.m_weights.push(0.0);
+	self_.m_groups.push(group);
+	self_.m_rest_positions.push(def.position);
+	return self_.m_positions.len() - 1;
+}
+
+pub(crate) fn destroy_particle(self_: &mut B2particleSystem, index: usize) {
+	let last: usize = self_.m_positions.len() - 1;
+	self_.m_springs.retain(|s| s.index_a != index && s.index_b != index);
+	for s in &mut self_.m_springs {
+		if s.index_a == last {
+			s.index_a = index;
+		}
+		if s.index_b == last {
+			s.index_b = index;
+		}
+	}
+
+	if let Some(group) = &self_.m_groups[index] {
+		group.borrow_mut().m_count -= 1;
+	}
+
+	self_.m_positions.swap_remove(index);
+	self_.m_velocities.swap_remove(index);
+	self_.m_colors.swap_remove(index);
+	self_.m_weights.swap_remove(index);
+	self_.m_groups.swap_remove(index);
+	self_.m_rest_positions.swap_remove(index);
+}
+
+pub(crate) fn create_particle_group(self_: &mut B2particleSystem, def: &B2particleGroupDef) -> B2particleGroupPtr {
+	let diameter: f32 = 2.0 * self_.m_def.radius;
+	let stride: f32 = if def.stride > 0.0 { def.stride } else { B2_PARTICLE_STRIDE * diameter };
+	let xf = B2Transform::new(def.position, B2Rot::new(def.angle));
+
+	let mut points: Vec<B2vec2> = Vec::new();
+	if let Some(shape) = &def.shape {
+		// The grid is aligned with the world axes, so neighbouring groups
+		// line up.
+		let shape = shape.borrow();
+		for child_index in 0..shape.get_child_count() {
+			let mut aabb = B2AABB::default();
+			shape.compute_aabb(&mut aabb, xf, child_index);
+			let mut y: f32 = f32::floor(aabb.lower_bound.y / stride) * stride;
+			while y < aabb.upper_bound.y {
+				let mut x: f32 = f32::floor(aabb.lower_bound.x / stride) * stride;
+				while x < aabb.upper_bound.x {
+					let p = B2vec2::new(x, y);
+					if shape.test_point(xf, p) {
+						points.push(p);
+					}
+					x += stride;
+				}
+				y += stride;
+			}
+		}
+	}
+	for p in &def.positions {
+		points.push(b2_mul_transform_by_vec2(xf, *p));
+	}
+
+	let group = Rc::new(RefCell::new(B2particleGroup {
+		m_flags: def.flags,
+		m_count: points.len(),
+		..Default::default()
+	}));
+
+	let first: usize = self_.m_positions.len();
+	for p in points {
+		let particle_def = B2particleDef {
+			position: p,
+			velocity: def.linear_velocity + b2_cross_scalar_by_vec(def.angular_velocity, p - def.position),
+			color: def.color,
+		};
+		create_particle(self_, &particle_def, Some(group.clone()));
+	}
+
+	if def.flags.contains(B2particleGroupFlags::ELASTIC) && def.flags.contains(B2particleGroupFlags::RIGID) == false {
+		// Reach the diagonal neighbours of the grid, or the lattice would
+		// shear freely.
+		let range: f32 = b2_max(diameter, 1.5 * stride);
+		let count: usize = self_.m_positions.len();
+		for a in first..count {
+			for b in a + 1..count {
+				let rest_length: f32 = b2_distance_vec2(self_.m_positions[a], self_.m_positions[b]);
+				if rest_length < range {
+					self_.m_springs.push(B2particleSpring {
+						index_a: a,
+						index_b: b,
+						rest_length,
+					});
+				}
+			}
+		}
+	}
+
+	self_.m_group_list.push(group.clone());
+	update_group(self_, &group);
+	return group;
+}
+
+pub(crate) fn destroy_particle_group(self_: &mut B2particleSystem, group: B2particleGroupPtr) {
+	// From the back, so the particles taking the freed indices are never
+	// ones still to be destroyed.
+	for index in group_indices(self_, &group).into_iter().rev() {
+		destroy_particle(self_, index);
+	}
+	self_.m_group_list.retain(|g| Rc::ptr_eq(g, &group) == false);
+}
+
+fn group_indices(self_: &B2particleSystem, group: &B2particleGroupPtr) -> Vec<usize> {
+	let mut indices = Vec::new();
+	for (index, g) in self_.m_groups.iter().enumerate() {
+		if let Some(g) = g {
+			if Rc::ptr_eq(g, group) {
+				indices.push(index);
+			}
+		}
+	}
+	return indices;
+}
+
+fn is_rigid_pair(self_: &B2particleSystem, a: usize, b: usize) -> bool {
+	if let (Some(group_a), Some(group_b)) = (&self_.m_groups[a], &self_.m_groups[b]) {
+		return Rc::ptr_eq(group_a, group_b) && group_a.borrow().m_flags.contains(B2particleGroupFlags::RIGID);
+	}
+	return false;
+}
+
+fn update_group(self_: &B2particleSystem, group: &B2particleGroupPtr) {
+	let indices: Vec<usize> = group_indices(self_, group);
+	let mass: f32 = get_particle_mass(self_);
+
+	let mut g = group.borrow_mut();
+	g.m_count = indices.len();
+	g.m_mass = mass * indices.len() as f32;
+	g.m_center = B2vec2::zero();
+	g.m_linear_velocity = B2vec2::zero();
+	g.m_angular_velocity = 0.0;
+	if indices.is_empty() {
+		return;
+	}
+
+	let inv_count: f32 = 1.0 / indices.len() as f32;
+	for i in &indices {
+		g.m_center += inv_count * self_.m_positions[*i];
+		g.m_linear_velocity += inv_count * self_.m_velocities[*i];
+	}
+
+	let mut inertia: f32 = 0.0;
+	let mut angular_momentum: f32 = 0.0;
+	for i in &indices {
+		let r: B2vec2 = self_.m_positions[*i] - g.m_center;
+		inertia += b2_dot(r, r);
+		angular_momentum += b2_cross(r, self_.m_velocities[*i] - g.m_linear_velocity);
+	}
+	if inertia > B2_EPSILON {
+		g.m_angular_velocity = angular_momentum / inertia;
+	}
+}
+
+// Two neighbouring particles. The normal points from a to b.
+struct ParticleContact {
+	index_a: usize,
+	index_b: usize,
+	weight: f32,
+	normal: B2vec2,
+}
+
+// A particle near a fixture. The normal points from the particle to the
+// fixture, the mass is the effective mass of the particle and the body.
+struct ParticleBodyContact<D: UserDataType> {
+	index: usize,
+	body: BodyPtr<D>,
+	weight: f32,
+	normal: B2vec2,
+	mass: f32,
+}
+
+// A fixture child the particles may touch during the step.
+struct ParticleCandidate<D: UserDataType> {
+	fixture: FixturePtr<D>,
+	child_index: usize,
+	aabb: B2AABB,
+	xf: B2Transform,
+}
+
+// Step the particle systems after the soft bodies. The solver follows
+// LiquidFun: the contacts give every particle a weight, crowded particles
+// push each other apart with a pressure, and the velocities are limited so a
+// particle moves less than its diameter in a step. The positions are only
+// changed by the velocities.
+pub(crate) fn step_particle_systems<D: UserDataType>(self_: &mut B2world<D>, step: B2timeStep) {
+	if self_.m_particle_system_list.is_empty() || step.dt == 0.0 {
+		return;
+	}
+
+	for system in self_.m_particle_system_list.clone() {
+		step_particle_system(self_, &mut system.borrow_mut(), step);
+	}
+}
+
+fn step_particle_system<D: UserDataType>(world: &B2world<D>, self_: &mut B2particleSystem, step: B2timeStep) {
+	if self_.m_positions.is_empty() {
+		return;
+	}
+
+	let gravity: B2vec2 = step.dt * self_.m_def.gravity_scale * world.m_gravity;
+	for v in &mut self_.m_velocities {
+		*v += gravity;
+	}
+
+	let contacts: Vec<ParticleContact> = find_contacts(self_);
+	let candidates: Vec<ParticleCandidate<D>> = find_candidates(world, self_);
+	let body_contacts: Vec<ParticleBodyContact<D>> = find_body_contacts(self_, &candidates);
+
+	for w in &mut self_.m_weights {
+		*w = 0.0;
+	}
+	for c in &contacts {
+		self_.m_weights[c.index_a] += c.weight;
+		self_.m_weights[c.index_b] += c.weight;
+	}
+	for c in &body_contacts {
+		self_.m_weights[c.index] += c.weight;
+	}
+
+	solve_viscous(self_, &contacts, &body_contacts);
+	solve_pressure(self_, &contacts, &body_contacts, step);
+	solve_damping(self_, &contacts, &body_contacts, step);
+	solve_elastic(self_, step);
+	limit_velocity(self_, step);
+	solve_rigid(self_, step);
+	solve_collision(self_, &candidates, step);
+
+	for (p, v) in self_.m_positions.iter_mut().zip(&self_.m_velocities) {
+		*p += step.dt * *v;
+	}
+
+	for group in &self_.m_group_list {
+		update_group(self_, group);
+	}
+}
+
+// Find the neighbours with a spatial hash of cells as large as the diameter.
+// Particles of the same rigid group don't interact.
+fn find_contacts(self_: &B2particleSystem) -> Vec<ParticleContact> {
+	let diameter: f32 = 2.0 * self_.m_def.radius;
+	let inv_diameter: f32 = 1.0 / diameter;
+	let cell = |p: B2vec2| -> (i32, i32) {
+		return (f32::floor(p.x * inv_diameter) as i32, f32::floor(p.y * inv_diameter) as i32);
+	};
+
+	let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+	for (index, p) in self_.m_positions.iter().enumerate() {
+		grid.entry(cell(*p)).or_default().push(index);
+	}
+
+	let mut contacts = Vec::new();
+	for (a, pa) in self_.m_positions.iter().enumerate() {
+		let (x, y) = cell(*pa);
+		for dx in -1..=1 {
+			for dy in -1..=1 {
+				let neighbours = match grid.get(&(x + dx, y + dy)) {
+					Some(neighbours) => neighbours,
+					None => continue,
+				};
+				for &b in neighbours {
+					if b <= a {
+						continue;
+					}
+					let d: B2vec2 = self_.m_positions[b] - *pa;
+					let distance_squared: f32 = d.length_squared();
+					if distance_squared >= diameter * diameter || is_rigid_pair(self_, a, b) {
+						continue;
+					}
+					let distance: f32 = b2_sqrt(distance_squared);
+					contacts.push(ParticleContact {
+						index_a: a,
+						index_b: b,
+						weight: 1.0 - distance * inv_diameter,
+						normal: if distance > B2_EPSILON { (1.0 / distance) * d } else { B2vec2::new(1.0, 0.0) },
+					});
+				}
+			}
+		}
+	}
+	return contacts;
+}
+
+// Gather the fixture children near the particles from the broad-phase. The
+// velocities are limited to a diameter per step, so the box reaches far
+// enough for the moves of the step.
+fn find_candidates<D: UserDataType>(world: &B2world<D>, self_: &B2particleSystem) -> Vec<ParticleCandidate<D>> {
+	let mut aabb = B2AABB {
+		lower_bound: self_.m_positions[0],
+		upper_bound: self_.m_positions[0],
+	};
+	for p in &self_.m_positions {
+		aabb.lower_bound = b2_min_vec2(aabb.lower_bound, *p);
+		aabb.upper_bound = b2_max_vec2(aabb.upper_bound, *p);
+	}
+	let extension: f32 = 4.0 * self_.m_def.radius + B2_AABB_EXTENSION;
+	aabb.lower_bound -= B2vec2::new(extension, extension);
+	aabb.upper_bound += B2vec2::new(extension, extension);
+
+	let mut filter = B2queryFilter::<D>::from_filter(self_.m_def.filter);
+	filter.include_sensors = false;
+
+	let mut candidates = Vec::new();
+	let broad_phase_ptr = world.m_contact_manager.borrow().m_broad_phase.clone();
+	let broad_phase = broad_phase_ptr.borrow();
+	broad_phase.query(|proxy_id: i32| -> bool {
+		let proxy = broad_phase.get_user_data(proxy_id);
+		let fixture;
+		let child_index;
+		{
+			let proxy = proxy.as_ref().unwrap().borrow();
+			fixture = upgrade_opt(&proxy.fixture);
+			child_index = proxy.child_index as usize;
+		}
+
+		if filter.should_query(fixture.clone()) == false {
+			return true;
+		}
+
+		let xf: B2Transform = fixture.borrow().get_body().borrow().get_transform();
+		candidates.push(ParticleCandidate {
+			fixture,
+			child_index,
+			aabb: broad_phase.get_fat_aabb(proxy_id),
+			xf,
+		});
+		return true;
+	}, aabb);
+
+	return candidates;
+}
+
+fn particle_aabb(p: B2vec2, extension: f32) -> B2AABB {
+	let r = B2vec2::new(extension, extension);
+	return B2AABB {
+		lower_bound: p - r,
+		upper_bound: p + r,
+	};
+}
+
+// The particles closer to a fixture than the diameter. A particle inside the
+// core of a fixture has a negative distance, found by casting a ray from the
+// outside of the fixture back to the particle.
+fn find_body_contacts<D: UserDataType>(
+	self_: &B2particleSystem,
+	candidates: &[ParticleCandidate<D>],
+) -> Vec<ParticleBodyContact<D>> {
+	let mut contacts = Vec::new();
+	if candidates.is_empty() {
+		return contacts;
+	}
+
+	let diameter: f32 = 2.0 * self_.m_def.radius;
+	let inv_mass: f32 = 1.0 / get_particle_mass(self_);
+	let mut proxy_b = B2distanceProxy::default();
+	let mut cache = B2simplexCache::default();
+	let mut output = B2distanceOutput::default();
+
+	for (index, p) in self_.m_positions.iter().enumerate() {
+		let aabb: B2AABB = particle_aabb(*p, diameter);
+		for candidate in candidates {
+			if b2_test_overlap(candidate.aabb, aabb) == false {
+				continue;
+			}
+
+			let fixture = candidate.fixture.borrow();
+			let mut input = B2distanceInput::default();
+			private_distance::set_shape(&mut input.proxy_a, &*fixture.get_shape(), candidate.child_index);
+			proxy_b.set_vertices(&[*p], 0.0);
+			input.proxy_b = proxy_b.clone();
+			input.transform_a = candidate.xf;
+			input.transform_b.set_identity();
+			input.use_radii = false;
+			cache.count = 0;
+			b2_distance_fn(&mut output, &mut cache, &input);
+
+			let shape_radius: f32 = input.proxy_a.m_radius;
+			let distance: f32;
+			let normal: B2vec2;
+			if output.distance > B2_EPSILON {
+				normal = (1.0 / output.distance) * (output.point_a - output.point_b);
+				distance = output.distance - shape_radius;
+			} else {
+				// Points on edges and chains aren't inside anything.
+				if fixture.test_point(*p) == false {
+					continue;
+				}
+				match find_exit(&fixture, candidate, *p) {
+					Some((exit_normal, depth)) => {
+						normal = -exit_normal;
+						distance = -depth - shape_radius;
+					}
+					None => continue,
+				}
+			}
+
+			if distance >= diameter {
+				continue;
+			}
+
+			let body = fixture.get_body();
+			let mass: f32;
+			{
+				let b = body.borrow();
+				let rn: f32 = b2_cross(*p - b.get_world_center(), normal);
+				let inv_m: f32 = inv_mass + b.m_inv_mass + b.m_inv_i * rn * rn;
+				mass = if inv_m > 0.0 { 1.0 / inv_m } else { 0.0 };
+			}
+			contacts.push(ParticleBodyContact {
+				index,
+				body,
+				weight: 1.0 - distance / diameter,
+				normal,
+				mass,
+			});
+		}
+	}
+	return contacts;
+}
+
+// Find the nearest way out of a fixture for a point inside it, from rays cast
+// back at the point from around the fixture. Returns the outward normal and
+// the depth of the point.
+fn find_exit<D: UserDataType>(fixture: &B2fixture<D>, candidate: &ParticleCandidate<D>, p: B2vec2) -> Option<(B2vec2, f32)> {
+	const K_DIRECTION_COUNT: usize = 8;
+
+	let reach: f32 = (candidate.aabb.upper_bound - candidate.aabb.lower_bound).length();
+	let mut exit: Option<(B2vec2, f32)> = None;
+	for i in 0..K_DIRECTION_COUNT {
+		let angle: f32 = 2.0 * B2_PI * (i as f32) / (K_DIRECTION_COUNT as f32);
+		let input = B2rayCastInput {
+			p1: p + reach * B2vec2::new(f32::cos(angle), f32::sin(angle)),
+			p2: p,
+			max_fraction: 1.0,
+		};
+		let mut output = B2rayCastOutput::default();
+		if fixture.ray_cast(&mut output, &input, candidate.child_index as i32) == false {
+			continue;
+		}
+		let hit: B2vec2 = input.p1 + output.fraction * (input.p2 - input.p1);
+		let depth: f32 = b2_dot(hit - p, output.normal);
+		if exit.map_or(true, |(_, d)| depth < d) {
+			exit = Some((output.normal, depth));
+		}
+	}
+	return exit;
+}
+
+// Neighbours and fixtures drag the particles along.
+fn solve_viscous<D: UserDataType>(
+	self_: &mut B2particleSystem,
+	contacts: &[ParticleContact],
+	body_contacts: &[ParticleBodyContact<D>],
+) {
+	let viscous_strength: f32 = self_.m_def.viscous_strength;
+	let inv_mass: f32 = 1.0 / get_particle_mass(self_);
+
+	for c in body_contacts {
+		let p: B2vec2 = self_.m_positions[c.index];
+		let mut b = c.body.borrow_mut();
+		let v: B2vec2 = b.get_linear_velocity_from_world_point(p) - self_.m_velocities[c.index];
+		let f: B2vec2 = viscous_strength * c.mass * c.weight * v;
+		self_.m_velocities[c.index] += inv_mass * f;
+		b.apply_linear_impulse(-f, p, true);
+	}
+
+	for c in contacts {
+		let v: B2vec2 = self_.m_velocities[c.index_b] - self_.m_velocities[c.index_a];
+		let f: B2vec2 = viscous_strength * c.weight * v;
+		self_.m_velocities[c.index_a] += f;
+		self_.m_velocities[c.index_b] -= f;
+	}
+}
+
+// The pressure grows with the weight above the weight of a resting fluid.
+// Fixtures push like particles at the pressure of their weight.
+fn solve_pressure<D: UserDataType>(
+	self_: &mut B2particleSystem,
+	contacts: &[ParticleContact],
+	body_contacts: &[ParticleBodyContact<D>],
+	step: B2timeStep,
+) {
+	let density: f32 = self_.m_def.density;
+	let diameter: f32 = 2.0 * self_.m_def.radius;
+	let critical_velocity: f32 = diameter * step.inv_dt;
+	let critical_pressure: f32 = density * critical_velocity * critical_velocity;
+	let pressure_per_weight: f32 = self_.m_def.pressure_strength * critical_pressure;
+	let max_pressure: f32 = B2_MAX_PARTICLE_PRESSURE * critical_pressure;
+	let velocity_per_pressure: f32 = step.dt / (density * diameter);
+	let inv_mass: f32 = 1.0 / get_particle_mass(self_);
+
+	let pressures: Vec<f32> = self_
+		.m_weights
+		.iter()
+		.map(|w| b2_clamp(pressure_per_weight * b2_max(0.0, w - B2_MIN_PARTICLE_WEIGHT), 0.0, max_pressure))
+		.collect();
+
+	for c in body_contacts {
+		let p: B2vec2 = self_.m_positions[c.index];
+		let h: f32 = pressures[c.index] + pressure_per_weight * c.weight;
+		let f: B2vec2 = velocity_per_pressure * c.weight * c.mass * h * c.normal;
+		self_.m_velocities[c.index] -= inv_mass * f;
+		c.body.borrow_mut().apply_linear_impulse(f, p, true);
+	}
+
+	for c in contacts {
+		let h: f32 = pressures[c.index_a] + pressures[c.index_b];
+		let f: B2vec2 = velocity_per_pressure * c.weight * h * c.normal;
+		self_.m_velocities[c.index_a] -= f;
+		self_.m_velocities[c.index_b] += f;
+	}
+}
+
+// Remove the approaching normal velocity, more of it for fast particles so
+// they don't bounce.
+fn solve_damping<D: UserDataType>(
+	self_: &mut B2particleSystem,
+	contacts: &[ParticleContact],
+	body_contacts: &[ParticleBodyContact<D>],
+	step: B2timeStep,
+) {
+	let linear_damping: f32 = self_.m_def.damping_strength;
+	let quadratic_damping: f32 = step.dt / (2.0 * self_.m_def.radius);
+	let inv_mass: f32 = 1.0 / get_particle_mass(self_);
+
+	for c in body_contacts {
+		let p: B2vec2 = self_.m_positions[c.index];
+		let mut b = c.body.borrow_mut();
+		let v: B2vec2 = b.get_linear_velocity_from_world_point(p) - self_.m_velocities[c.index];
+		let vn: f32 = b2_dot(v, c.normal);
+		if vn < 0.0 {
+			let damping: f32 = b2_max(linear_damping * c.weight, b2_min(-quadratic_damping * vn, 0.5));
+			let f: B2vec2 = damping * c.mass * vn * c.normal;
+			self_.m_velocities[c.index] += inv_mass * f;
+			b.apply_linear_impulse(-f, p, true);
+		}
+	}
+
+	for c in contacts {
+		let v: B2vec2 = self_.m_velocities[c.index_b] - self_.m_velocities[c.index_a];
+		let vn: f32 = b2_dot(v, c.normal);
+		if vn < 0.0 {
+			let damping: f32 = b2_max(linear_damping * c.weight, b2_min(-quadratic_damping * vn, 0.5));
+			let f: B2vec2 = damping * vn * c.normal;
+			self_.m_velocities[c.index_a] += f;
+			self_.m_velocities[c.index_b] -= f;
+		}
+	}
+}
+
+// The springs of the elastic groups act on the positions at the end of the
+// step.
+fn solve_elastic(self_: &mut B2particleSystem, step: B2timeStep) {
+	let strength: f32 = step.inv_dt * self_.m_def.elastic_strength;
+	for s in &self_.m_springs {
+		let pa: B2vec2 = self_.m_positions[s.index_a] + step.dt * self_.m_velocities[s.index_a];
+		let pb: B2vec2 = self_.m_positions[s.index_b] + step.dt * self_.m_velocities[s.index_b];
+		let d: B2vec2 = pb - pa;
+		let length: f32 = d.length();
+		if length < B2_EPSILON {
+			continue;
+		}
+		let f: B2vec2 = (strength * (s.rest_length - length) / length) * d;
+		self_.m_velocities[s.index_a] -= f;
+		self_.m_velocities[s.index_b] += f;
+	}
+}
+
+// A particle moving less than its diameter in a step can't pass through its
+// neighbours.
+fn limit_velocity(self_: &mut B2particleSystem, step: B2timeStep) {
+	let critical_velocity: f32 = 2.0 * self_.m_def.radius * step.inv_dt;
+	let critical_velocity_squared: f32 = critical_velocity * critical_velocity;
+	for v in &mut self_.m_velocities {
+		let v2: f32 = b2_dot(*v, *v);
+		if v2 > critical_velocity_squared {
+			*v = (critical_velocity / b2_sqrt(v2)) * *v;
+		}
+	}
+}
+
+// Stop the particles moving into a fixture at the surface. The momentum they
+// lose goes into the body.
+fn solve_collision<D: UserDataType>(self_: &mut B2particleSystem, candidates: &[ParticleCandidate<D>], step: B2timeStep) {
+	if candidates.is_empty() {
+		return;
+	}
+
+	let diameter: f32 = 2.0 * self_.m_def.radius;
+	let mass: f32 = get_particle_mass(self_);
+	for (p, v) in self_.m_positions.iter().zip(self_.m_velocities.iter_mut()) {
+		let aabb: B2AABB = particle_aabb(*p, diameter);
+		for candidate in candidates {
+			if b2_test_overlap(candidate.aabb, aabb) == false {
+				continue;
+			}
+
+			let input = B2rayCastInput {
+				p1: *p,
+				p2: *p + step.dt * *v,
+				max_fraction: 1.0,
+			};
+			let mut output = B2rayCastOutput::default();
+			let fixture = candidate.fixture.borrow();
+			if fixture.ray_cast(&mut output, &input, candidate.child_index as i32) == false {
+				continue;
+			}
+
+			let hit: B2vec2 =
+				(1.0 - output.fraction) * input.p1 + output.fraction * input.p2 + B2_LINEAR_SLOP * output.normal;
+			let velocity: B2vec2 = step.inv_dt * (hit - *p);
+			let f: B2vec2 = mass * (*v - velocity);
+			*v = velocity;
+			fixture.get_body().borrow_mut().apply_linear_impulse(f, hit, true);
+		}
+	}
+}
+
+// The particles of a rigid group move to their rest shape, turned and moved
+// to best match where the step takes them. The collisions after this only
+// change the shape slightly, and it is restored in the next step.
+fn solve_rigid(self_: &mut B2particleSystem, step: B2timeStep) {
+	for group in &self_.m_group_list {
+		if group.borrow().m_flags.contains(B2particleGroupFlags::RIGID) == false {
+			continue;
+		}
+		let indices: Vec<usize> = group_indices(self_, group);
+		if indices.is_empty() {
+			continue;
+		}
+
+		let inv_count: f32 = 1.0 / indices.len() as f32;
+		let mut rest_center = B2vec2::zero();
+		let mut center = B2vec2::zero();
+		for i in &indices {
+			rest_center += inv_count * self_.m_rest_positions[*i];
+			center += inv_count * (self_.m_positions[*i] + step.dt * self_.m_velocities[*i]);
+		}
+
+		let mut sin: f32 = 0.0;
+		let mut cos: f32 = 0.0;
+		for i in &indices {
+			let r0: B2vec2 = self_.m_rest_positions[*i] - rest_center;
+			let r: B2vec2 = self_.m_positions[*i] + step.dt * self_.m_velocities[*i] - center;
+			sin += b2_cross(r0, r);
+			cos += b2_dot(r0, r);
+		}
+		let angle: f32 = b2_atan2(sin, cos);
+		let q = B2Rot::new(angle);
+
+		for i in &indices {
+			let target: B2vec2 = center + b2_mul_rot_by_vec2(q, self_.m_rest_positions[*i] - rest_center);
+			self_.m_velocities[*i] = step.inv_dt * (target - self_.m_positions[*i]);
+		}
+		group.borrow_mut().m_angle = angle;
+	}
+}
+
+pub(crate) fn draw(self_: &B2particleSystem, draw: &mut dyn B2drawTrait) {
+	let radius: f32 = self_.m_def.radius;
+	for (p, color) in self_.m_positions.iter().zip(&self_.m_colors) {
+		draw.draw_solid_circle(*p, radius, B2vec2::zero(), *color);
+	}
+
+	// The centers of the groups that keep their shape.
+	for group in &self_.m_group_list {
+		let g = group.borrow();
+		if g.m_count > 0 && g.m_flags.is_empty() == false {
+			draw.draw_point(g.m_center, 5.0, B2color::new(0.9, 0.9, 0.9));
+		}
+	}
+}
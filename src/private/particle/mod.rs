@@ -0,0 +1 @@
+pub mod b2_particle_system;
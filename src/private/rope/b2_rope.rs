@@ -1,5 +1,6 @@
 use crate::b2_rope::*;
 use crate::b2_draw::*;
+use crate::b2_fixture::*;
 use crate::b2_math::*;
 use crate::b2_common::*;
 
@@ -13,6 +14,10 @@ pub(crate) fn default() -> B2rope {
 		m_stretch_constraints: Vec::default(),
 		m_bend_constraints: Vec::default(),
 		m_tuning: Default::default(),
+		m_dt: 0.0,
+		m_radius: 0.0,
+		m_friction: 0.0,
+		m_filter: B2filter::default(),
 	}
 }
 
@@ -119,6 +124,9 @@ pub(crate) fn create(_self: &mut B2rope, def: &B2ropeDef) {
 	}
 
 	_self.m_gravity = def.gravity;
+	_self.m_radius = def.radius;
+	_self.m_friction = def.friction;
+	_self.m_filter = def.filter;
 
 	set_tuning(_self, &def.tuning);
 }
@@ -174,6 +182,21 @@ pub(crate) fn step(_self: &mut B2rope, dt: f32, iterations: i32, position: B2vec
 		return;
 	}
 
+	let gravity: B2vec2 = _self.m_gravity;
+	predict(_self, dt, gravity, Some(position));
+
+	for _i in 0..iterations {
+		solve_constraints(_self, dt);
+	}
+
+	finish_step(_self, dt);
+}
+
+// Apply gravity and damping and move the vertices to their predicted
+// positions. Vertices without mass follow the rope position when one is given
+// and stay in place otherwise.
+pub(crate) fn predict(_self: &mut B2rope, dt: f32, gravity: B2vec2, position: Option<B2vec2>) {
+	_self.m_dt = dt;
 	let inv_dt: f32 = 1.0 / dt;
 	let d: f32 = f32::exp(-dt * _self.m_tuning.damping);
 
@@ -181,9 +204,11 @@ pub(crate) fn step(_self: &mut B2rope, dt: f32, iterations: i32, position: B2vec
 	for p in &mut _self.m_positions {
 		if p.m_inv_masses > 0.0 {
 			p.m_vs *= d;
-			p.m_vs += dt * _self.m_gravity;
-		} else {
+			p.m_vs += dt * gravity;
+		} else if let Some(position) = position {
 			p.m_vs = inv_dt * (p.m_bind_positions + position - p.m_p0s);
+		} else {
+			p.m_vs.set_zero();
 		}
 	}
 
@@ -204,40 +229,45 @@ pub(crate) fn step(_self: &mut B2rope, dt: f32, iterations: i32, position: B2vec
 	for p in &mut _self.m_positions {
 		p.m_ps += dt * p.m_vs;
 	}
+}
 
-	// solve constraints
-	for _i in 0..iterations {
-		match _self.m_tuning.bending_model {
-			B2bendingModel::B2SpringAngleBendingModel => {
-				//nothing
-			}
-			B2bendingModel::B2PbdAngleBendingModel => {
-				solve_bend_pbd_angle(_self);
-			}
-			B2bendingModel::B2XpbdAngleBendingModel => {
-				solve_bend_xpbd_angle(_self, dt);
-			}
-			B2bendingModel::B2PbdDistanceBendingModel => {
-				solve_bend_pbd_distance(_self);
-			}
-			B2bendingModel::B2PbdHeightBendingModel => {
-				solve_bend_pbd_height(_self);
-			}
-			B2bendingModel::B2PbdTriangleBendingModel => {
-				solve_bend_pbd_triangle(_self);
-			}
-		}
-
-		match _self.m_tuning.stretching_model {
-			B2stretchingModel::B2PbdStretchingModel => {
-				solve_stretch_pbd(_self);
-			}
-			B2stretchingModel::B2XpbdStretchingModel => {
-				solve_stretch_xpbd(_self, dt);
-			}
+// One iteration of the bending and stretching constraints.
+pub(crate) fn solve_constraints(_self: &mut B2rope, dt: f32) {
+	match _self.m_tuning.bending_model {
+		B2bendingModel::B2SpringAngleBendingModel => {
+			//nothing
+		}
+		B2bendingModel::B2PbdAngleBendingModel => {
+			solve_bend_pbd_angle(_self);
+		}
+		B2bendingModel::B2XpbdAngleBendingModel => {
+			solve_bend_xpbd_angle(_self, dt);
+		}
+		B2bendingModel::B2PbdDistanceBendingModel => {
+			solve_bend_pbd_distance(_self);
+		}
+		B2bendingModel::B2PbdHeightBendingModel => {
+			solve_bend_pbd_height(_self);
+		}
+		B2bendingModel::B2PbdTriangleBendingModel => {
+			solve_bend_pbd_triangle(_self);
 		}
 	}
 
+	match _self.m_tuning.stretching_model {
+		B2stretchingModel::B2PbdStretchingModel => {
+			solve_stretch_pbd(_self);
+		}
+		B2stretchingModel::B2XpbdStretchingModel => {
+			solve_stretch_xpbd(_self, dt);
+		}
+	}
+}
+
+// Derive the velocities from the solved positions.
+pub(crate) fn finish_step(_self: &mut B2rope, dt: f32) {
+	let inv_dt: f32 = 1.0 / dt;
+
 	// Constrain velocity
 	for p in &mut _self.m_positions {
 		p.m_vs = inv_dt * (p.m_ps - p.m_p0s);
@@ -245,6 +275,178 @@ pub(crate) fn step(_self: &mut B2rope, dt: f32, iterations: i32, position: B2vec
 	}
 }
 
+// Rebuild the bending constraint j of a straight rest configuration, for
+// constraints created at runtime.
+fn make_straight_bend(_self: &B2rope, j: usize) -> B2ropeBend {
+	let mut c = B2ropeBend::default();
+	c.i1 = j as i32;
+	c.i2 = (j + 1) as i32;
+	c.i3 = (j + 2) as i32;
+	c.inv_mass1 = _self.m_positions[j].m_inv_masses;
+	c.inv_mass2 = _self.m_positions[j + 1].m_inv_masses;
+	c.inv_mass3 = _self.m_positions[j + 2].m_inv_masses;
+	c.l1 = _self.m_stretch_constraints[j].l;
+	c.l2 = _self.m_stretch_constraints[j + 1].l;
+
+	if c.l1 * c.l2 == 0.0 {
+		return c;
+	}
+
+	let j2: f32 = 1.0 / c.l1 + 1.0 / c.l2;
+	c.inv_effective_mass =
+		c.inv_mass1 / (c.l1 * c.l1) + c.inv_mass2 * j2 * j2 + c.inv_mass3 / (c.l2 * c.l2);
+	c.alpha1 = c.l2 / (c.l1 + c.l2);
+	c.alpha2 = c.l1 / (c.l1 + c.l2);
+	return c;
+}
+
+// Constraints always connect consecutive vertices, refresh their indices
+// after vertices were inserted or removed.
+fn update_indices(_self: &mut B2rope) {
+	_self.m_stretch_count = _self.m_stretch_constraints.len();
+	_self.m_bend_count = _self.m_bend_constraints.len();
+	for (i, c) in _self.m_stretch_constraints.iter_mut().enumerate() {
+		c.i1 = i as i32;
+		c.i2 = (i + 1) as i32;
+	}
+	for (i, c) in _self.m_bend_constraints.iter_mut().enumerate() {
+		c.i1 = i as i32;
+		c.i2 = (i + 1) as i32;
+		c.i3 = (i + 2) as i32;
+	}
+}
+
+pub(crate) fn cut(_self: &mut B2rope, segment: usize) -> B2rope {
+	b2_assert(segment < _self.m_stretch_constraints.len());
+
+	let mut rope: B2rope = default();
+	rope.m_position = _self.m_position;
+	rope.m_gravity = _self.m_gravity;
+	rope.m_tuning = _self.m_tuning;
+	rope.m_dt = _self.m_dt;
+	rope.m_radius = _self.m_radius;
+	rope.m_friction = _self.m_friction;
+	rope.m_filter = _self.m_filter;
+
+	// The second piece starts at the vertex after the cut segment.
+	let first: usize = segment + 1;
+	rope.m_positions = _self.m_positions.split_off(first);
+	rope.m_stretch_constraints = _self.m_stretch_constraints.split_off(first);
+	_self.m_stretch_constraints.pop();
+	rope.m_bend_constraints = _self.m_bend_constraints.split_off(b2_min(first, _self.m_bend_constraints.len()));
+	_self.m_bend_constraints.truncate(first.saturating_sub(2));
+
+	update_indices(_self);
+	update_indices(&mut rope);
+	return rope;
+}
+
+pub(crate) fn reel(_self: &mut B2rope, delta: f32) {
+	if _self.m_stretch_constraints.is_empty() {
+		return;
+	}
+
+	// New segments get the rest length of the segment after the winch.
+	let nominal: f32 = if _self.m_stretch_constraints.len() > 1 {
+		_self.m_stretch_constraints[1].l
+	} else {
+		_self.m_stretch_constraints[0].l
+	};
+	if nominal <= B2_LINEAR_SLOP {
+		return;
+	}
+
+	// The first rest length may go negative until segments are merged.
+	_self.m_stretch_constraints[0].l += delta;
+
+	// Reel out: split the first segment. The new vertex is placed on the
+	// current segment at the fraction of the rest length it takes, so the
+	// strain and the tension of the segment don't change.
+	while _self.m_stretch_constraints[0].l > 1.5 * nominal {
+		let rest: f32 = _self.m_stretch_constraints[0].l;
+		let fraction: f32 = nominal / rest;
+		let v0: B2ropePositions = _self.m_positions[0];
+		let v1: B2ropePositions = _self.m_positions[1];
+		let mass_source: &B2ropePositions = if v1.m_inv_masses > 0.0 { &v1 } else { &v0 };
+
+		let mut v = B2ropePositions::default();
+		v.m_ps = v1.m_ps + fraction * (v0.m_ps - v1.m_ps);
+		v.m_p0s = v1.m_p0s + fraction * (v0.m_p0s - v1.m_p0s);
+		v.m_vs = v1.m_vs + fraction * (v0.m_vs - v1.m_vs);
+		v.m_bind_positions = v1.m_bind_positions + fraction * (v0.m_bind_positions - v1.m_bind_positions);
+		v.m_inv_masses = mass_source.m_inv_masses;
+		_self.m_positions.insert(1, v);
+
+		let mut c: B2ropeStretch = _self.m_stretch_constraints[0];
+		c.l = nominal;
+		_self.m_stretch_constraints[0].l = rest - nominal;
+		_self.m_stretch_constraints.insert(1, c);
+
+		if _self.m_positions.len() > 2 {
+			_self.m_bend_constraints.insert(0, B2ropeBend::default());
+		}
+		refresh_winch(_self);
+	}
+
+	// Reel in: remove the vertex after the winch, merging the first two
+	// segments.
+	while _self.m_stretch_constraints.len() > 1 && _self.m_stretch_constraints[0].l < 0.5 * nominal {
+		let rest: f32 = _self.m_stretch_constraints[0].l + _self.m_stretch_constraints[1].l;
+		_self.m_positions.remove(1);
+		_self.m_stretch_constraints.remove(1);
+		_self.m_stretch_constraints[0].l = rest;
+
+		if _self.m_bend_constraints.is_empty() == false {
+			_self.m_bend_constraints.remove(0);
+		}
+		refresh_winch(_self);
+	}
+
+	if _self.m_stretch_constraints[0].l < B2_LINEAR_SLOP {
+		_self.m_stretch_constraints[0].l = B2_LINEAR_SLOP;
+		refresh_winch(_self);
+	}
+}
+
+// Refresh the constraints around the winch after the first vertices changed.
+fn refresh_winch(_self: &mut B2rope) {
+	update_indices(_self);
+
+	for i in 0..b2_min(2, _self.m_stretch_constraints.len()) {
+		let c: &mut B2ropeStretch = &mut _self.m_stretch_constraints[i];
+		c.inv_mass1 = _self.m_positions[i].m_inv_masses;
+		c.inv_mass2 = _self.m_positions[i + 1].m_inv_masses;
+	}
+	for j in 0..b2_min(2, _self.m_bend_constraints.len()) {
+		let lambda: f32 = _self.m_bend_constraints[j].lambda;
+		_self.m_bend_constraints[j] = make_straight_bend(_self, j);
+		_self.m_bend_constraints[j].lambda = lambda;
+	}
+
+	// Recompute the springs of the new constraints.
+	let tuning: B2ropeTuning = _self.m_tuning;
+	set_tuning(_self, &tuning);
+}
+
+pub(crate) fn get_length(_self: &B2rope) -> f32 {
+	return _self.m_stretch_constraints.iter().map(|c| c.l).sum();
+}
+
+pub(crate) fn get_current_length(_self: &B2rope) -> f32 {
+	return _self
+		.m_positions
+		.windows(2)
+		.map(|w| b2_distance_vec2(w[0].m_ps, w[1].m_ps))
+		.sum();
+}
+
+pub(crate) fn get_tension(_self: &B2rope, segment: usize) -> f32 {
+	if _self.m_dt == 0.0 {
+		return 0.0;
+	}
+	return -_self.m_stretch_constraints[segment].lambda / (_self.m_dt * _self.m_dt);
+}
+
 pub(crate) fn reset(_self: &mut B2rope, position: B2vec2) {
 	_self.m_position = position;
 
@@ -266,7 +468,7 @@ pub(crate) fn reset(_self: &mut B2rope, position: B2vec2) {
 pub(crate) fn solve_stretch_pbd(_self: &mut B2rope) {
 	let stiffness: f32 = _self.m_tuning.stretch_stiffness;
 
-	for c in &_self.m_stretch_constraints {
+	for c in &mut _self.m_stretch_constraints {
 		let mut p1: B2vec2 = _self.m_positions[c.i1 as usize].m_ps;
 		let mut p2: B2vec2 = _self.m_positions[c.i2 as usize].m_ps;
 
@@ -286,6 +488,9 @@ pub(crate) fn solve_stretch_pbd(_self: &mut B2rope) {
 
 		_self.m_positions[c.i1 as usize].m_ps = p1;
 		_self.m_positions[c.i2 as usize].m_ps = p2;
+
+		// Same units and sign as the XPBD multiplier, for the tension.
+		c.lambda += stiffness * (c.l - l) / sum;
 	}
 }
 
@@ -0,0 +1,467 @@
+use crate::b2_body::*;
+use crate::b2_collision::*;
+use crate::b2_distance::*;
+use crate::b2_fixture::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_rope::*;
+use crate::b2_time_step::*;
+use crate::b2_world::*;
+use crate::b2_world_callbacks::*;
+use crate::b2rs_common::*;
+
+use crate::private::collision::b2_distance as private_distance;
+use crate::private::rope::b2_rope as private;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub(crate) fn create_rope<D: UserDataType>(self_: &mut B2world<D>, def: &B2ropeDef) -> B2ropePtr<D> {
+	b2_assert(self_.is_locked() == false);
+
+	let mut rope = B2rope::default();
+	rope.create(def);
+	let rope = Rc::new(RefCell::new(B2worldRope {
+		m_rope: rope,
+		m_attachments: Vec::new(),
+	}));
+	self_.m_rope_list.push(rope.clone());
+	return rope;
+}
+
+pub(crate) fn destroy_rope<D: UserDataType>(self_: &mut B2world<D>, rope: B2ropePtr<D>) {
+	b2_assert(self_.is_locked() == false);
+	self_.m_rope_list.retain(|r| Rc::ptr_eq(r, &rope) == false);
+}
+
+pub(crate) fn cut_rope<D: UserDataType>(self_: &mut B2world<D>, rope: &B2ropePtr<D>, segment: usize) -> B2ropePtr<D> {
+	b2_assert(self_.is_locked() == false);
+
+	let mut rope = rope.borrow_mut();
+	let piece = rope.m_rope.cut(segment);
+
+	// The piece starts at the vertex after the cut segment.
+	let first: usize = segment + 1;
+	let mut attachments: Vec<B2ropeAttachment<D>> = Vec::new();
+	for mut a in std::mem::take(&mut rope.m_attachments) {
+		if a.vertex < first {
+			rope.m_attachments.push(a);
+		} else {
+			a.vertex -= first;
+			attachments.push(a);
+		}
+	}
+
+	let piece = Rc::new(RefCell::new(B2worldRope {
+		m_rope: piece,
+		m_attachments: attachments,
+	}));
+	self_.m_rope_list.push(piece.clone());
+	return piece;
+}
+
+pub(crate) fn reel<D: UserDataType>(self_: &mut B2worldRope<D>, delta: f32) {
+	let count: usize = self_.m_rope.get_vertex_count();
+	self_.m_rope.reel(delta);
+
+	// Reeling only inserts or removes vertices right after the first one.
+	let new_count: usize = self_.m_rope.get_vertex_count();
+	if new_count > count {
+		let inserted: usize = new_count - count;
+		for a in &mut self_.m_attachments {
+			if a.vertex >= 1 {
+				a.vertex += inserted;
+			}
+		}
+	} else if new_count < count {
+		let removed: usize = count - new_count;
+		self_.m_attachments.retain(|a| a.vertex == 0 || a.vertex > removed);
+		for a in &mut self_.m_attachments {
+			if a.vertex > removed {
+				a.vertex -= removed;
+			}
+		}
+	}
+}
+
+pub(crate) fn attach<D: UserDataType>(self_: &mut B2worldRope<D>, vertex: usize, body: BodyPtr<D>, anchor: B2vec2) {
+	b2_assert(vertex < self_.m_rope.get_vertex_count());
+	let local_anchor: B2vec2 = body.borrow().get_local_point(anchor);
+	self_.m_attachments.retain(|a| a.vertex != vertex);
+	self_.m_attachments.push(B2ropeAttachment {
+		vertex,
+		body,
+		local_anchor,
+	});
+}
+
+// Called when a body is destroyed.
+pub(crate) fn remove_attachments<D: UserDataType>(self_: &B2world<D>, body: &BodyPtr<D>) {
+	for rope in &self_.m_rope_list {
+		rope.borrow_mut().m_attachments.retain(|a| Rc::ptr_eq(&a.body, body) == false);
+	}
+}
+
+pub(crate) fn shift_origin<D: UserDataType>(self_: &B2world<D>, new_origin: B2vec2) {
+	for rope in &self_.m_rope_list {
+		let rope = &mut rope.borrow_mut().m_rope;
+		rope.m_position -= new_origin;
+		for p in &mut rope.m_positions {
+			p.m_ps -= new_origin;
+			p.m_p0s -= new_origin;
+		}
+	}
+}
+
+// The position state of an attached body while the rope is solved. Bodies
+// that can't be moved by the rope have no mass here.
+struct RopeBody<D: UserDataType> {
+	body: BodyPtr<D>,
+	local_center: B2vec2,
+	c0: B2vec2,
+	a0: f32,
+	c: B2vec2,
+	a: f32,
+	inv_mass: f32,
+	inv_i: f32,
+}
+
+// A fixture child the rope may touch during the step.
+struct RopeCandidate<D: UserDataType> {
+	fixture: FixturePtr<D>,
+	child_index: usize,
+	aabb: B2AABB,
+	xf: B2Transform,
+}
+
+// Step the ropes of the world after the bodies were solved. Attached vertices
+// are joined to their anchors by zero length constraints. The correction of
+// such a constraint is shared with the body by its effective mass at the
+// anchor, and the body velocity picks up the position change, so the rope
+// pulls the body. Collisions with fixtures push the vertices out and don't
+// affect the bodies.
+pub(crate) fn step_ropes<D: UserDataType>(self_: &mut B2world<D>, step: B2timeStep) {
+	if self_.m_rope_list.is_empty() || step.dt == 0.0 {
+		return;
+	}
+
+	let mut moved = false;
+	for rope in self_.m_rope_list.clone() {
+		let mut rope = rope.borrow_mut();
+		let rope = &mut *rope;
+		moved = step_rope(self_, &mut rope.m_rope, &rope.m_attachments, step, |_rope| {}, false) || moved;
+	}
+
+	if moved {
+		// Moved bodies may start touching other fixtures.
+		self_.m_contact_manager.borrow_mut().find_new_contacts();
+	}
+}
+
+// Step one rope with its attachments. solve_shape runs after the rope constraints in every
+// iteration, for the extra constraints of the soft bodies. With push_bodies the
+// collisions push the dynamic bodies back. Returns true if a body moved.
+pub(crate) fn step_rope<D: UserDataType, F: FnMut(&mut B2rope)>(
+	world: &B2world<D>,
+	rope: &mut B2rope,
+	attachments: &[B2ropeAttachment<D>],
+	step: B2timeStep,
+	mut solve_shape: F,
+	push_bodies: bool,
+) -> bool {
+	let dt: f32 = step.dt;
+	let iterations: i32 = b2_max(step.velocity_iterations, 1);
+
+	private::predict(rope, dt, world.m_gravity, None);
+
+	let mut bodies: Vec<RopeBody<D>> = Vec::new();
+	let mut body_indices: Vec<usize> = Vec::with_capacity(attachments.len());
+	for attachment in attachments {
+		if let Some(index) = bodies.iter().position(|b| Rc::ptr_eq(&b.body, &attachment.body)) {
+			body_indices.push(index);
+			continue;
+		}
+		let b = attachment.body.borrow();
+		let movable = b.get_type() == B2bodyType::B2DynamicBody && b.is_awake();
+		bodies.push(RopeBody {
+			body: attachment.body.clone(),
+			local_center: b.m_sweep.local_center,
+			c0: b.m_sweep.c,
+			a0: b.m_sweep.a,
+			c: b.m_sweep.c,
+			a: b.m_sweep.a,
+			inv_mass: if movable { b.m_inv_mass } else { 0.0 },
+			inv_i: if movable { b.m_inv_i } else { 0.0 },
+		});
+		body_indices.push(bodies.len() - 1);
+	}
+
+	let candidates: Vec<RopeCandidate<D>> = find_candidates(world, rope, attachments);
+
+	let mut corrections: Vec<f32> = vec![0.0; attachments.len()];
+	let mut pushes: Vec<RopePush<D>> = Vec::new();
+	let mut contacts: Vec<RopeContact> = vec![RopeContact::default(); rope.m_positions.len()];
+	for _i in 0..iterations {
+		private::solve_constraints(rope, dt);
+		solve_shape(rope);
+		solve_attachments(rope, attachments, &body_indices, &mut bodies, &mut corrections);
+		solve_collisions(rope, attachments, &candidates, &mut contacts, if push_bodies { Some(&mut pushes) } else { None });
+	}
+
+	private::finish_step(rope, dt);
+
+	// The momentum the collisions gave the vertices goes the other way into
+	// the bodies. A sleeping body wakes up when it is hit hard enough.
+	for push in &pushes {
+		let mut b = push.body.borrow_mut();
+		if b.get_type() != B2bodyType::B2DynamicBody {
+			continue;
+		}
+		let wake: bool = b.is_awake() || push.dp.length() > B2_LINEAR_SLOP;
+		b.apply_linear_impulse(-step.inv_dt * push.mass * push.dp, push.point, wake);
+	}
+
+	// Write the attached bodies back.
+	let mut moved = false;
+	for (index, correction) in body_indices.iter().zip(&corrections) {
+		// A sleeping body is held in place, but wakes up once the rope pulls
+		// on it.
+		let mut b = bodies[*index].body.borrow_mut();
+		if b.get_type() == B2bodyType::B2DynamicBody && b.is_awake() == false && *correction > B2_LINEAR_SLOP {
+			b.set_awake(true);
+		}
+	}
+	for rope_body in &bodies {
+		if rope_body.inv_mass == 0.0 && rope_body.inv_i == 0.0 {
+			continue;
+		}
+		let mut b = rope_body.body.borrow_mut();
+		b.m_linear_velocity += step.inv_dt * (rope_body.c - rope_body.c0);
+		b.m_angular_velocity += step.inv_dt * (rope_body.a - rope_body.a0);
+		b.m_sweep.c = rope_body.c;
+		b.m_sweep.a = rope_body.a;
+		b.synchronize_transform();
+		b.synchronize_fixtures_by_world(world);
+		moved = true;
+	}
+	return moved;
+}
+
+fn body_anchor<D: UserDataType>(rope_body: &RopeBody<D>, local_anchor: B2vec2) -> B2vec2 {
+	let q = B2Rot::new(rope_body.a);
+	return rope_body.c + b2_mul_rot_by_vec2(q, local_anchor - rope_body.local_center);
+}
+
+// The corrections are the constraint errors found in this iteration.
+fn solve_attachments<D: UserDataType>(
+	rope: &mut B2rope,
+	attachments: &[B2ropeAttachment<D>],
+	body_indices: &[usize],
+	bodies: &mut [RopeBody<D>],
+	corrections: &mut [f32],
+) {
+	for (i, attachment) in attachments.iter().enumerate() {
+		let rope_body: &mut RopeBody<D> = &mut bodies[body_indices[i]];
+		let p: &mut B2ropePositions = &mut rope.m_positions[attachment.vertex];
+
+		let anchor: B2vec2 = body_anchor(rope_body, attachment.local_anchor);
+		let mut n: B2vec2 = p.m_ps - anchor;
+		let length: f32 = n.normalize();
+		corrections[i] = length;
+		if length < B2_EPSILON {
+			continue;
+		}
+
+		let r: B2vec2 = anchor - rope_body.c;
+		let rn: f32 = b2_cross(r, n);
+		let sum: f32 = p.m_inv_masses + rope_body.inv_mass + rope_body.inv_i * rn * rn;
+		if sum == 0.0 {
+			continue;
+		}
+
+		let impulse: B2vec2 = (length / sum) * n;
+		p.m_ps -= p.m_inv_masses * impulse;
+		rope_body.c += rope_body.inv_mass * impulse;
+		rope_body.a += rope_body.inv_i * b2_cross(r, impulse);
+	}
+}
+
+// A collision correction of a vertex, to push the body back.
+struct RopePush<D: UserDataType> {
+	body: BodyPtr<D>,
+	point: B2vec2,
+	mass: f32,
+	dp: B2vec2,
+}
+
+// Gather the fixture children near the rope from the broad-phase. Fixtures of
+// the attached bodies are skipped, like jointed bodies that don't collide.
+fn find_candidates<D: UserDataType>(
+	world: &B2world<D>,
+	rope: &B2rope,
+	attachments: &[B2ropeAttachment<D>],
+) -> Vec<RopeCandidate<D>> {
+	let mut candidates = Vec::new();
+	if rope.m_positions.is_empty() {
+		return candidates;
+	}
+
+	let mut aabb = B2AABB {
+		lower_bound: rope.m_positions[0].m_ps,
+		upper_bound: rope.m_positions[0].m_ps,
+	};
+	for p in &rope.m_positions {
+		aabb.lower_bound = b2_min_vec2(aabb.lower_bound, b2_min_vec2(p.m_ps, p.m_p0s));
+		aabb.upper_bound = b2_max_vec2(aabb.upper_bound, b2_max_vec2(p.m_ps, p.m_p0s));
+	}
+	let r = B2vec2::new(rope.m_radius + B2_AABB_EXTENSION, rope.m_radius + B2_AABB_EXTENSION);
+	aabb.lower_bound -= r;
+	aabb.upper_bound += r;
+
+	let mut filter = B2queryFilter::<D>::from_filter(rope.m_filter);
+	filter.include_sensors = false;
+
+	let broad_phase_ptr = world.m_contact_manager.borrow().m_broad_phase.clone();
+	let broad_phase = broad_phase_ptr.borrow();
+	broad_phase.query(|proxy_id: i32| -> bool {
+		let proxy = broad_phase.get_user_data(proxy_id);
+		let fixture;
+		let child_index;
+		{
+			let proxy = proxy.as_ref().unwrap().borrow();
+			fixture = upgrade_opt(&proxy.fixture);
+			child_index = proxy.child_index as usize;
+		}
+
+		let body = fixture.borrow().get_body();
+		if attachments.iter().any(|a| Rc::ptr_eq(&a.body, &body)) {
+			return true;
+		}
+
+		if filter.should_query(fixture.clone()) == false {
+			return true;
+		}
+
+		let xf: B2Transform = body.borrow().get_transform();
+		candidates.push(RopeCandidate {
+			fixture,
+			child_index,
+			aabb: broad_phase.get_fat_aabb(proxy_id),
+			xf,
+		});
+		return true;
+	}, aabb);
+
+	return candidates;
+}
+
+// The friction state of a vertex over the iterations of a step.
+#[derive(Default, Clone, Copy)]
+struct RopeContact {
+	// The sum of the pushes out of the fixtures.
+	push: f32,
+	// The tangential motion removed so far.
+	friction: f32,
+}
+
+// Push the free vertices out of the fixtures. Friction removes the tangential
+// motion of the step, up to the friction times the pushes of the step so far.
+// Vertices resting on a fixture keep their friction in the iterations that
+// don't push them. The corrections are recorded in pushes when it is given.
+fn solve_collisions<D: UserDataType>(
+	rope: &mut B2rope,
+	attachments: &[B2ropeAttachment<D>],
+	candidates: &[RopeCandidate<D>],
+	contacts: &mut [RopeContact],
+	mut pushes: Option<&mut Vec<RopePush<D>>>,
+) {
+	if candidates.is_empty() {
+		return;
+	}
+
+	let radius: f32 = rope.m_radius;
+	let friction: f32 = rope.m_friction;
+	let mut proxy_b = B2distanceProxy::default();
+	let mut cache = B2simplexCache::default();
+	let mut output = B2distanceOutput::default();
+
+	for (vertex, p) in rope.m_positions.iter_mut().enumerate() {
+		if p.m_inv_masses == 0.0 || attachments.iter().any(|a| a.vertex == vertex) {
+			continue;
+		}
+		let contact: &mut RopeContact = &mut contacts[vertex];
+
+		for candidate in candidates {
+			let r = B2vec2::new(radius + B2_LINEAR_SLOP, radius + B2_LINEAR_SLOP);
+			let vertex_aabb = B2AABB {
+				lower_bound: p.m_ps - r,
+				upper_bound: p.m_ps + r,
+			};
+			if b2_test_overlap(candidate.aabb, vertex_aabb) == false {
+				continue;
+			}
+
+			let fixture = candidate.fixture.borrow();
+			let mut input = B2distanceInput::default();
+			private_distance::set_shape(&mut input.proxy_a, &*fixture.get_shape(), candidate.child_index);
+			proxy_b.set_vertices(&[p.m_ps], 0.0);
+			input.proxy_b = proxy_b.clone();
+			input.transform_a = candidate.xf;
+			input.transform_b.set_identity();
+			input.use_radii = false;
+			cache.count = 0;
+			b2_distance_fn(&mut output, &mut cache, &input);
+
+			let target: f32 = input.proxy_a.m_radius + radius;
+			if output.distance >= target + B2_LINEAR_SLOP {
+				continue;
+			}
+
+			let normal: B2vec2;
+			let push: f32;
+			if output.distance > B2_EPSILON {
+				normal = (1.0 / output.distance) * (output.point_b - output.point_a);
+				push = b2_max(target - output.distance, 0.0);
+			} else {
+				// The vertex is inside the core, find where it entered.
+				let ray_input = B2rayCastInput {
+					p1: p.m_p0s,
+					p2: p.m_ps,
+					max_fraction: 1.0,
+				};
+				let mut ray_output = B2rayCastOutput::default();
+				if fixture.ray_cast(&mut ray_output, &ray_input, candidate.child_index as i32) == false {
+					continue;
+				}
+				normal = ray_output.normal;
+				let hit: B2vec2 = p.m_p0s + ray_output.fraction * (p.m_ps - p.m_p0s);
+				push = b2_dot(normal, hit - p.m_ps) + target;
+			}
+
+			let p_start: B2vec2 = p.m_ps;
+			p.m_ps += push * normal;
+			contact.push += push;
+
+			let dp: B2vec2 = p.m_ps - p.m_p0s;
+			let tangent: B2vec2 = dp - b2_dot(dp, normal) * normal;
+			let tangent_length: f32 = tangent.length();
+			let max_friction: f32 = b2_max(friction * contact.push - contact.friction, 0.0);
+			if tangent_length <= max_friction {
+				p.m_ps -= tangent;
+				contact.friction += tangent_length;
+			} else {
+				p.m_ps -= (max_friction / tangent_length) * tangent;
+				contact.friction += max_friction;
+			}
+
+			if let Some(pushes) = pushes.as_mut() {
+				pushes.push(RopePush {
+					body: fixture.get_body(),
+					point: p.m_ps - radius * normal,
+					mass: 1.0 / p.m_inv_masses,
+					dp: p.m_ps - p_start,
+				});
+			}
+		}
+	}
+}
@@ -0,0 +1,302 @@
+use crate::b2_draw::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_rope::*;
+use crate::b2_soft_body::*;
+use crate::b2_time_step::*;
+use crate::b2_world::*;
+use crate::b2rs_common::UserDataType;
+
+use crate::private::rope::b2_rope as private_rope;
+use crate::private::rope::b2_rope_world;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub(crate) fn set_as_circle(self_: &mut B2softBodyDef, radius: f32, count: usize) {
+	b2_assert(count >= 3);
+	self_.vertices.clear();
+	for i in 0..count {
+		let angle: f32 = 2.0 * B2_PI * (i as f32) / (count as f32);
+		self_.vertices.push(B2vec2::new(radius * f32::cos(angle), radius * f32::sin(angle)));
+	}
+}
+
+// The signed area of a closed outline, positive when counter clockwise.
+fn signed_area(points: &[B2vec2]) -> f32 {
+	let count: usize = points.len();
+	let mut area: f32 = 0.0;
+	for i in 0..count {
+		let j: usize = if i + 1 < count { i + 1 } else { 0 };
+		area += b2_cross(points[i], points[j]);
+	}
+	return 0.5 * area;
+}
+
+pub(crate) fn create(def: &B2softBodyDef) -> B2softBody {
+	let count: usize = def.vertices.len();
+	b2_assert(count >= 3);
+
+	let mut vertices: Vec<B2vec2> = def.vertices.clone();
+	let mut area: f32 = signed_area(&vertices);
+	if area < 0.0 {
+		vertices.reverse();
+		area = -area;
+	}
+	b2_assert(area > B2_EPSILON);
+
+	let mass: f32 = def.density * area / (count as f32);
+	let inv_mass: f32 = if mass > 0.0 { 1.0 / mass } else { 0.0 };
+
+	let mut ring: B2rope = B2rope::default();
+	ring.m_position = def.position;
+	for v in &vertices {
+		ring.m_positions.push(B2ropePositions {
+			m_bind_positions: *v,
+			m_ps: def.position + *v,
+			m_p0s: def.position + *v,
+			m_vs: def.linear_velocity,
+			m_inv_masses: inv_mass,
+		});
+	}
+
+	// The last edge closes the ring.
+	for i in 0..count {
+		let j: usize = if i + 1 < count { i + 1 } else { 0 };
+		ring.m_stretch_constraints.push(B2ropeStretch {
+			i1: i as i32,
+			i2: j as i32,
+			inv_mass1: inv_mass,
+			inv_mass2: inv_mass,
+			l: b2_distance_vec2(vertices[i], vertices[j]),
+			..Default::default()
+		});
+	}
+
+	// The tuning sets up the edge springs. Without bending constraints the
+	// spring bending model does nothing.
+	let mut tuning = B2ropeTuning::default();
+	tuning.bending_model = B2bendingModel::B2SpringAngleBendingModel;
+	tuning.damping = def.damping;
+	if def.edge_hertz > 0.0 {
+		tuning.stretching_model = B2stretchingModel::B2XpbdStretchingModel;
+		tuning.stretch_hertz = def.edge_hertz;
+		tuning.stretch_damping = def.edge_damping_ratio;
+	} else {
+		tuning.stretching_model = B2stretchingModel::B2PbdStretchingModel;
+	}
+	private_rope::set_tuning(&mut ring, &tuning);
+
+	// The soft body solves the edges itself, so the rope has no constraints.
+	let edges: Vec<B2ropeStretch> = std::mem::take(&mut ring.m_stretch_constraints);
+	ring.m_stretch_count = 0;
+	ring.m_bend_count = 0;
+
+	ring.m_radius = def.radius;
+	ring.m_friction = def.friction;
+	ring.m_filter = def.filter;
+
+	return B2softBody {
+		m_ring: ring,
+		m_edges: edges,
+		m_rest_area: area,
+		m_pressure: def.pressure,
+		m_pressure_stiffness: b2_clamp(def.pressure_stiffness, 0.0, 1.0),
+	};
+}
+
+pub(crate) fn create_soft_body<D: UserDataType>(self_: &mut B2world<D>, def: &B2softBodyDef) -> B2softBodyPtr {
+	b2_assert(self_.is_locked() == false);
+
+	let soft_body = Rc::new(RefCell::new(create(def)));
+	self_.m_soft_body_list.push(soft_body.clone());
+	return soft_body;
+}
+
+pub(crate) fn destroy_soft_body<D: UserDataType>(self_: &mut B2world<D>, soft_body: B2softBodyPtr) {
+	b2_assert(self_.is_locked() == false);
+	self_.m_soft_body_list.retain(|s| Rc::ptr_eq(s, &soft_body) == false);
+}
+
+pub(crate) fn shift_origin<D: UserDataType>(self_: &B2world<D>, new_origin: B2vec2) {
+	for soft_body in &self_.m_soft_body_list {
+		let mut soft_body = soft_body.borrow_mut();
+		soft_body.m_ring.m_position -= new_origin;
+		for p in &mut soft_body.m_ring.m_positions {
+			p.m_ps -= new_origin;
+			p.m_p0s -= new_origin;
+		}
+	}
+}
+
+// Step the soft bodies like ropes, with the edges and the area constraint as
+// the shape constraints.
+pub(crate) fn step_soft_bodies<D: UserDataType>(self_: &mut B2world<D>, step: B2timeStep) {
+	if self_.m_soft_body_list.is_empty() || step.dt == 0.0 {
+		return;
+	}
+
+	for soft_body in self_.m_soft_body_list.clone() {
+		let mut soft_body = soft_body.borrow_mut();
+		let target_area: f32 = soft_body.m_pressure * soft_body.m_rest_area;
+		let stiffness: f32 = soft_body.m_pressure_stiffness;
+		let xpbd: bool = soft_body.m_ring.m_tuning.stretching_model == B2stretchingModel::B2XpbdStretchingModel;
+
+		let mut edges: Vec<B2ropeStretch> = std::mem::take(&mut soft_body.m_edges);
+		for c in &mut edges {
+			c.lambda = 0.0;
+		}
+		b2_rope_world::step_rope(
+			self_,
+			&mut soft_body.m_ring,
+			&[],
+			step,
+			|ring| {
+				solve_edges(ring, &mut edges, xpbd, step.dt);
+				solve_area(ring, target_area, stiffness);
+			},
+			true,
+		);
+		soft_body.m_edges = edges;
+	}
+}
+
+// The edges are solved like the rope stretching, but all from the same
+// positions and then averaged. Edges solved one after the other around the
+// ring make it turn and roll away on its own.
+fn solve_edges(ring: &mut B2rope, edges: &mut [B2ropeStretch], xpbd: bool, dt: f32) {
+	// Each vertex is shared by two edges.
+	const K_RELAXATION: f32 = 0.5;
+
+	let mut corrections: Vec<B2vec2> = vec![B2vec2::zero(); ring.m_positions.len()];
+	for c in edges {
+		let p1: &B2ropePositions = &ring.m_positions[c.i1 as usize];
+		let p2: &B2ropePositions = &ring.m_positions[c.i2 as usize];
+
+		let mut u: B2vec2 = p2.m_ps - p1.m_ps;
+		let l: f32 = u.normalize();
+
+		let sum: f32 = c.inv_mass1 + c.inv_mass2;
+		if sum == 0.0 {
+			continue;
+		}
+
+		let impulse: f32 = if xpbd {
+			let alpha: f32 = 1.0 / (c.spring * dt * dt);
+			let beta: f32 = dt * dt * c.damper;
+			let sigma: f32 = alpha * beta / dt;
+			let cdot: f32 = b2_dot(u, (p2.m_ps - p2.m_p0s) - (p1.m_ps - p1.m_p0s));
+			let b: f32 = (l - c.l) + alpha * c.lambda + sigma * cdot;
+			-K_RELAXATION * b / ((1.0 + sigma) * sum + alpha)
+		} else {
+			-K_RELAXATION * (l - c.l) / sum
+		};
+		c.lambda += impulse;
+
+		corrections[c.i1 as usize] -= (c.inv_mass1 * impulse) * u;
+		corrections[c.i2 as usize] += (c.inv_mass2 * impulse) * u;
+	}
+
+	for (p, correction) in ring.m_positions.iter_mut().zip(&corrections) {
+		p.m_ps += *correction;
+	}
+}
+
+// The area constraint C = area - target. The gradient for a vertex is half
+// the perpendicular of the chord between its neighbours, pointing out of the
+// outline.
+fn solve_area(ring: &mut B2rope, target_area: f32, stiffness: f32) {
+	let count: usize = ring.m_positions.len();
+	let c: f32 = get_area(ring) - target_area;
+
+	let mut gradients: Vec<B2vec2> = Vec::with_capacity(count);
+	let mut sum: f32 = 0.0;
+	for i in 0..count {
+		let prev: B2vec2 = ring.m_positions[if i > 0 { i - 1 } else { count - 1 }].m_ps;
+		let next: B2vec2 = ring.m_positions[if i + 1 < count { i + 1 } else { 0 }].m_ps;
+		let gradient: B2vec2 = 0.5 * b2_cross_vec_by_scalar(next - prev, 1.0);
+		sum += ring.m_positions[i].m_inv_masses * gradient.length_squared();
+		gradients.push(gradient);
+	}
+
+	if sum == 0.0 {
+		return;
+	}
+
+	let impulse: f32 = -stiffness * c / sum;
+	for (p, gradient) in ring.m_positions.iter_mut().zip(&gradients) {
+		p.m_ps += (p.m_inv_masses * impulse) * *gradient;
+	}
+}
+
+pub(crate) fn get_area(ring: &B2rope) -> f32 {
+	let points: Vec<B2vec2> = ring.m_positions.iter().map(|p| p.m_ps).collect();
+	return signed_area(&points);
+}
+
+pub(crate) fn get_center(self_: &B2softBody) -> B2vec2 {
+	let points: Vec<B2vec2> = self_.get_outline();
+	let count: usize = points.len();
+
+	// Triangles from the first point, like the polygon centroid.
+	let origin: B2vec2 = points[0];
+	let mut center = B2vec2::zero();
+	let mut area: f32 = 0.0;
+	for i in 1..count - 1 {
+		let e1: B2vec2 = points[i] - origin;
+		let e2: B2vec2 = points[i + 1] - origin;
+		let triangle_area: f32 = 0.5 * b2_cross(e1, e2);
+		area += triangle_area;
+		center += triangle_area * (1.0 / 3.0) * (e1 + e2);
+	}
+
+	if b2_abs(area) < B2_EPSILON {
+		// A collapsed outline, use the average.
+		let mut sum = B2vec2::zero();
+		for p in &points {
+			sum += *p;
+		}
+		return (1.0 / count as f32) * sum;
+	}
+	return origin + (1.0 / area) * center;
+}
+
+pub(crate) fn get_linear_velocity(self_: &B2softBody) -> B2vec2 {
+	let mut sum = B2vec2::zero();
+	for p in &self_.m_ring.m_positions {
+		sum += p.m_vs;
+	}
+	return (1.0 / self_.m_ring.m_positions.len() as f32) * sum;
+}
+
+pub(crate) fn get_mass(self_: &B2softBody) -> f32 {
+	let mut mass: f32 = 0.0;
+	for p in &self_.m_ring.m_positions {
+		if p.m_inv_masses > 0.0 {
+			mass += 1.0 / p.m_inv_masses;
+		}
+	}
+	return mass;
+}
+
+pub(crate) fn apply_linear_impulse(self_: &mut B2softBody, impulse: B2vec2) {
+	let mass: f32 = get_mass(self_);
+	if mass == 0.0 {
+		return;
+	}
+	let dv: B2vec2 = (1.0 / mass) * impulse;
+	for p in &mut self_.m_ring.m_positions {
+		if p.m_inv_masses > 0.0 {
+			p.m_vs += dv;
+		}
+	}
+}
+
+pub(crate) fn draw(self_: &B2softBody, draw: &mut dyn B2drawTrait) {
+	let c = B2color::new(0.9, 0.6, 0.6);
+	let points: Vec<B2vec2> = self_.get_outline();
+	draw.draw_polygon(&points, c);
+	for p in &points {
+		draw.draw_point(*p, 4.0, c);
+	}
+}
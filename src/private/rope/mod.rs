@@ -1 +1,3 @@
-pub mod b2_rope;
\ No newline at end of file
+pub mod b2_rope;
+pub mod b2_rope_world;
+pub mod b2_soft_body;
@@ -0,0 +1,210 @@
+use crate::b2_body::*;
+use crate::b2_fixture::*;
+use crate::b2_joint::*;
+use crate::b2_math::*;
+use crate::b2_common::*;
+use crate::b2_world::*;
+use crate::b2rs_common::*;
+use crate::joints::b2_revolute_joint::*;
+use crate::shapes::b2_polygon_shape::*;
+use crate::vehicle::b2_vehicle::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub(crate) fn new<D: UserDataType>(world: B2worldPtr<D>, def: &B2vehicleDef<D>) -> B2vehicle<D> {
+	b2_assert(def.chassis_shape.is_some());
+
+	let chassis_def = B2bodyDef {
+		body_type: B2bodyType::B2DynamicBody,
+		position: def.position,
+		angle: def.angle,
+		user_data: def.user_data.clone(),
+		..Default::default()
+	};
+	let chassis = B2world::create_body(world.clone(), &chassis_def);
+	B2body::create_fixture_by_shape(chassis.clone(), def.chassis_shape.clone().unwrap(), def.chassis_density);
+
+	let xf: B2Transform = chassis.borrow().get_transform();
+	let mut tires = Vec::<B2tire<D>>::with_capacity(def.tires.len());
+	for tire_def in &def.tires {
+		let body_def = B2bodyDef {
+			body_type: B2bodyType::B2DynamicBody,
+			position: b2_mul_transform_by_vec2(xf, tire_def.local_position),
+			angle: def.angle,
+			..Default::default()
+		};
+		let body = B2world::create_body(world.clone(), &body_def);
+
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(tire_def.half_width, tire_def.half_length);
+		let fixture = B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(shape)), tire_def.density);
+
+		// The limits hold the tire at the steering angle.
+		let mut joint_def = B2revoluteJointDef::default();
+		joint_def.base.body_a = Some(chassis.clone());
+		joint_def.base.body_b = Some(body.clone());
+		joint_def.local_anchor_a = tire_def.local_position;
+		joint_def.local_anchor_b = B2vec2::zero();
+		joint_def.enable_limit = true;
+		let joint = world.borrow_mut().create_joint(&B2JointDefEnum::RevoluteJoint(joint_def));
+
+		tires.push(B2tire {
+			m_def: tire_def.clone(),
+			m_body: body,
+			m_fixture: fixture,
+			m_joint: joint,
+			m_steer_angle: 0.0,
+			m_surface_friction: 1.0,
+		});
+	}
+
+	return B2vehicle {
+		m_world: Rc::downgrade(&world),
+		m_chassis: chassis,
+		m_tires: tires,
+		m_input: B2vehicleInput::default(),
+		m_surfaces: Vec::new(),
+	};
+}
+
+pub(crate) fn set_surface_friction<D: UserDataType>(
+	self_: &mut B2vehicle<D>,
+	fixture: FixturePtr<D>,
+	multiplier: f32,
+) {
+	b2_assert(multiplier >= 0.0);
+	clear_surface_friction(self_, fixture.clone());
+	self_.m_surfaces.push((Rc::downgrade(&fixture), multiplier));
+}
+
+pub(crate) fn clear_surface_friction<D: UserDataType>(self_: &mut B2vehicle<D>, fixture: FixturePtr<D>) {
+	let fixture = Rc::downgrade(&fixture);
+	// Also drop surfaces whose fixture was destroyed.
+	self_
+		.m_surfaces
+		.retain(|(surface, _)| surface.strong_count() > 0 && surface.ptr_eq(&fixture) == false);
+}
+
+// The highest multiplier of the registered surfaces touching the tire, 1 when there are none.
+fn find_surface_friction<D: UserDataType>(surfaces: &[(FixtureWeakPtr<D>, f32)], tire: &B2tire<D>) -> f32 {
+	let mut result: Option<f32> = None;
+	for (contact, _other) in tire.m_body.borrow().contacts() {
+		let contact = contact.borrow();
+		let base = contact.get_base();
+		let fixture_a = base.get_fixture_a();
+		let other = if Rc::ptr_eq(&fixture_a, &tire.m_fixture) {
+			base.get_fixture_b()
+		} else {
+			fixture_a
+		};
+		let other = Rc::downgrade(&other);
+		for (surface, multiplier) in surfaces {
+			if surface.ptr_eq(&other) {
+				result = Some(result.map_or(*multiplier, |m| b2_max(m, *multiplier)));
+			}
+		}
+	}
+	return result.unwrap_or(1.0);
+}
+
+// Evaluate the drive force curve at the speed ratio.
+fn drive_curve(curve: &[B2vec2], ratio: f32) -> f32 {
+	if curve.is_empty() {
+		return 1.0;
+	}
+	if ratio <= curve[0].x {
+		return curve[0].y;
+	}
+	for i in 1..curve.len() {
+		let p1 = curve[i - 1];
+		let p2 = curve[i];
+		if ratio <= p2.x {
+			let dx = p2.x - p1.x;
+			if dx <= B2_EPSILON {
+				return p2.y;
+			}
+			return p1.y + (ratio - p1.x) / dx * (p2.y - p1.y);
+		}
+	}
+	return curve[curve.len() - 1].y;
+}
+
+fn update_steering<D: UserDataType>(tire: &mut B2tire<D>, steering: f32, dt: f32) {
+	if tire.m_def.steerable == false {
+		return;
+	}
+
+	let target = b2_clamp(steering, -1.0, 1.0) * tire.m_def.max_steer_angle;
+	let max_turn = tire.m_def.steer_speed * dt;
+	tire.m_steer_angle += b2_clamp(target - tire.m_steer_angle, -max_turn, max_turn);
+
+	let mut joint = tire.m_joint.borrow_mut();
+	if let JointAsDerivedMut::ERevoluteJoint(ref mut revolute) = joint.as_derived_mut() {
+		revolute.set_limits(tire.m_steer_angle, tire.m_steer_angle);
+	}
+}
+
+fn update_tire<D: UserDataType>(tire: &B2tire<D>, input: &B2vehicleInput, dt: f32) {
+	let def = &tire.m_def;
+	let surface = tire.m_surface_friction;
+	let locked = input.handbrake && def.handbrake;
+
+	let mut body = tire.m_body.borrow_mut();
+	let forward = body.get_world_vector(B2vec2::new(0.0, 1.0));
+	let right = body.get_world_vector(B2vec2::new(1.0, 0.0));
+	let center = body.get_world_center();
+	let mass = body.get_mass();
+
+	// Cancel the sideways velocity up to the grip of the tire.
+	let mut grip = def.max_lateral_impulse * surface;
+	if locked {
+		grip *= def.handbrake_grip;
+	}
+	let mut impulse = -mass * b2_dot(right, body.get_linear_velocity()) * right;
+	let impulse_length = impulse.length();
+	if impulse_length > grip {
+		impulse *= grip / impulse_length;
+	}
+	body.apply_linear_impulse(impulse, center, true);
+
+	// Damp the spin.
+	let angular_impulse = -def.angular_drag * body.get_inertia() * body.get_angular_velocity();
+	body.apply_angular_impulse(angular_impulse, true);
+
+	// Rolling resistance.
+	let speed = b2_dot(forward, body.get_linear_velocity());
+	body.apply_force(-def.rolling_drag * speed * forward, center, true);
+
+	// Drive.
+	let throttle = b2_clamp(input.throttle, -1.0, 1.0);
+	if def.driven && throttle != 0.0 {
+		let top_speed = if throttle > 0.0 { def.max_forward_speed } else { def.max_backward_speed };
+		// Only the speed in the throttle direction counts towards the top speed.
+		let ratio = if top_speed > 0.0 { b2_max(speed * throttle.signum(), 0.0) / top_speed } else { 1.0 };
+		if ratio < 1.0 {
+			let force = throttle * def.max_drive_force * drive_curve(&def.drive_curve, ratio) * surface;
+			body.apply_force(force * forward, center, true);
+		}
+	}
+
+	// Brake, never reversing the tire.
+	let brake = if locked { 1.0 } else { b2_clamp(input.brake, 0.0, 1.0) };
+	if brake > 0.0 && speed != 0.0 {
+		let brake_impulse = b2_min(brake * def.brake_force * surface * dt, mass * b2_abs(speed));
+		body.apply_linear_impulse(-speed.signum() * brake_impulse * forward, center, true);
+	}
+}
+
+pub(crate) fn update<D: UserDataType>(self_: &mut B2vehicle<D>, dt: f32) {
+	b2_assert(upgrade(&self_.m_world).borrow().is_locked() == false);
+
+	self_.m_surfaces.retain(|(surface, _)| surface.strong_count() > 0);
+
+	let input = self_.m_input;
+	for tire in &mut self_.m_tires {
+		tire.m_surface_friction = find_surface_friction(&self_.m_surfaces, tire);
+		update_steering(tire, input.steering, dt);
+		update_tire(tire, &input, dt);
+	}
+}
@@ -1,4 +1,5 @@
 pub mod serialize_b2_fixture;
 pub mod serialize_b2_joint;
 pub mod serialize_b2_body;
+pub mod serialize_b2_rope;
 pub mod serialize_b2_world;
\ No newline at end of file
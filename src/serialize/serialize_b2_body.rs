@@ -11,6 +11,7 @@ use std::rc::{Rc};
 use std::cell::RefCell;
 
 use crate::b2_body::*;
+use crate::b2_math::*;
 use crate::b2rs_common::UserDataType;
 use crate::b2_joint::*;
 use crate::b2_world::*;
@@ -19,13 +20,18 @@ use crate::joints::serialize::serialize_b2_distance_joint::*;
 use crate::joints::serialize::serialize_b2_friction_joint::*;
 use crate::joints::serialize::serialize_b2_gear_joint::*;
 use crate::joints::serialize::serialize_b2_motor_joint::*;
+use crate::joints::serialize::serialize_b2_mouse_joint::*;
 use crate::joints::serialize::serialize_b2_prismatic_joint::*;
 use crate::joints::serialize::serialize_b2_pulley_joint::*;
 use crate::joints::serialize::serialize_b2_revolute_joint::*;
 use crate::joints::serialize::serialize_b2_weld_joint::*;
 use crate::joints::serialize::serialize_b2_wheel_joint::*;
+use crate::joints::serialize::serialize_b2_angle_joint::*;
+use crate::joints::serialize::serialize_b2_filter_joint::*;
+use crate::joints::serialize::serialize_b2_custom_joint::*;
 
 use crate::serialize::serialize_b2_fixture::*;
+use crate::shapes::serialize_b2_custom_shape::*;
 
 use strum::VariantNames;
 use strum_macros::EnumVariantNames;
@@ -47,6 +53,10 @@ impl<D: UserDataType> B2bodyToDef<D> for B2body<D> {
             allow_sleep: self.m_flags.contains(BodyFlags::E_AUTO_SLEEP_FLAG),
             awake: self.m_flags.contains(BodyFlags::E_AWAKE_FLAG),
             fixed_rotation: self.m_flags.contains(BodyFlags::E_FIXED_ROTATION_FLAG),
+            fixed_translation_x: self.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_X_FLAG),
+            fixed_translation_y: self.m_flags.contains(BodyFlags::E_FIXED_TRANSLATION_Y_FLAG),
+            max_linear_speed: self.m_max_linear_speed,
+            max_angular_speed: self.m_max_angular_speed,
             bullet: self.m_flags.contains(BodyFlags::E_BULLET_FLAG),
             enabled: self.m_flags.contains(BodyFlags::E_ENABLED_FLAG),
             gravity_scale: self.m_gravity_scale,
@@ -64,14 +74,59 @@ impl<D: UserDataType> Serialize for B2body<D> {
 
         let definition = self.get_def();
         state.serialize_field("m_definition", &definition)?;
-        state.serialize_field("m_fixture_list", &self.m_fixture_list)?;
+        state.serialize_field("m_fixture_list", &B2fixturesArrayContext {
+            m_fixtures: &self.m_fixture_list,
+        })?;
         state.end()
     }
 }
 
+// The body definition of version 0 saves, from before the motion limits.
+#[derive(Deserialize)]
+struct B2bodyDefV0<D: UserDataType> {
+    body_type: B2bodyType,
+    position: B2vec2,
+    angle: f32,
+    linear_velocity: B2vec2,
+    angular_velocity: f32,
+    linear_damping: f32,
+    angular_damping: f32,
+    allow_sleep: bool,
+    awake: bool,
+    fixed_rotation: bool,
+    bullet: bool,
+    enabled: bool,
+    user_data: Option<D::Body>,
+    gravity_scale: f32,
+}
+
+impl<D: UserDataType> B2bodyDefV0<D> {
+    fn into_def(self) -> B2bodyDef<D> {
+        return B2bodyDef {
+            body_type: self.body_type,
+            position: self.position,
+            angle: self.angle,
+            linear_velocity: self.linear_velocity,
+            angular_velocity: self.angular_velocity,
+            linear_damping: self.linear_damping,
+            angular_damping: self.angular_damping,
+            allow_sleep: self.allow_sleep,
+            awake: self.awake,
+            fixed_rotation: self.fixed_rotation,
+            bullet: self.bullet,
+            enabled: self.enabled,
+            user_data: self.user_data,
+            gravity_scale: self.gravity_scale,
+            ..Default::default()
+        };
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct B2bodyDefinitionVisitorContext<D: UserDataType> {
     pub(crate) m_world: B2worldPtr<D>,
+    pub(crate) m_version: u32,
+    pub(crate) m_custom_shape_factory: Option<B2customShapeFactoryPtr>,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2bodyDefinitionVisitorContext<U> {
@@ -105,13 +160,20 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2bodyDefinitionVisitorConte
             {
                 let world = self.0.m_world.clone();
 
-                let def: B2bodyDef<U> = seq
-                    .next_element()?
-                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let def: B2bodyDef<U> = if self.0.m_version == 0 {
+                    seq.next_element::<B2bodyDefV0<U>>()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?
+                        .into_def()
+                } else {
+                    seq.next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?
+                };
                 let body = Some(B2world::create_body(world.clone(), &def));
 
                 seq.next_element_seed(B2fixtureListVisitorContext {
                     body: Rc::downgrade(&body.clone().unwrap()),
+                    m_version: self.0.m_version,
+                    m_custom_shape_factory: self.0.m_custom_shape_factory.clone(),
                 })?
                 .ok_or_else(|| de::Error::invalid_length(0, &self))?;
 
@@ -123,7 +185,7 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2bodyDefinitionVisitorConte
                 V: MapAccess<'de>,
             {
                 let mut body = None;
-                let world = self.0.m_world;
+                let world = self.0.m_world.clone();
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::m_definition => {
@@ -133,6 +195,8 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2bodyDefinitionVisitorConte
                         Field::m_fixture_list => {
                             map.next_value_seed(B2fixtureListVisitorContext {
                                 body: Rc::downgrade(&body.clone().unwrap()),
+                                m_version: self.0.m_version,
+                                m_custom_shape_factory: self.0.m_custom_shape_factory.clone(),
                             })?;
                         }
                     }
@@ -147,6 +211,8 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2bodyDefinitionVisitorConte
 
 pub(crate) struct B2bodyListContext<D: UserDataType> {
     pub(crate) m_world: B2worldPtr<D>,
+    pub(crate) m_version: u32,
+    pub(crate) m_custom_shape_factory: Option<B2customShapeFactoryPtr>,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2bodyListContext<U> {
@@ -171,6 +237,8 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2bodyListContext<U> {
             {
                 let context = B2bodyDefinitionVisitorContext {
                     m_world: self.0.m_world.clone(),
+                    m_version: self.0.m_version,
+                    m_custom_shape_factory: self.0.m_custom_shape_factory.clone(),
                 };
                 while let Some(_elem) = seq.next_element_seed(context.clone())? {}
                 Ok(())
@@ -185,8 +253,10 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2bodyListContext<U> {
 #[derive(Clone)]
 pub(crate) struct B2jointDefinitionVisitorContext<D: UserDataType> {
     pub(crate) m_world: B2worldPtr<D>,
+    pub(crate) m_version: u32,
     pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
     pub(crate) m_all_joints: Rc<RefCell<Vec<B2jointPtr<D>>>>,
+    pub(crate) m_custom_joint_factory: Option<B2customJointFactoryPtr<D>>,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorContext<U> {
@@ -255,7 +325,12 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorCont
                         world.borrow_mut().create_joint(&B2JointDefEnum::GearJoint(def));
                     }
                     B2jointType::EMouseJoint => {
-                        panic!();
+                        let state = seq.next_element_seed(B2mouseJointDefContext {
+                            m_body_array: self.0.m_body_array.clone(),
+                        })?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+                        create_mouse_joint(&world, state);
                     }
                     B2jointType::EMotorJoint => {
                         let def = seq.next_element_seed(B2motorJointDefContext {
@@ -276,6 +351,7 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorCont
                     B2jointType::ERevoluteJoint => {
                         let def = seq.next_element_seed(B2revoluteJointDefContext {
                             m_body_array: self.0.m_body_array.clone(),
+                            m_version: self.0.m_version,
                         })?
                         .ok_or_else(|| de::Error::invalid_length(0, &self))?;
 
@@ -284,6 +360,7 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorCont
                     B2jointType::EPrismaticJoint => {
                         let def = seq.next_element_seed(B2prismaticJointDefContext {
                             m_body_array: self.0.m_body_array.clone(),
+                            m_version: self.0.m_version,
                         })?
                         .ok_or_else(|| de::Error::invalid_length(0, &self))?;
 
@@ -300,11 +377,37 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorCont
                     B2jointType::EWheelJoint => {
                         let def = seq.next_element_seed(B2wheelJointDefContext {
                             m_body_array: self.0.m_body_array.clone(),
+                            m_version: self.0.m_version,
                         })?
                         .ok_or_else(|| de::Error::invalid_length(0, &self))?;
 
                         world.borrow_mut().create_joint(&B2JointDefEnum::WheelJoint(def));
                     }
+                    B2jointType::EAngleJoint => {
+                        let def = seq.next_element_seed(B2angleJointDefContext {
+                            m_body_array: self.0.m_body_array.clone(),
+                        })?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+                        world.borrow_mut().create_joint(&B2JointDefEnum::AngleJoint(def));
+                    }
+                    B2jointType::EFilterJoint => {
+                        let def = seq.next_element_seed(B2filterJointDefContext {
+                            m_body_array: self.0.m_body_array.clone(),
+                        })?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+                        world.borrow_mut().create_joint(&B2JointDefEnum::FilterJoint(def));
+                    }
+                    B2jointType::ECustomJoint => {
+                        let def = seq.next_element_seed(B2customJointDefContext {
+                            m_body_array: self.0.m_body_array.clone(),
+                            m_custom_joint_factory: self.0.m_custom_joint_factory.clone(),
+                        })?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+                        world.borrow_mut().create_joint(&B2JointDefEnum::CustomJoint(def));
+                    }
                 }
 
                 Ok(())
@@ -348,7 +451,12 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorCont
                                     let gear_joint = world.borrow_mut().create_joint(&B2JointDefEnum::GearJoint(def));
                                     all_joints.borrow_mut().push(gear_joint);
                                 }
-                                B2jointType::EMouseJoint => {}
+                                B2jointType::EMouseJoint => {
+                                    let state = map.next_value_seed(B2mouseJointDefContext {
+                                        m_body_array: self.0.m_body_array.clone(),
+                                    })?;
+                                    create_mouse_joint(&world, state);
+                                }
                                 B2jointType::EMotorJoint => {
                                     let def = map.next_value_seed(B2motorJointDefContext {
                                         m_body_array: self.0.m_body_array.clone(),
@@ -364,12 +472,14 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorCont
                                 B2jointType::ERevoluteJoint => {
                                     let def = map.next_value_seed(B2revoluteJointDefContext {
                                         m_body_array: self.0.m_body_array.clone(),
+                                        m_version: self.0.m_version,
                                     })?;
                                     world.borrow_mut().create_joint(&B2JointDefEnum::RevoluteJoint(def));
                                 }
                                 B2jointType::EPrismaticJoint => {
                                     let def = map.next_value_seed(B2prismaticJointDefContext {
                                         m_body_array: self.0.m_body_array.clone(),
+                                        m_version: self.0.m_version,
                                     })?;
                                     world.borrow_mut().create_joint(&B2JointDefEnum::PrismaticJoint(def));
                                 }
@@ -382,9 +492,29 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorCont
                                 B2jointType::EWheelJoint => {
                                     let def = map.next_value_seed(B2wheelJointDefContext {
                                         m_body_array: self.0.m_body_array.clone(),
+                                        m_version: self.0.m_version,
                                     })?;
                                     world.borrow_mut().create_joint(&B2JointDefEnum::WheelJoint(def));
                                 }
+                                B2jointType::EAngleJoint => {
+                                    let def = map.next_value_seed(B2angleJointDefContext {
+                                        m_body_array: self.0.m_body_array.clone(),
+                                    })?;
+                                    world.borrow_mut().create_joint(&B2JointDefEnum::AngleJoint(def));
+                                }
+                                B2jointType::EFilterJoint => {
+                                    let def = map.next_value_seed(B2filterJointDefContext {
+                                        m_body_array: self.0.m_body_array.clone(),
+                                    })?;
+                                    world.borrow_mut().create_joint(&B2JointDefEnum::FilterJoint(def));
+                                }
+                                B2jointType::ECustomJoint => {
+                                    let def = map.next_value_seed(B2customJointDefContext {
+                                        m_body_array: self.0.m_body_array.clone(),
+                                        m_custom_joint_factory: self.0.m_custom_joint_factory.clone(),
+                                    })?;
+                                    world.borrow_mut().create_joint(&B2JointDefEnum::CustomJoint(def));
+                                }
                             }
                             
                         }
@@ -402,8 +532,10 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointDefinitionVisitorCont
 
 pub(crate) struct B2jointListContext<D: UserDataType> {
     pub(crate) m_world: B2worldPtr<D>,
+    pub(crate) m_version: u32,
     pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
     pub(crate) m_all_joints: Rc<RefCell<Vec<B2jointPtr<D>>>>,
+    pub(crate) m_custom_joint_factory: Option<B2customJointFactoryPtr<D>>,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointListContext<U> {
@@ -428,8 +560,10 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2jointListContext<U> {
             {
                 let context = B2jointDefinitionVisitorContext {
                     m_world: self.0.m_world.clone(),
+                    m_version: self.0.m_version,
                     m_body_array: self.0.m_body_array.clone(),
                     m_all_joints: self.0.m_all_joints.clone(),
+                    m_custom_joint_factory: self.0.m_custom_joint_factory.clone(),
                 };
                 while let Some(_elem) = seq.next_element_seed(context.clone())? {}
                 Ok(())
@@ -2,8 +2,11 @@ use crate::b2_fixture::*;
 use crate::b2_body::*;
 use crate::b2rs_common::UserDataType;
 use crate::b2_shape::*;
+use crate::b2rs_linked_list::*;
 use crate::shapes::b2_chain_shape::*;
 use crate::shapes::b2_circle_shape::*;
+use crate::shapes::b2_custom_shape::*;
+use crate::shapes::serialize_b2_custom_shape::*;
 use crate::shapes::b2_edge_shape::*;
 use crate::shapes::b2_polygon_shape::*;
 use crate::shapes::b2rs_to_derived_shape::*;
@@ -13,7 +16,7 @@ use std::rc::{Rc};
 use serde::de;
 use serde::de::{DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde::{
-	ser::{SerializeStruct},
+	ser::{self, SerializeSeq, SerializeStruct},
 	Deserialize, Serialize, Serializer,
 };
 use std::fmt;
@@ -25,7 +28,7 @@ impl<D: UserDataType> Serialize for B2fixture<D> {
 	where
 		S: Serializer,
 	{
-		let mut state = serializer.serialize_struct("B2fixture", 7)?;
+		let mut state = serializer.serialize_struct("B2fixture", 9)?;
 		state.serialize_field("m_friction", &self.m_friction)?;
 		state.serialize_field("m_restitution", &self.m_restitution)?;
 		state.serialize_field("m_restitution_threshold", &self.m_restitution_threshold)?;
@@ -46,13 +49,66 @@ impl<D: UserDataType> Serialize for B2fixture<D> {
 			ShapeAsDerived::AsChain(ref shape) => {
 				state.serialize_field("m_shape", &shape)?;
 			}
+			ShapeAsDerived::AsCustom(ref shape) => {
+				let data = shape.get_shape().serialize_data().ok_or_else(|| {
+					ser::Error::custom("custom shapes without serialize_data can't be serialized")
+				})?;
+				state.serialize_field("m_shape", &data)?;
+			}
+		}
+		state.serialize_field("m_one_way_direction", &self.m_one_way_direction)?;
+		state.serialize_field("m_toughness", &self.m_toughness)?;
+		state.end()
+	}
+}
+
+fn is_saved<D: UserDataType>(fixture: &B2fixture<D>) -> bool {
+	if let ShapeAsDerived::AsCustom(shape) = fixture.m_shape.as_ref().unwrap().as_derived() {
+		// Custom shapes without a save hook are left out, like custom joints.
+		return shape.get_shape().serialize_data().is_some();
+	}
+	return true;
+}
+
+pub(crate) struct B2fixturesArrayContext<'a, D: UserDataType> {
+	pub(crate) m_fixtures: &'a LinkedList<B2fixture<D>>,
+}
+
+impl<'a, D: UserDataType> Serialize for B2fixturesArrayContext<'a, D> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let fixtures: Vec<FixturePtr<D>> = self
+			.m_fixtures
+			.iter()
+			.filter(|f| is_saved(&f.borrow()))
+			.collect();
+		let mut state = serializer.serialize_seq(Some(fixtures.len()))?;
+		for f in fixtures {
+			state.serialize_element(&*f.borrow())?;
 		}
 		state.end()
 	}
 }
 
+fn create_custom_shape<E: de::Error>(
+	factory: &Option<B2customShapeFactoryPtr>,
+	data: &str,
+) -> Result<B2customShape, E> {
+	let factory = factory
+		.as_ref()
+		.ok_or_else(|| de::Error::custom("a custom shape factory is needed to load custom shapes"))?;
+	let shape = factory
+		.create_custom_shape(data)
+		.ok_or_else(|| de::Error::custom("the custom shape factory failed"))?;
+	return Ok(B2customShape::new(shape));
+}
+
 struct B2fixtureVisitorContext<D: UserDataType> {
 	pub(crate) body: BodyWeakPtr<D>,
+	pub(crate) m_version: u32,
+	pub(crate) m_custom_shape_factory: Option<B2customShapeFactoryPtr>,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2fixtureVisitorContext<U> {
@@ -75,6 +131,8 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2fixtureVisitorContext<U> {
 			m_filter,
 			m_shape_type,
 			m_shape,
+			m_one_way_direction,
+			m_toughness,
 		}
 		struct B2fixtureVisitor<D: UserDataType>(B2fixtureVisitorContext<D>);
 
@@ -145,8 +203,28 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2fixtureVisitorContext<U> {
 							.ok_or_else(|| de::Error::invalid_length(0, &self))?;
 						definition.shape = Some(Rc::new(RefCell::new(shape)));
 					}
-					_ => panic!(),
+					B2ShapeType::ECustom => {
+						let data: String = seq
+							.next_element()?
+							.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+						let shape = create_custom_shape(&self.0.m_custom_shape_factory, &data)?;
+						definition.shape = Some(Rc::new(RefCell::new(shape)));
+					}
+					B2ShapeType::ETypeCount => {
+						return Err(de::Error::custom("invalid shape type"));
+					}
 				}
+
+				// Version 0 fixtures have no one-way direction and no toughness.
+				if self.0.m_version >= 1 {
+					definition.one_way_direction = seq
+						.next_element()?
+						.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+					definition.toughness = seq
+						.next_element()?
+						.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+				}
+
 				B2body::create_fixture(body, &definition);
 				Ok(())
 			}
@@ -178,6 +256,12 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2fixtureVisitorContext<U> {
 						Field::m_filter => {
 							definition.filter = map.next_value()?;
 						}
+						Field::m_one_way_direction => {
+							definition.one_way_direction = map.next_value()?;
+						}
+						Field::m_toughness => {
+							definition.toughness = map.next_value()?;
+						}
 						Field::m_shape_type => {
 							shape_type = map.next_value()?;
 						}
@@ -198,7 +282,14 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2fixtureVisitorContext<U> {
 								let shape: B2polygonShape = map.next_value()?;
 								definition.shape = Some(Rc::new(RefCell::new(shape)));
 							}
-							_ => panic!(),
+							B2ShapeType::ECustom => {
+								let data: String = map.next_value()?;
+								let shape = create_custom_shape(&self.0.m_custom_shape_factory, &data)?;
+								definition.shape = Some(Rc::new(RefCell::new(shape)));
+							}
+							B2ShapeType::ETypeCount => {
+								return Err(de::Error::custom("invalid shape type"));
+							}
 						},
 					}
 				}
@@ -212,6 +303,8 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2fixtureVisitorContext<U> {
 
 pub(crate) struct B2fixtureListVisitorContext<D: UserDataType> {
 	pub(crate) body: BodyWeakPtr<D>,
+	pub(crate) m_version: u32,
+	pub(crate) m_custom_shape_factory: Option<B2customShapeFactoryPtr>,
 }
 
 impl<'de, U: UserDataType> DeserializeSeed<'de> for B2fixtureListVisitorContext<U> {
@@ -236,6 +329,8 @@ impl<'de, U: UserDataType> DeserializeSeed<'de> for B2fixtureListVisitorContext<
 			{
 				while let Some(_elem) = seq.next_element_seed(B2fixtureVisitorContext {
 					body: self.0.body.clone(),
+					m_version: self.0.m_version,
+					m_custom_shape_factory: self.0.m_custom_shape_factory.clone(),
 				})? {}
 				Ok(())
 			}
@@ -0,0 +1,213 @@
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{
+    de::DeserializeSeed,
+    ser::{SerializeSeq, SerializeStruct},
+    Deserialize, Serialize, Serializer,
+};
+use std::fmt;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::b2_body::*;
+use crate::b2_math::*;
+use crate::b2_rope::*;
+use crate::b2_world::*;
+use crate::b2rs_common::UserDataType;
+
+use strum::VariantNames;
+use strum_macros::EnumVariantNames;
+
+// An attachment with the body saved as its index in the world body list.
+#[derive(Serialize, Deserialize)]
+struct B2ropeAttachmentIndex {
+    vertex: usize,
+    body: i32,
+    local_anchor: B2vec2,
+}
+
+struct B2ropeWithAttachments<'a, D: UserDataType> {
+    rope: &'a B2worldRope<D>,
+}
+
+impl<'a, D: UserDataType> Serialize for B2ropeWithAttachments<'a, D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let attachments: Vec<B2ropeAttachmentIndex> = self
+            .rope
+            .m_attachments
+            .iter()
+            .map(|a| B2ropeAttachmentIndex {
+                vertex: a.vertex,
+                body: a.body.borrow().m_island_index,
+                local_anchor: a.local_anchor,
+            })
+            .collect();
+
+        let mut state = serializer.serialize_struct("B2rope", 2)?;
+        state.serialize_field("rope", &self.rope.m_rope)?;
+        state.serialize_field("attachments", &attachments)?;
+        state.end()
+    }
+}
+
+// Needs the body island indices set by the world serializer.
+pub(crate) struct B2ropesArrayContext<'a, D: UserDataType> {
+    pub(crate) m_ropes: &'a [B2ropePtr<D>],
+}
+
+impl<'a, D: UserDataType> Serialize for B2ropesArrayContext<'a, D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.m_ropes.len()))?;
+        for r in self.m_ropes {
+            state.serialize_element(&B2ropeWithAttachments { rope: &r.borrow() })?;
+        }
+        state.end()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct B2ropeDefinitionVisitorContext<D: UserDataType> {
+    pub(crate) m_world: B2worldPtr<D>,
+    pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
+}
+
+impl<D: UserDataType> B2ropeDefinitionVisitorContext<D> {
+    fn add_rope<E: de::Error>(
+        &self,
+        rope: B2rope,
+        attachments: Vec<B2ropeAttachmentIndex>,
+    ) -> Result<(), E> {
+        let mut rope = B2worldRope {
+            m_rope: rope,
+            m_attachments: Vec::new(),
+        };
+        let bodies = self.m_body_array.borrow();
+        for a in attachments {
+            if a.vertex >= rope.m_rope.m_positions.len() {
+                return Err(de::Error::custom("rope attachment to a missing vertex"));
+            }
+            let body = bodies
+                .get(a.body as usize)
+                .ok_or_else(|| de::Error::custom("rope attachment to a missing body"))?;
+            rope.m_attachments.push(B2ropeAttachment {
+                vertex: a.vertex,
+                body: body.clone(),
+                local_anchor: a.local_anchor,
+            });
+        }
+        self.m_world
+            .borrow_mut()
+            .m_rope_list
+            .push(Rc::new(RefCell::new(rope)));
+        return Ok(());
+    }
+}
+
+impl<'de, U: UserDataType> DeserializeSeed<'de> for B2ropeDefinitionVisitorContext<U> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(field_identifier, rename_all = "lowercase")]
+        #[derive(EnumVariantNames)]
+        #[allow(non_camel_case_types)]
+        enum Field {
+            rope,
+            attachments,
+        }
+
+        struct B2ropeDefinitionVisitor<D: UserDataType>(B2ropeDefinitionVisitorContext<D>);
+
+        impl<'de, U: UserDataType> Visitor<'de> for B2ropeDefinitionVisitor<U> {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct B2rope")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let rope: B2rope = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let attachments: Vec<B2ropeAttachmentIndex> = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                self.0.add_rope(rope, attachments)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut rope: Option<B2rope> = None;
+                let mut attachments: Vec<B2ropeAttachmentIndex> = Vec::new();
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::rope => {
+                            rope = Some(map.next_value()?);
+                        }
+                        Field::attachments => {
+                            attachments = map.next_value()?;
+                        }
+                    }
+                }
+                let rope = rope.ok_or_else(|| de::Error::missing_field("rope"))?;
+
+                self.0.add_rope(rope, attachments)
+            }
+        }
+
+        deserializer.deserialize_struct("B2rope", Field::VARIANTS, B2ropeDefinitionVisitor(self))
+    }
+}
+
+pub(crate) struct B2ropeListContext<D: UserDataType> {
+    pub(crate) m_world: B2worldPtr<D>,
+    pub(crate) m_body_array: Rc<RefCell<Vec<BodyPtr<D>>>>,
+}
+
+impl<'de, U: UserDataType> DeserializeSeed<'de> for B2ropeListContext<U> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct B2ropeListVisitor<D: UserDataType>(B2ropeListContext<D>);
+
+        impl<'de, U: UserDataType> Visitor<'de> for B2ropeListVisitor<U> {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct B2ropeListContext")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let context = B2ropeDefinitionVisitorContext {
+                    m_world: self.0.m_world.clone(),
+                    m_body_array: self.0.m_body_array.clone(),
+                };
+                while let Some(_elem) = seq.next_element_seed(context.clone())? {}
+                Ok(())
+            }
+        }
+
+        deserializer.deserialize_seq(B2ropeListVisitor(self))
+    }
+}
@@ -1,10 +1,10 @@
 use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde::{
+    de::DeserializeSeed,
     ser::{SerializeSeq, SerializeStruct},
     Deserialize, Serialize, Serializer,
 };
 use std::fmt;
-use std::marker::PhantomData;
 use std::rc::Rc;
 use std::cell::RefCell;
 
@@ -17,16 +17,31 @@ use crate::joints::serialize::serialize_b2_distance_joint::*;
 use crate::joints::serialize::serialize_b2_friction_joint::*;
 use crate::joints::serialize::serialize_b2_gear_joint::*;
 use crate::joints::serialize::serialize_b2_motor_joint::*;
+use crate::joints::serialize::serialize_b2_mouse_joint::*;
 use crate::joints::serialize::serialize_b2_prismatic_joint::*;
 use crate::joints::serialize::serialize_b2_pulley_joint::*;
 use crate::joints::serialize::serialize_b2_revolute_joint::*;
 use crate::joints::serialize::serialize_b2_weld_joint::*;
 use crate::joints::serialize::serialize_b2_wheel_joint::*;
+use crate::joints::serialize::serialize_b2_angle_joint::*;
+use crate::joints::serialize::serialize_b2_filter_joint::*;
+use crate::joints::serialize::serialize_b2_custom_joint::*;
+use crate::shapes::serialize_b2_custom_shape::*;
 
 use strum::VariantNames;
 use strum_macros::EnumVariantNames;
 
 use crate::serialize::serialize_b2_body::*;
+use crate::serialize::serialize_b2_rope::*;
+
+/// The world format written by B2world serialization. Saves without a
+/// version are version 0, the format from before the world was versioned.
+pub const B2_WORLD_FORMAT_VERSION: u32 = 1;
+
+// Binary saves of version 0 start with the bits of the gravity x component
+// where versioned saves start with the version. Every normal or zero float has
+// bits outside of this range, so only a subnormal gravity is taken for a version.
+const B2_WORLD_VERSION_BITS_LIMIT: u32 = 0x0080_0000;
 
 struct JointWithType<T: Serialize> {
     jtype: B2jointType,
@@ -62,7 +77,11 @@ impl<D: UserDataType> Serialize for B2jointsArrayContext<D>
         let mut state = serializer.serialize_seq(Some(len))?;
         for j in &self.m_joints_to_process {
             match j.borrow().as_derived() {
-                JointAsDerived::EMouseJoint(_joint) => {
+                JointAsDerived::EMouseJoint(joint) => {
+                    state.serialize_element(&JointWithType {
+                        jtype: B2jointType::EMouseJoint,
+                        joint_def: joint.get_state(),
+                    })?;
                 }
                 JointAsDerived::EDistanceJoint(joint) => {
                     let def = joint.get_def();
@@ -123,6 +142,27 @@ impl<D: UserDataType> Serialize for B2jointsArrayContext<D>
                         jtype: def.base.jtype,
                         joint_def: def,
                     })?;}
+                JointAsDerived::EAngleJoint(joint) => {
+                    let def = joint.get_def();
+                    state.serialize_element(&JointWithType {
+                        jtype: def.base.jtype,
+                        joint_def: def,
+                    })?;
+                }
+                JointAsDerived::EFilterJoint(joint) => {
+                    let def = joint.get_def();
+                    state.serialize_element(&JointWithType {
+                        jtype: def.base.jtype,
+                        joint_def: def,
+                    })?;
+                }
+                JointAsDerived::ECustomJoint(joint) => {
+                    let def = joint.get_def();
+                    state.serialize_element(&JointWithType {
+                        jtype: def.base.jtype,
+                        joint_def: def,
+                    })?;
+                }
             }
         }
         state.end()
@@ -134,8 +174,9 @@ impl<D: UserDataType> Serialize for B2world<D> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("B2world", 3)?;
+        let mut state = serializer.serialize_struct("B2world", 6)?;
 
+        state.serialize_field("m_version", &B2_WORLD_FORMAT_VERSION)?;
         state.serialize_field("m_gravity", &self.m_gravity)?;
         state.serialize_field("m_bodies_list", &self.m_body_list)?;
         for (i, ref mut b) in self.m_body_list.iter().enumerate() {
@@ -149,6 +190,13 @@ impl<D: UserDataType> Serialize for B2world<D> {
         for ref b in self.m_joint_list.iter(){
             let mut j = b.borrow_mut();
             j.get_base_mut().m_index = -1;
+            if let JointAsDerived::ECustomJoint(custom) = j.as_derived() {
+                // Custom joints without a save hook are left out, so they
+                // must not take an index that a gear joint could refer to.
+                if custom.get_custom_joint().borrow().serialize_data().is_none() {
+                    continue;
+                }
+            }
             if j.get_base().m_type!=B2jointType::EGearJoint
             {
                 not_gear_joint.push(b.clone());
@@ -173,16 +221,59 @@ impl<D: UserDataType> Serialize for B2world<D> {
             m_joints_to_process: gear_joint,
         } )?;
 
+        state.serialize_field("m_ropes_list", &B2ropesArrayContext {
+            m_ropes: &self.m_rope_list,
+        })?;
+
         state.end()
     }
 }
 
+// Bodies and joints are pushed to the front of the world lists, so the loaded
+// lists are in the reverse of the saved order that the indices refer to.
+fn in_saved_order<T>(mut items: Vec<T>) -> Rc<RefCell<Vec<T>>> {
+    items.reverse();
+    return Rc::new(RefCell::new(items));
+}
+
 pub struct B2worldDeserializeResult<U: UserDataType> {
     pub world: B2worldPtr<U>,
 }
 
 impl<'de, U: UserDataType> Deserialize<'de> for B2worldDeserializeResult<U> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        return B2worldDeserializeContext::default().deserialize(deserializer);
+    }
+}
+
+/// Deserialization seed for worlds that need load hooks. Use it with
+/// DeserializeSeed::deserialize instead of deserializing
+/// B2worldDeserializeResult directly.
+pub struct B2worldDeserializeContext<U: UserDataType> {
+    /// Recreates custom joints. Loading a world that contains custom joints
+    /// fails without it.
+    pub custom_joint_factory: Option<B2customJointFactoryPtr<U>>,
+    /// Recreates custom shapes. Loading a world that contains custom shape
+    /// fixtures fails without it.
+    pub custom_shape_factory: Option<B2customShapeFactoryPtr>,
+}
+
+impl<U: UserDataType> Default for B2worldDeserializeContext<U> {
+    fn default() -> Self {
+        return Self {
+            custom_joint_factory: None,
+            custom_shape_factory: None,
+        };
+    }
+}
+
+impl<'de, U: UserDataType> DeserializeSeed<'de> for B2worldDeserializeContext<U> {
+    type Value = B2worldDeserializeResult<U>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -191,15 +282,14 @@ impl<'de, U: UserDataType> Deserialize<'de> for B2worldDeserializeResult<U> {
         #[derive(EnumVariantNames)]
         #[allow(non_camel_case_types)]
         enum Field {
+            m_version,
             m_gravity,
             m_bodies_list,
             m_joints_list,
-            m_gear_joints_list
-        }
-        #[derive(Default)]
-        struct B2worldVisitor<U: UserDataType> {
-            marker: PhantomData<U>,
+            m_gear_joints_list,
+            m_ropes_list,
         }
+        struct B2worldVisitor<U: UserDataType>(B2worldDeserializeContext<U>);
 
         impl<'de, U: UserDataType> Visitor<'de> for B2worldVisitor<U> {
             type Value = B2worldPtr<U>;
@@ -212,40 +302,71 @@ impl<'de, U: UserDataType> Deserialize<'de> for B2worldDeserializeResult<U> {
             where
                 V: SeqAccess<'de>,
             {
-                let m_gravity: B2vec2 = seq
+                let first: u32 = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let version: u32;
+                let m_gravity: B2vec2;
+                if first > 0 && first < B2_WORLD_VERSION_BITS_LIMIT {
+                    version = first;
+                    m_gravity = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                } else {
+                    version = 0;
+                    let gravity_y: f32 = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    m_gravity = B2vec2::new(f32::from_bits(first), gravity_y);
+                }
+                if version > B2_WORLD_FORMAT_VERSION {
+                    return Err(de::Error::custom(format!("unknown world format version {}", version)));
+                }
                 let world = B2world::new(m_gravity);
 
                 seq.next_element_seed(B2bodyListContext {
                     m_world: world.clone(),
+                    m_version: version,
+                    m_custom_shape_factory: self.0.custom_shape_factory.clone(),
                 })?
                 .ok_or_else(|| de::Error::invalid_length(0, &self))?;
 
                 let m_body_array;
                 {
-                    m_body_array = Rc::new(RefCell::new(world.borrow().m_body_list.iter().collect::<Vec<_>>()));
+                    m_body_array = in_saved_order(world.borrow().m_body_list.iter().collect());
                 }  
 
                 seq.next_element_seed(B2jointListContext {
                                 m_world: world.clone(),
+                                m_version: version,
                                 m_body_array: m_body_array.clone(),
-                                m_all_joints: Rc::default()
+                                m_all_joints: Rc::default(),
+                                m_custom_joint_factory: self.0.custom_joint_factory.clone(),
                             })?
                 .ok_or_else(|| de::Error::invalid_length(0, &self))?;
 
                 let m_all_joints;
                 {
-                    m_all_joints = Rc::new(RefCell::new(world.borrow().m_joint_list.iter().collect::<Vec<_>>()));
+                    m_all_joints = in_saved_order(world.borrow().m_joint_list.iter().collect());
                 }  
 
                 seq.next_element_seed(B2jointListContext {
                     m_world: world.clone(),
+                    m_version: version,
                     m_body_array: m_body_array.clone(),
-                    m_all_joints
+                    m_all_joints,
+                    m_custom_joint_factory: self.0.custom_joint_factory.clone(),
                 })?
                 .ok_or_else(|| de::Error::invalid_length(0, &self))?;
-                
+
+                if version >= 1 {
+                    seq.next_element_seed(B2ropeListContext {
+                        m_world: world.clone(),
+                        m_body_array: m_body_array.clone(),
+                    })?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                }
+
                 Ok(world)
             }
 
@@ -253,12 +374,19 @@ impl<'de, U: UserDataType> Deserialize<'de> for B2worldDeserializeResult<U> {
             where
                 V: MapAccess<'de>,
             {
+                let mut version: u32 = 0;
                 let mut world = None;
                 let mut m_body_array = None;
                 let mut m_all_joints = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
+                        Field::m_version => {
+                            version = map.next_value()?;
+                            if version > B2_WORLD_FORMAT_VERSION {
+                                return Err(de::Error::custom(format!("unknown world format version {}", version)));
+                            }
+                        }
                         Field::m_gravity => {
                             let m_gravity = map.next_value()?;
                             world = Some(B2world::<U>::new(m_gravity));
@@ -266,26 +394,38 @@ impl<'de, U: UserDataType> Deserialize<'de> for B2worldDeserializeResult<U> {
                         Field::m_bodies_list => {                            
                             map.next_value_seed(B2bodyListContext {
                                 m_world: world.clone().unwrap(),
+                                m_version: version,
+                                m_custom_shape_factory: self.0.custom_shape_factory.clone(),
                             })?;
 
-                            m_body_array = Some(Rc::new(RefCell::new(world.as_ref().unwrap().borrow().m_body_list.iter().collect::<Vec<_>>())));
+                            m_body_array = Some(in_saved_order(world.as_ref().unwrap().borrow().m_body_list.iter().collect()));
                         }
                         Field::m_joints_list => {                          
  
                             map.next_value_seed(B2jointListContext {
                                 m_world: world.clone().unwrap(),
+                                m_version: version,
                                 m_body_array: m_body_array.clone().unwrap(),
-                                m_all_joints: Rc::default()
+                                m_all_joints: Rc::default(),
+                                m_custom_joint_factory: self.0.custom_joint_factory.clone(),
                             })?;
 
-                            m_all_joints = Some(Rc::new(RefCell::new(world.as_ref().unwrap().borrow().m_joint_list.iter().collect::<Vec<_>>())));
+                            m_all_joints = Some(in_saved_order(world.as_ref().unwrap().borrow().m_joint_list.iter().collect()));
                         }
                         Field::m_gear_joints_list => {                          
 
                             map.next_value_seed(B2jointListContext {
+                                m_world: world.clone().unwrap(),
+                                m_version: version,
+                                m_body_array: m_body_array.clone().unwrap(),
+                                m_all_joints: m_all_joints.clone().unwrap(),
+                                m_custom_joint_factory: self.0.custom_joint_factory.clone(),
+                            })?;
+                        }
+                        Field::m_ropes_list => {
+                            map.next_value_seed(B2ropeListContext {
                                 m_world: world.clone().unwrap(),
                                 m_body_array: m_body_array.clone().unwrap(),
-                                m_all_joints: m_all_joints.clone().unwrap()
                             })?;
                         }
                     }
@@ -295,7 +435,7 @@ impl<'de, U: UserDataType> Deserialize<'de> for B2worldDeserializeResult<U> {
         }
 
         let result =
-            deserializer.deserialize_struct("B2world", Field::VARIANTS, B2worldVisitor::default());
+            deserializer.deserialize_struct("B2world", Field::VARIANTS, B2worldVisitor(self));
         Ok(B2worldDeserializeResult { world: result? })
     }
 }
@@ -0,0 +1,135 @@
+use crate::b2_collision::*;
+use crate::b2_common::*;
+use crate::b2_math::*;
+use crate::b2_shape::*;
+use crate::private::collision::b2_custom_shape as private;
+use std::rc::Rc;
+
+pub type B2customShapePtr = Rc<dyn B2customShapeTrait>;
+
+/// Implement this trait to collide your own convex shapes, for example
+/// ellipses or capsules with different end radii. Collision against every
+/// other shape goes through GJK on the support function, so the shape must
+/// be convex.
+///
+/// A world with custom shape fixtures is saved through serialize_data and
+/// loaded through a B2customShapeFactory, the same way custom joints are.
+/// Fixtures whose shape has no save hook are left out of the saved world.
+pub trait B2customShapeTrait {
+	/// Get the point of the core shape furthest along the direction d.
+	/// Both are in local coordinates and d is not normalized. The core shape
+	/// is the shape without the radius.
+	fn support(&self, d: B2vec2) -> B2vec2;
+
+	/// The rounding radius added around the core shape. A capsule is a
+	/// segment core with a radius. This keeps flat sides exact.
+	fn get_radius(&self) -> f32 {
+		return 0.0;
+	}
+
+	/// The number of support directions sampled to build the collision hull.
+	/// Curved cores need more samples for smooth contacts. Counts are
+	/// clamped to the range 3 to 255.
+	fn get_support_count(&self) -> usize {
+		return 32;
+	}
+
+	/// Given a transform, compute the axis aligned bounding box, including
+	/// the radius. The polygon skin is added by the world.
+	fn compute_aabb(&self, aabb: &mut B2AABB, xf: B2Transform);
+
+	/// Compute the mass properties using the density. The inertia is
+	/// computed about the local origin.
+	fn compute_mass(&self, mass_data: &mut B2massData, density: f32);
+
+	/// Cast a ray against the shape.
+	fn ray_cast(&self, output: &mut B2rayCastOutput, input: &B2rayCastInput, xf: B2Transform) -> bool;
+
+	/// Save hook used when the world is serialized. Return the shape encoded
+	/// as a string; it is handed back to
+	/// B2customShapeFactory::create_custom_shape on load. Fixtures with a
+	/// shape returning None are not saved.
+	#[cfg(feature="serde_support")]
+	fn serialize_data(&self) -> Option<String> {
+		return None;
+	}
+}
+
+/// A convex shape described by a user support function. The support
+/// function is sampled once into a hull of at most get_support_count
+/// vertices, which is what the distance proxy and the manifold builder use.
+#[derive(Clone)]
+pub struct B2customShape {
+	pub base: B2Shape,
+	pub(crate) m_shape: B2customShapePtr,
+	/// The sampled core hull in local coordinates, counter-clockwise.
+	pub(crate) m_hull: Vec<B2vec2>,
+}
+
+impl B2customShape {
+	pub fn new(shape: B2customShapePtr) -> Self {
+		return private::new(shape);
+	}
+
+	/// Get the user shape.
+	pub fn get_shape(&self) -> B2customShapePtr {
+		return self.m_shape.clone();
+	}
+
+	/// Get the sampled core hull in local coordinates.
+	pub fn get_hull(&self) -> &[B2vec2] {
+		return &self.m_hull;
+	}
+}
+
+impl B2shapeDynTrait for B2customShape {
+	fn get_base(&self) -> &B2Shape {
+		return &self.base;
+	}
+
+	fn get_type(&self) -> B2ShapeType {
+		return self.base.get_type();
+	}
+
+	fn clone_box(&self) -> Box<dyn B2shapeDynTrait> {
+		return Box::new(self.clone());
+	}
+
+	fn clone_rc(&self) -> ShapePtr {
+		return Rc::new(self.clone());
+	}
+
+	/// [see](B2shapeDynTrait::get_child_count)
+	fn get_child_count(&self) -> usize {
+		return 1;
+	}
+
+	/// Implement b2Shape. The test uses the sampled hull.
+	fn test_point(&self, transform: B2Transform, p: B2vec2) -> bool {
+		return private::test_point(self, transform, p);
+	}
+
+	/// Implement b2Shape.
+	fn ray_cast(
+		&self,
+		output: &mut B2rayCastOutput,
+		input: &B2rayCastInput,
+		xf: B2Transform,
+		_child_index: usize,
+	) -> bool {
+		return self.m_shape.ray_cast(output, input, xf);
+	}
+
+	/// [see](B2shapeDynTrait::compute_aabb)
+	fn compute_aabb(&self, aabb: &mut B2AABB, xf: B2Transform, _child_index: usize) {
+		self.m_shape.compute_aabb(aabb, xf);
+		let r = B2vec2::new(B2_POLYGON_RADIUS, B2_POLYGON_RADIUS);
+		aabb.lower_bound -= r;
+		aabb.upper_bound += r;
+	}
+
+	/// [see](B2shapeDynTrait::compute_mass)
+	fn compute_mass(&self, mass_data: &mut B2massData, density: f32) {
+		self.m_shape.compute_mass(mass_data, density);
+	}
+}
@@ -1,5 +1,6 @@
 use super::b2_chain_shape::*;
 use super::b2_circle_shape::*;
+use super::b2_custom_shape::*;
 use super::b2_edge_shape::*;
 use super::b2_polygon_shape::*;
 
@@ -11,6 +12,7 @@ pub trait ToDerivedShape {
     fn as_edge(&self) -> Option<&B2edgeShape>;
     fn as_polygon(&self) -> Option<&B2polygonShape>;
     fn as_chain(&self) -> Option<&B2chainShape>;
+    fn as_custom(&self) -> Option<&B2customShape>;
 }
 
 pub enum ShapeAsDerived<'a>
@@ -19,6 +21,7 @@ pub enum ShapeAsDerived<'a>
     AsEdge(&'a B2edgeShape),
     AsPolygon(&'a B2polygonShape),
     AsChain(&'a B2chainShape),
+    AsCustom(&'a B2customShape),
 }
 
 impl ToDerivedShape for B2circleShape {
@@ -42,6 +45,10 @@ impl ToDerivedShape for B2circleShape {
     fn as_chain(&self) -> Option<&B2chainShape> {
         None
     }
+
+    fn as_custom(&self) -> Option<&B2customShape> {
+        None
+    }
 }
 
 impl ToDerivedShape for B2edgeShape {
@@ -64,6 +71,10 @@ impl ToDerivedShape for B2edgeShape {
     fn as_chain(&self) -> Option<&B2chainShape> {
         None
     }
+
+    fn as_custom(&self) -> Option<&B2customShape> {
+        None
+    }
 }
 
 impl ToDerivedShape for B2polygonShape {
@@ -85,6 +96,10 @@ impl ToDerivedShape for B2polygonShape {
     fn as_chain(&self) -> Option<&B2chainShape> {
         None
     }
+
+    fn as_custom(&self) -> Option<&B2customShape> {
+        None
+    }
 }
 
 impl ToDerivedShape for B2chainShape {
@@ -106,4 +121,33 @@ impl ToDerivedShape for B2chainShape {
     fn as_chain(&self) -> Option<&B2chainShape> {
         Some(self)
     }
+
+    fn as_custom(&self) -> Option<&B2customShape> {
+        None
+    }
+}
+
+impl ToDerivedShape for B2customShape {
+    fn as_derived(&self) -> ShapeAsDerived{
+        return ShapeAsDerived::AsCustom(&self);
+    }
+    fn as_circle(&self) -> Option<&B2circleShape> {
+        None
+    }
+
+    fn as_edge(&self) -> Option<&B2edgeShape> {
+        None
+    }
+
+    fn as_polygon(&self) -> Option<&B2polygonShape> {
+        None
+    }
+
+    fn as_chain(&self) -> Option<&B2chainShape> {
+        None
+    }
+
+    fn as_custom(&self) -> Option<&B2customShape> {
+        Some(self)
+    }
 }
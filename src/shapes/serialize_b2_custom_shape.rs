@@ -0,0 +1,13 @@
+use super::b2_custom_shape::*;
+
+use std::rc::Rc;
+
+/// Load hook for custom shapes. Pass it to B2worldDeserializeContext to
+/// restore the custom shape fixtures of a saved world.
+pub trait B2customShapeFactory {
+	/// Recreate a shape from the string returned by
+	/// B2customShapeTrait::serialize_data. Returning None fails the load.
+	fn create_custom_shape(&self, data: &str) -> Option<B2customShapePtr>;
+}
+
+pub type B2customShapeFactoryPtr = Rc<dyn B2customShapeFactory>;
@@ -0,0 +1,278 @@
+use crate::b2_body::*;
+use crate::b2_fixture::*;
+use crate::b2_joint::*;
+use crate::b2_math::*;
+use crate::b2_shape::*;
+use crate::b2_world::*;
+use crate::b2rs_common::UserDataType;
+use crate::private::vehicle::b2_vehicle as private;
+
+impl Default for B2tireDef {
+	fn default() -> Self {
+		return Self {
+			local_position: B2vec2::zero(),
+			half_width: 0.25,
+			half_length: 0.6,
+			density: 1.0,
+			steerable: false,
+			driven: false,
+			handbrake: false,
+			max_steer_angle: 0.6,
+			steer_speed: 3.0,
+			max_lateral_impulse: 3.0,
+			handbrake_grip: 0.3,
+			max_drive_force: 150.0,
+			max_forward_speed: 50.0,
+			max_backward_speed: 15.0,
+			drive_curve: Vec::new(),
+			brake_force: 300.0,
+			rolling_drag: 0.2,
+			angular_drag: 0.1,
+		};
+	}
+}
+
+/// Tire definition. A tire is a small box body attached to the chassis with a
+/// revolute joint. The tire local y axis is the rolling direction.
+#[derive(Clone, Debug)]
+pub struct B2tireDef {
+	/// The tire position in chassis coordinates.
+	pub local_position: B2vec2,
+
+	/// Half width of the tire box, usually in meters.
+	pub half_width: f32,
+
+	/// Half length of the tire box, usually in meters.
+	pub half_length: f32,
+
+	/// The tire density, usually in kg/m^2.
+	pub density: f32,
+
+	/// Does the steering input turn this tire?
+	pub steerable: bool,
+
+	/// Does the throttle input drive this tire?
+	pub driven: bool,
+
+	/// Is this tire locked by the handbrake?
+	pub handbrake: bool,
+
+	/// The maximum steering angle in radians.
+	pub max_steer_angle: f32,
+
+	/// How fast the tire turns towards the steering angle, in radians per second.
+	pub steer_speed: f32,
+
+	/// The largest sideways impulse the tire can apply to cancel skidding, usually in kg*m/s.
+	/// This is the lateral grip of the tire.
+	pub max_lateral_impulse: f32,
+
+	/// The lateral grip scale while the handbrake locks this tire.
+	pub handbrake_grip: f32,
+
+	/// The largest drive force, usually in N.
+	pub max_drive_force: f32,
+
+	/// The top speed going forward, usually in m/s.
+	pub max_forward_speed: f32,
+
+	/// The top speed going backward, usually in m/s.
+	pub max_backward_speed: f32,
+
+	/// The drive force curve. Each point maps the speed ratio (speed / top speed) on x
+	/// to a drive force scale on y. Points must be sorted by x. Values between points are
+	/// interpolated linearly. An empty curve applies the full drive force at any speed.
+	pub drive_curve: Vec<B2vec2>,
+
+	/// The brake force at full brake input, usually in N.
+	pub brake_force: f32,
+
+	/// Rolling resistance, scales the forward velocity into a drag force.
+	pub rolling_drag: f32,
+
+	/// Scales the angular impulse that damps the tire spin.
+	pub angular_drag: f32,
+}
+
+impl<D: UserDataType> Default for B2vehicleDef<D> {
+	fn default() -> Self {
+		return Self {
+			position: B2vec2::zero(),
+			angle: 0.0,
+			chassis_shape: None,
+			chassis_density: 0.1,
+			tires: Vec::new(),
+			user_data: None,
+		};
+	}
+}
+
+/// Vehicle definition. Vehicles are meant for top-down games, so the world
+/// gravity should be zero.
+#[derive(Clone)]
+pub struct B2vehicleDef<D: UserDataType> {
+	/// The world position of the chassis.
+	pub position: B2vec2,
+
+	/// The world angle of the chassis in radians.
+	pub angle: f32,
+
+	/// The chassis shape, this must be set. The chassis local y axis points forward.
+	pub chassis_shape: Option<ShapeDefPtr>,
+
+	/// The chassis density, usually in kg/m^2.
+	pub chassis_density: f32,
+
+	/// The tires of the vehicle.
+	pub tires: Vec<B2tireDef>,
+
+	/// Use this to store application specific body data of the chassis.
+	pub user_data: Option<D::Body>,
+}
+
+/// Driver input, applied on each update.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct B2vehicleInput {
+	/// Throttle in [-1,1]. Negative values drive backward.
+	pub throttle: f32,
+	/// Steering in [-1,1]. Positive values turn left.
+	pub steering: f32,
+	/// Brake in [0,1].
+	pub brake: f32,
+	/// Lock the handbrake tires.
+	pub handbrake: bool,
+}
+
+/// A tire of a vehicle.
+pub struct B2tire<D: UserDataType> {
+	pub(crate) m_def: B2tireDef,
+	pub(crate) m_body: BodyPtr<D>,
+	pub(crate) m_fixture: FixturePtr<D>,
+	pub(crate) m_joint: B2jointPtr<D>,
+	pub(crate) m_steer_angle: f32,
+	pub(crate) m_surface_friction: f32,
+}
+
+impl<D: UserDataType> B2tire<D> {
+	/// Get the tire body.
+	pub fn get_body(&self) -> BodyPtr<D> {
+		return self.m_body.clone();
+	}
+
+	/// Get the tire fixture.
+	pub fn get_fixture(&self) -> FixturePtr<D> {
+		return self.m_fixture.clone();
+	}
+
+	/// Get the revolute joint connecting the tire to the chassis.
+	pub fn get_joint(&self) -> B2jointPtr<D> {
+		return self.m_joint.clone();
+	}
+
+	/// Get the tire definition.
+	pub fn get_def(&self) -> &B2tireDef {
+		return &self.m_def;
+	}
+
+	/// Get the tire definition to tune the tire at runtime.
+	pub fn get_def_mut(&mut self) -> &mut B2tireDef {
+		return &mut self.m_def;
+	}
+
+	/// Get the current steering angle in radians.
+	pub fn get_steer_angle(&self) -> f32 {
+		return self.m_steer_angle;
+	}
+
+	/// Get the surface friction multiplier found by the last update.
+	pub fn get_surface_friction(&self) -> f32 {
+		return self.m_surface_friction;
+	}
+
+	/// Get the forward speed of the tire, usually in m/s.
+	pub fn get_forward_speed(&self) -> f32 {
+		let body = self.m_body.borrow();
+		let forward = body.get_world_vector(B2vec2::new(0.0, 1.0));
+		return b2_dot(forward, body.get_linear_velocity());
+	}
+
+	/// Get the sideways speed of the tire, usually in m/s.
+	pub fn get_lateral_speed(&self) -> f32 {
+		let body = self.m_body.borrow();
+		let right = body.get_world_vector(B2vec2::new(1.0, 0.0));
+		return b2_dot(right, body.get_linear_velocity());
+	}
+}
+
+/// A top-down vehicle with a tire friction model. Each frame set the input and call
+/// update before B2world::step. The tires cancel their sideways velocity up to their
+/// grip, apply the drive force along their rolling direction, brake and damp their spin.
+/// Everything is applied with B2body::apply_force and B2body::apply_linear_impulse.
+///
+/// Surfaces scale the tire grip and drive force. Register a fixture, usually a sensor,
+/// with set_surface_friction. A tire touching several surfaces uses the highest multiplier
+/// and a tire on no registered surface uses 1.
+pub struct B2vehicle<D: UserDataType> {
+	pub(crate) m_world: B2worldWeakPtr<D>,
+	pub(crate) m_chassis: BodyPtr<D>,
+	pub(crate) m_tires: Vec<B2tire<D>>,
+	pub(crate) m_input: B2vehicleInput,
+	pub(crate) m_surfaces: Vec<(FixtureWeakPtr<D>, f32)>,
+}
+
+impl<D: UserDataType> B2vehicle<D> {
+	/// Create the chassis, the tires and their joints in the world.
+	/// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+	/// <strong>Warning:</strong> This function is locked during callbacks.
+	/// </p>
+	pub fn new(world: B2worldPtr<D>, def: &B2vehicleDef<D>) -> Self {
+		return private::new(world, def);
+	}
+
+	/// Apply the current input to the tires. Call once before each B2world::step.
+	pub fn update(&mut self, dt: f32) {
+		private::update(self, dt);
+	}
+
+	/// Set the driver input.
+	pub fn set_input(&mut self, input: B2vehicleInput) {
+		self.m_input = input;
+	}
+
+	/// Get the driver input.
+	pub fn get_input(&self) -> B2vehicleInput {
+		return self.m_input;
+	}
+
+	/// Set the friction multiplier of a surface fixture.
+	pub fn set_surface_friction(&mut self, fixture: FixturePtr<D>, multiplier: f32) {
+		private::set_surface_friction(self, fixture, multiplier);
+	}
+
+	/// Remove a surface fixture.
+	pub fn clear_surface_friction(&mut self, fixture: FixturePtr<D>) {
+		private::clear_surface_friction(self, fixture);
+	}
+
+	/// Get the chassis body.
+	pub fn get_chassis(&self) -> BodyPtr<D> {
+		return self.m_chassis.clone();
+	}
+
+	/// Get the tires, in the order of B2vehicleDef::tires.
+	pub fn get_tires(&self) -> &[B2tire<D>] {
+		return &self.m_tires;
+	}
+
+	/// Get the tires to tune them at runtime.
+	pub fn get_tires_mut(&mut self) -> &mut [B2tire<D>] {
+		return &mut self.m_tires;
+	}
+
+	/// Get the forward speed of the chassis, usually in m/s.
+	pub fn get_forward_speed(&self) -> f32 {
+		let body = self.m_chassis.borrow();
+		let forward = body.get_world_vector(B2vec2::new(0.0, 1.0));
+		return b2_dot(forward, body.get_linear_velocity());
+	}
+}
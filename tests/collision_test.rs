@@ -1,6 +1,7 @@
 /// Unit tests for collision algorithms
 #[cfg(test)]
 mod collision_test {
+	use box2d_rs::b2_collision::*;
 	use box2d_rs::b2_math::*;
 	use box2d_rs::b2_shape::*;
     //use box2d_rs::b2_settings::*;
@@ -79,4 +80,23 @@ mod collision_test {
 		assert!(b2_abs(mass_data2.mass - mass) < 20.0 * (abs_tol + rel_tol * mass));
 		assert!(b2_abs(mass_data2.i - inertia) < 40.0 * (abs_tol + rel_tol * inertia));
 	}
+
+#[test]
+    fn polygon_ray_cast_near_start() {
+		let mut polygon = B2polygonShape::default();
+		polygon.set_as_box(1.0, 1.0);
+		let mut xf = B2Transform::default();
+		xf.set_identity();
+
+		// A short ray that enters the box right after its start.
+		let input = B2rayCastInput {
+			p1: B2vec2::new(-1.01, 0.0),
+			p2: B2vec2::new(-0.01, 0.0),
+			max_fraction: 1.0,
+		};
+		let mut output = B2rayCastOutput::default();
+		assert!(polygon.ray_cast(&mut output, &input, xf, 0));
+		assert!(b2_abs(output.fraction - 0.01) < 1.0e-4);
+		assert!(output.normal == B2vec2::new(-1.0, 0.0));
+	}
 }
@@ -11,9 +11,16 @@ mod test {
 	use box2d_rs::shapes::b2_circle_shape::*;
 	use box2d_rs::b2_shape::*;
 	use box2d_rs::b2_joint::*;
+	use box2d_rs::joints::b2_angle_joint::*;
+	use box2d_rs::joints::b2_custom_joint::*;
 	use box2d_rs::joints::b2_distance_joint::*;
+	use box2d_rs::joints::b2_filter_joint::*;
+	use box2d_rs::joints::b2_mouse_joint::*;
 	use box2d_rs::joints::b2_prismatic_joint::*;
 	use box2d_rs::joints::b2_revolute_joint::*;
+	use box2d_rs::joints::b2_wheel_joint::*;
+	use box2d_rs::b2_time_step::*;
+	use box2d_rs::b2_common::*;
 
     #[cfg(feature="serde_support")]
 	use serde::{Serialize, Deserialize};
@@ -107,4 +114,511 @@ mod test {
 			assert!(t == 0.0);
 		}
 	}
+
+	#[test]
+	fn angle_and_filter_joints() {
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 0.5;
+
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.density = 1.0;
+		fixture_def.shape = Some(Rc::new(RefCell::new(circle)));
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.angular_velocity = 10.0;
+		let spinner = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(spinner.clone(), &fixture_def);
+
+		// Two overlapping bodies that would push apart without the filter joint.
+		body_def.angular_velocity = 0.0;
+		body_def.position.set(5.0, 0.0);
+		let body_a = B2world::create_body(world.clone(), &body_def);
+		body_def.position.set(5.2, 0.0);
+		let body_b = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(body_a.clone(), &fixture_def);
+		B2body::create_fixture(body_b.clone(), &fixture_def);
+
+		let mut angle_joint_def = B2angleJointDef::default();
+		angle_joint_def.initialize(ground.clone(), spinner.clone());
+		angle_joint_def.enable_limit = true;
+		angle_joint_def.lower_angle = -0.25;
+		angle_joint_def.upper_angle = 0.5;
+		let angle_joint = world.borrow_mut().create_joint(&B2JointDefEnum::AngleJoint(angle_joint_def));
+
+		let mut filter_joint_def = B2filterJointDef::default();
+		filter_joint_def.initialize(body_a.clone(), body_b.clone());
+		world.borrow_mut().create_joint(&B2JointDefEnum::FilterJoint(filter_joint_def));
+
+		const TIME_STEP: f32 = 1.0 / 60.;
+		for _ in 0..60 {
+			world.borrow_mut().step(TIME_STEP, 8, 3);
+		}
+
+		// The rotation is held at the upper limit while the body falls freely.
+		let angle: f32 = spinner.borrow().get_angle();
+		assert!(b2_abs(angle - 0.5) < 0.05);
+		assert!(spinner.borrow().get_position().y < -4.0);
+		assert!(b2_abs(spinner.borrow().get_position().x) < 1.0e-4);
+		assert!(angle_joint.borrow().get_reaction_force(60.0) == B2vec2::zero());
+
+		// The filtered bodies fall side by side without separating.
+		let d: B2vec2 = body_b.borrow().get_position() - body_a.borrow().get_position();
+		assert!(b2_abs(d.x - 0.2) < 1.0e-4);
+		assert!(b2_abs(d.y) < 1.0e-4);
+	}
+
+	#[test]
+	fn springs_and_servos() {
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 0.5;
+
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.filter.mask_bits = 0;
+		fixture_def.density = 1.0;
+		fixture_def.shape = Some(Rc::new(RefCell::new(circle)));
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+
+		// A pendulum arm held by an angular spring.
+		body_def.position.set(2.0, 0.0);
+		let arm = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(arm.clone(), &fixture_def);
+
+		// An arm driven by the revolute servo.
+		body_def.position.set(12.0, 0.0);
+		let servo_arm = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(servo_arm.clone(), &fixture_def);
+
+		// A slider driven by the prismatic servo.
+		body_def.position.set(20.0, 0.0);
+		let slider = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(slider.clone(), &fixture_def);
+
+		let mut revolute_joint_def = B2revoluteJointDef::default();
+		revolute_joint_def.initialize(ground.clone(), arm.clone(), B2vec2::new(0.0, 0.0));
+		revolute_joint_def.enable_spring = true;
+		revolute_joint_def.rest_angle = 0.5;
+		// About 2 hertz and critically damped for the inertia around the pivot.
+		revolute_joint_def.stiffness = 500.0;
+		revolute_joint_def.damping = 80.0;
+		let spring_joint = world.borrow_mut().create_joint(&B2JointDefEnum::RevoluteJoint(revolute_joint_def));
+
+		let mut revolute_joint_def = B2revoluteJointDef::default();
+		revolute_joint_def.initialize(ground.clone(), servo_arm.clone(), B2vec2::new(10.0, 0.0));
+		revolute_joint_def.enable_servo = true;
+		revolute_joint_def.target_angle = 1.0;
+		revolute_joint_def.max_servo_speed = 1.0;
+		revolute_joint_def.max_servo_torque = 1000.0;
+		let servo_joint = world.borrow_mut().create_joint(&B2JointDefEnum::RevoluteJoint(revolute_joint_def));
+
+		let mut prismatic_joint_def = B2prismaticJointDef::default();
+		prismatic_joint_def.initialize(ground.clone(), slider.clone(), B2vec2::new(20.0, 0.0), B2vec2::new(1.0, 0.0));
+		prismatic_joint_def.enable_servo = true;
+		prismatic_joint_def.target_translation = 2.0;
+		prismatic_joint_def.max_servo_speed = 1.0;
+		prismatic_joint_def.max_servo_force = 1000.0;
+		world.borrow_mut().create_joint(&B2JointDefEnum::PrismaticJoint(prismatic_joint_def));
+
+		const TIME_STEP: f32 = 1.0 / 60.;
+		for _ in 0..30 {
+			world.borrow_mut().step(TIME_STEP, 8, 3);
+		}
+
+		// The servos move at their maximum speed.
+		assert!(b2_abs(servo_arm.borrow().get_angle() - 0.5) < 0.02);
+		assert!(b2_abs(slider.borrow().get_position().x - 20.5) < 0.02);
+
+		for _ in 0..150 {
+			world.borrow_mut().step(TIME_STEP, 8, 3);
+		}
+
+		// The servos hold their targets against gravity.
+		assert!(b2_abs(servo_arm.borrow().get_angle() - 1.0) < 0.01);
+		assert!(b2_abs(slider.borrow().get_position().x - 22.0) < 0.01);
+		assert!(b2_abs(slider.borrow().get_position().y) < 0.01);
+		match servo_joint.borrow().as_derived() {
+			JointAsDerived::ERevoluteJoint(joint) => {
+				assert!(joint.get_servo_torque(60.0) > 0.0);
+			}
+			_ => panic!(),
+		}
+
+		// The spring settles close to its rest angle, sagging a little under gravity.
+		let angle: f32 = arm.borrow().get_angle();
+		assert!(angle < 0.5 && angle > 0.45);
+		assert!(b2_abs(arm.borrow().get_angular_velocity()) < 0.01);
+		assert!(spring_joint.borrow().get_reaction_torque(60.0) > 0.0);
+	}
+
+	#[test]
+	fn joint_friction() {
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 0.5;
+
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.filter.mask_bits = 0;
+		fixture_def.density = 1.0;
+		fixture_def.shape = Some(Rc::new(RefCell::new(circle)));
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.angular_velocity = 4.0;
+
+		// A spinning door and a spinning wheel slowed down by friction.
+		let door = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(door.clone(), &fixture_def);
+		body_def.position.set(10.0, 0.0);
+		let wheel = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(wheel.clone(), &fixture_def);
+
+		// A motor running through the friction.
+		body_def.position.set(20.0, 0.0);
+		body_def.angular_velocity = 0.0;
+		let driven = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(driven.clone(), &fixture_def);
+
+		// Drawers on a vertical axis, one held by friction and one sliding.
+		body_def.position.set(30.0, 0.0);
+		let held = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(held.clone(), &fixture_def);
+		body_def.position.set(40.0, 0.0);
+		let sliding = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(sliding.clone(), &fixture_def);
+
+		let inertia: f32 = door.borrow().get_inertia();
+		let mass: f32 = held.borrow().get_mass();
+
+		let mut revolute_joint_def = B2revoluteJointDef::default();
+		revolute_joint_def.initialize(ground.clone(), door.clone(), B2vec2::new(0.0, 0.0));
+		// Stops the door in one second.
+		revolute_joint_def.max_friction_torque = 4.0 * inertia;
+		let door_joint = world.borrow_mut().create_joint(&B2JointDefEnum::RevoluteJoint(revolute_joint_def));
+
+		let mut wheel_joint_def = B2wheelJointDef::default();
+		wheel_joint_def.initialize(ground.clone(), wheel.clone(), B2vec2::new(10.0, 0.0), B2vec2::new(0.0, 1.0));
+		wheel_joint_def.enable_limit = true;
+		wheel_joint_def.max_friction_torque = 4.0 * inertia;
+		world.borrow_mut().create_joint(&B2JointDefEnum::WheelJoint(wheel_joint_def));
+
+		let mut revolute_joint_def = B2revoluteJointDef::default();
+		revolute_joint_def.initialize(ground.clone(), driven.clone(), B2vec2::new(20.0, 0.0));
+		revolute_joint_def.max_friction_torque = 4.0 * inertia;
+		revolute_joint_def.enable_motor = true;
+		revolute_joint_def.motor_speed = 2.0;
+		revolute_joint_def.max_motor_torque = 100.0 * inertia;
+		world.borrow_mut().create_joint(&B2JointDefEnum::RevoluteJoint(revolute_joint_def));
+
+		let mut prismatic_joint_def = B2prismaticJointDef::default();
+		prismatic_joint_def.initialize(ground.clone(), held.clone(), B2vec2::new(30.0, 0.0), B2vec2::new(0.0, 1.0));
+		prismatic_joint_def.max_friction_force = 1.5 * mass * 10.0;
+		let held_joint = world.borrow_mut().create_joint(&B2JointDefEnum::PrismaticJoint(prismatic_joint_def.clone()));
+
+		prismatic_joint_def.initialize(ground.clone(), sliding.clone(), B2vec2::new(40.0, 0.0), B2vec2::new(0.0, 1.0));
+		prismatic_joint_def.max_friction_force = 0.5 * mass * 10.0;
+		world.borrow_mut().create_joint(&B2JointDefEnum::PrismaticJoint(prismatic_joint_def));
+
+		const TIME_STEP: f32 = 1.0 / 60.;
+		for _ in 0..30 {
+			world.borrow_mut().step(TIME_STEP, 8, 3);
+		}
+
+		// Half way to stopping.
+		assert!(b2_abs(door.borrow().get_angular_velocity() - 2.0) < 0.1);
+		assert!(b2_abs(wheel.borrow().get_angular_velocity() - 2.0) < 0.1);
+		assert!(b2_abs(door_joint.borrow().get_reaction_torque(60.0) + 4.0 * inertia) < 1.0e-3);
+
+		for _ in 0..60 {
+			world.borrow_mut().step(TIME_STEP, 8, 3);
+		}
+
+		assert!(b2_abs(door.borrow().get_angular_velocity()) < 1.0e-3);
+		assert!(b2_abs(wheel.borrow().get_angular_velocity()) < 1.0e-3);
+		assert!(b2_abs(driven.borrow().get_angular_velocity() - 2.0) < 1.0e-3);
+
+		// Friction holds the weight of one drawer, the other slides at half of gravity.
+		assert!(b2_abs(held.borrow().get_position().y) < 1.0e-3);
+		match held_joint.borrow().as_derived() {
+			JointAsDerived::EPrismaticJoint(joint) => {
+				assert!(b2_abs(joint.get_friction_force(60.0) - mass * 10.0) < 1.0e-2);
+			}
+			_ => panic!(),
+		}
+		assert!(b2_abs(sliding.borrow().get_linear_velocity().y + 7.5) < 0.1);
+	}
+
+	// Keeps the center of body_b within max_length of the center of body_a.
+	struct MaxDistanceJoint {
+		max_length: f32,
+		u: B2vec2,
+		length: f32,
+		mass: f32,
+		impulse: f32,
+	}
+
+	impl MaxDistanceJoint {
+		fn new(max_length: f32) -> Self {
+			return Self {
+				max_length,
+				u: B2vec2::zero(),
+				length: 0.0,
+				mass: 0.0,
+				impulse: 0.0,
+			};
+		}
+	}
+
+	impl B2customJoint<UserDataTypes> for MaxDistanceJoint {
+		fn get_anchor_a(&self, base: &B2joint<UserDataTypes>) -> B2vec2 {
+			return base.get_body_a().borrow().get_world_center();
+		}
+		fn get_anchor_b(&self, base: &B2joint<UserDataTypes>) -> B2vec2 {
+			return base.get_body_b().borrow().get_world_center();
+		}
+		fn get_reaction_force(&self, inv_dt: f32) -> B2vec2 {
+			return inv_dt * self.impulse * self.u;
+		}
+		fn get_reaction_torque(&self, _inv_dt: f32) -> f32 {
+			return 0.0;
+		}
+		fn init_velocity_constraints(
+			&mut self,
+			bodies: &B2customJointBodies,
+			data: &B2solverData,
+			positions: &[B2position],
+			velocities: &mut [B2velocity],
+		) {
+			self.u = positions[bodies.index_b].c - positions[bodies.index_a].c;
+			self.length = self.u.normalize();
+			self.mass = bodies.inv_mass_a + bodies.inv_mass_b;
+			if self.mass > 0.0 {
+				self.mass = 1.0 / self.mass;
+			}
+
+			if data.step.warm_starting {
+				self.impulse *= data.step.dt_ratio;
+				let p: B2vec2 = self.impulse * self.u;
+				velocities[bodies.index_a].v -= bodies.inv_mass_a * p;
+				velocities[bodies.index_b].v += bodies.inv_mass_b * p;
+			} else {
+				self.impulse = 0.0;
+			}
+		}
+		fn solve_velocity_constraints(
+			&mut self,
+			bodies: &B2customJointBodies,
+			data: &B2solverData,
+			velocities: &mut [B2velocity],
+		) {
+			let cdot: f32 = b2_dot(self.u, velocities[bodies.index_b].v - velocities[bodies.index_a].v);
+			let c: f32 = self.length - self.max_length;
+			let bias: f32 = if c < 0.0 { c * data.step.inv_dt } else { 0.0 };
+			let mut impulse: f32 = -self.mass * (cdot + bias);
+			let old_impulse: f32 = self.impulse;
+			self.impulse = b2_min(0.0, self.impulse + impulse);
+			impulse = self.impulse - old_impulse;
+
+			let p: B2vec2 = impulse * self.u;
+			velocities[bodies.index_a].v -= bodies.inv_mass_a * p;
+			velocities[bodies.index_b].v += bodies.inv_mass_b * p;
+		}
+		fn solve_position_constraints(
+			&mut self,
+			bodies: &B2customJointBodies,
+			_data: &B2solverData,
+			positions: &mut [B2position],
+		) -> bool {
+			let mut u: B2vec2 = positions[bodies.index_b].c - positions[bodies.index_a].c;
+			let length: f32 = u.normalize();
+			let c: f32 = b2_clamp(length - self.max_length, 0.0, B2_MAX_LINEAR_CORRECTION);
+			let p: B2vec2 = -self.mass * c * u;
+			positions[bodies.index_a].c -= bodies.inv_mass_a * p;
+			positions[bodies.index_b].c += bodies.inv_mass_b * p;
+			return length - self.max_length < B2_LINEAR_SLOP;
+		}
+
+		#[cfg(feature="serde_support")]
+		fn serialize_data(&self) -> Option<String> {
+			return Some(self.max_length.to_string());
+		}
+	}
+
+	fn create_tether(world: B2worldPtr<UserDataTypes>) -> (BodyPtr<UserDataTypes>, BodyPtr<UserDataTypes>) {
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 0.25;
+
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.density = 1.0;
+		fixture_def.shape = Some(Rc::new(RefCell::new(circle)));
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position.set(1.0, 0.0);
+		body_def.linear_damping = 2.0;
+		let ball = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(ball.clone(), &fixture_def);
+
+		let mut joint_def = B2customJointDef::default();
+		joint_def.initialize(ground.clone(), ball.clone(), Rc::new(RefCell::new(MaxDistanceJoint::new(2.0))));
+		world.borrow_mut().create_joint(&B2JointDefEnum::CustomJoint(joint_def));
+
+		return (ground, ball);
+	}
+
+	#[test]
+	fn custom_joint() {
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let (_ground, ball) = create_tether(world.clone());
+
+		const TIME_STEP: f32 = 1.0 / 60.;
+		for _ in 0..60 {
+			world.borrow_mut().step(TIME_STEP, 8, 3);
+			let length: f32 = ball.borrow().get_position().length();
+			assert!(length < 2.0 + B2_LINEAR_SLOP);
+		}
+
+		for _ in 0..600 {
+			world.borrow_mut().step(TIME_STEP, 8, 3);
+		}
+
+		// The ball hangs below the anchor and goes to sleep with its joint.
+		let position: B2vec2 = ball.borrow().get_position();
+		assert!(b2_abs(position.x) < 0.01);
+		assert!(b2_abs(position.y + 2.0) < 0.01);
+		assert!(ball.borrow().is_awake() == false);
+
+		let joint = world.borrow().get_joint_list().iter().next().unwrap();
+		let mass: f32 = ball.borrow().get_mass();
+		let force: B2vec2 = joint.borrow().get_reaction_force(1.0 / TIME_STEP);
+		// Linear damping takes a part of the weight before the joint is solved.
+		assert!(b2_abs(force.y - 10.0 * mass) < 0.5 * mass);
+		let joint = joint.borrow();
+		match joint.as_derived() {
+			JointAsDerived::ECustomJoint(custom) => {
+				assert!(custom.get_custom_joint().borrow().get_reaction_torque(60.0) == 0.0);
+			}
+			_ => panic!(),
+		}
+	}
+
+	#[cfg(feature="serde_support")]
+	struct MaxDistanceJointFactory;
+
+	#[cfg(feature="serde_support")]
+	impl box2d_rs::joints::serialize::serialize_b2_custom_joint::B2customJointFactory<UserDataTypes> for MaxDistanceJointFactory {
+		fn create_custom_joint(&self, data: &str) -> Option<B2customJointPtr<UserDataTypes>> {
+			let max_length: f32 = data.parse().ok()?;
+			return Some(Rc::new(RefCell::new(MaxDistanceJoint::new(max_length))));
+		}
+	}
+
+	#[cfg(feature="serde_support")]
+	#[test]
+	fn custom_joint_serde() {
+		use box2d_rs::serialize::serialize_b2_world::*;
+		use serde::de::DeserializeSeed;
+
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		create_tether(world.clone());
+
+		let text = serde_json::to_string(&*world.borrow()).unwrap();
+
+		// Without the hook the custom joint can't be restored.
+		assert!(serde_json::from_str::<B2worldDeserializeResult<UserDataTypes>>(&text).is_err());
+
+		let context = B2worldDeserializeContext {
+			custom_joint_factory: Some(Rc::new(MaxDistanceJointFactory)),
+			..Default::default()
+		};
+		let mut deserializer = serde_json::Deserializer::from_str(&text);
+		let loaded = context.deserialize(&mut deserializer).unwrap().world;
+		assert!(loaded.borrow().get_joint_count() == 1);
+
+		for _ in 0..60 {
+			loaded.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		let ball = loaded.borrow().get_body_list().iter().find(|b| b.borrow().get_type() == B2bodyType::B2DynamicBody).unwrap();
+		assert!(ball.borrow().get_position().length() < 2.0 + B2_LINEAR_SLOP);
+	}
+
+	#[cfg(feature="serde_support")]
+	#[test]
+	fn mouse_joint_serde() {
+		use box2d_rs::serialize::serialize_b2_world::*;
+
+		let world = B2world::<UserDataTypes>::new(B2vec2::zero());
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 0.5;
+
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.density = 1.0;
+		fixture_def.shape = Some(Rc::new(RefCell::new(circle)));
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position.set(0.0, 5.0);
+		let ball = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture(ball.clone(), &fixture_def);
+
+		let mut joint_def = B2mouseJointDef::default();
+		joint_def.base.body_a = Some(ground.clone());
+		joint_def.base.body_b = Some(ball.clone());
+		joint_def.target = B2vec2::new(0.25, 5.0);
+		joint_def.max_force = 100.0;
+		b2_linear_stiffness(&mut joint_def.stiffness, &mut joint_def.damping, 5.0, 0.7, ground.clone(), ball.clone());
+		let joint = world.borrow_mut().create_joint(&B2JointDefEnum::MouseJoint(joint_def));
+
+		// Drag the ball part of the way.
+		match joint.borrow_mut().as_derived_mut() {
+			JointAsDerivedMut::EMouseJoint(mouse) => mouse.set_target(B2vec2::new(3.0, 5.0)),
+			_ => panic!(),
+		}
+		for _ in 0..10 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		let anchor: B2vec2 = joint.borrow().get_anchor_b();
+		assert!(anchor.x > 0.5 && anchor.x < 2.5);
+
+		let text = serde_json::to_string(&*world.borrow()).unwrap();
+		let loaded = serde_json::from_str::<B2worldDeserializeResult<UserDataTypes>>(&text).unwrap().world;
+		assert!(loaded.borrow().get_joint_count() == 1);
+
+		// The drag goes on from where it was saved.
+		let loaded_joint = loaded.borrow().get_joint_list().iter().next().unwrap();
+		let loaded_anchor: B2vec2 = loaded_joint.borrow().get_anchor_b();
+		assert!(b2_distance_vec2_squared(anchor, loaded_anchor) < 1.0e-6);
+
+		// Binary saves read the anchor in order too.
+		let bytes = bincode::serialize(&*world.borrow()).unwrap();
+		let copy = bincode::deserialize::<B2worldDeserializeResult<UserDataTypes>>(&bytes).unwrap().world;
+		let copy_joint = copy.borrow().get_joint_list().iter().next().unwrap();
+		assert!(b2_distance_vec2_squared(anchor, copy_joint.borrow().get_anchor_b()) < 1.0e-6);
+		match loaded_joint.borrow().as_derived() {
+			JointAsDerived::EMouseJoint(mouse) => {
+				assert!(mouse.get_target() == B2vec2::new(3.0, 5.0));
+				assert!(mouse.get_max_force() == 100.0);
+			}
+			_ => panic!(),
+		}
+
+		for _ in 0..120 {
+			loaded.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		assert!(b2_distance_vec2(loaded_joint.borrow().get_anchor_b(), B2vec2::new(3.0, 5.0)) < 0.05);
+	}
 }
@@ -8,10 +8,26 @@ mod test {
     use box2d_rs::b2rs_common::UserDataType;
     use box2d_rs::b2_world::*;
 	use box2d_rs::shapes::b2_circle_shape::*;
+	use box2d_rs::shapes::b2_polygon_shape::*;
+	use box2d_rs::shapes::b2_chain_shape::*;
+	use box2d_rs::shapes::b2_custom_shape::*;
+	use box2d_rs::b2_fixture::*;
+	use box2d_rs::b2_joint::*;
+	use box2d_rs::joints::b2_revolute_joint::*;
 	use box2d_rs::b2_world_callbacks::*;
 	use box2d_rs::b2_contact::*;
 	use box2d_rs::b2_collision::*;
 	use box2d_rs::b2_common::*;
+	use box2d_rs::b2_force_field::*;
+	use box2d_rs::b2_rope::*;
+	use box2d_rs::b2_soft_body::*;
+	use box2d_rs::b2_particle::*;
+	use box2d_rs::b2_particle_system::*;
+	use box2d_rs::b2_shape::*;
+	use box2d_rs::b2_settings::*;
+	use box2d_rs::character::b2_character_controller::*;
+	use box2d_rs::vehicle::b2_vehicle::*;
+	use box2d_rs::controllers::b2_buoyancy_controller::*;
 
     #[cfg(feature="serde_support")]
 	use serde::{Serialize, Deserialize};
@@ -86,4 +102,1829 @@ mod test {
 		assert_eq!(world.borrow().get_contact_list().iter().next().is_none(), false);
 		assert_eq!(listener.borrow().begin_contact, true);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn cast_shape()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		let mut wall = B2polygonShape::default();
+		wall.set_as_box(0.5, 5.0);
+
+		let mut body_def = B2bodyDef::default();
+		body_def.position = B2vec2::new(10.0, 0.0);
+		let body = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(wall)), 0.0);
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 1.0;
+
+		let mut hit_fraction = 1.0;
+		let mut hit_normal = B2vec2::zero();
+		world.borrow().cast_shape(&circle, B2Transform::new(B2vec2::zero(), B2Rot::new(0.0)), B2vec2::new(20.0, 0.0), &B2queryFilter::default(),
+			|_fixture, _point: B2vec2, normal: B2vec2, fraction: f32| -> f32 {
+				hit_fraction = fraction;
+				hit_normal = normal;
+				return fraction;
+			});
+
+		// The circle touches the wall face at x = 9.5 after travelling 8.5 units.
+		assert!(b2_abs(hit_fraction * 20.0 - 8.5) < 0.05);
+		assert!(b2_abs(hit_normal.x + 1.0) < 0.01);
+
+		// A filter that rejects the wall category reports no hit.
+		let mut filter = B2queryFilter::default();
+		filter.mask_bits = 0x0002;
+		let mut hit = false;
+		world.borrow().cast_shape(&circle, B2Transform::new(B2vec2::zero(), B2Rot::new(0.0)), B2vec2::new(20.0, 0.0), &filter,
+			|_fixture, _point: B2vec2, _normal: B2vec2, _fraction: f32| -> f32 {
+				hit = true;
+				return 1.0;
+			});
+		assert_eq!(hit, false);
+	}
+
+	#[test]
+	fn overlap_queries()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		// The fat AABB of this rotated box overlaps the query circle, the box itself does not.
+		let mut diamond = B2polygonShape::default();
+		diamond.set_as_box_angle(1.0, 1.0, B2vec2::zero(), 0.25 * B2_PI);
+		let body_def = B2bodyDef::default();
+		let body = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(diamond)), 0.0);
+
+		let mut aabb_count = 0;
+		world.borrow().query_aabb(|_fixture| -> bool {
+			aabb_count += 1;
+			return true;
+		}, B2AABB { lower_bound: B2vec2::new(0.8, 0.8), upper_bound: B2vec2::new(1.2, 1.2) });
+		assert_eq!(aabb_count, 1);
+
+		let mut count = 0;
+		world.borrow().overlap_circle(B2vec2::new(1.0, 1.0), 0.2, &B2queryFilter::default(), |_fixture| -> bool {
+			count += 1;
+			return true;
+		});
+		assert_eq!(count, 0);
+
+		world.borrow().overlap_circle(B2vec2::new(0.5, 0.5), 0.2, &B2queryFilter::default(), |_fixture| -> bool {
+			count += 1;
+			return true;
+		});
+		assert_eq!(count, 1);
+
+		let mut hit = false;
+		world.borrow().overlap_point(B2vec2::new(1.2, 0.0), &B2queryFilter::default(), |_fixture| -> bool {
+			hit = true;
+			return true;
+		});
+		assert_eq!(hit, true);
+	}
+
+	#[test]
+	fn ray_cast_filter()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		// Three walls along the x axis, the middle one is a sensor.
+		for i in 0..3 {
+			let mut wall = B2polygonShape::default();
+			wall.set_as_box(0.5, 5.0);
+			let mut body_def = B2bodyDef::default();
+			body_def.position = B2vec2::new(10.0 + 5.0 * i as f32, 0.0);
+			let body = B2world::create_body(world.clone(), &body_def);
+			let mut fixture_def = B2fixtureDef::default();
+			fixture_def.shape = Some(Rc::new(RefCell::new(wall)));
+			fixture_def.is_sensor = i == 1;
+			fixture_def.user_data = Some(i);
+			B2body::create_fixture(body, &fixture_def);
+		}
+
+		let p1 = B2vec2::zero();
+		let p2 = B2vec2::new(30.0, 0.0);
+
+		let hits = world.borrow().ray_cast_all(p1, p2, &B2queryFilter::default());
+		let ids: Vec<i32> = hits.iter().map(|h| h.fixture.borrow().get_user_data().unwrap()).collect();
+		assert_eq!(ids, vec![0, 1, 2]);
+
+		let mut filter = B2queryFilter::default();
+		filter.include_sensors = false;
+		filter.predicate = Some(Rc::new(|fixture: FixturePtr<UserDataTypes>| fixture.borrow().get_user_data() != Some(0)));
+		let closest = world.borrow().ray_cast_closest(p1, p2, &filter).unwrap();
+		assert_eq!(closest.fixture.borrow().get_user_data(), Some(2));
+		assert!(b2_abs(closest.point.x - 19.5) < 0.01);
+	}
+
+	#[test]
+	fn fixture_distance()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		// A floor made of a chain, the closest child is the middle segment.
+		let mut chain = B2chainShape::default();
+		chain.create_chain(&[B2vec2::new(-20.0, 5.0), B2vec2::new(-10.0, 0.0), B2vec2::new(10.0, 0.0), B2vec2::new(20.0, 5.0)],
+			B2vec2::new(-30.0, 5.0), B2vec2::new(30.0, 5.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let ground_fixture = B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(chain)), 0.0);
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 1.0;
+		let mut body_def = B2bodyDef::default();
+		body_def.position = B2vec2::new(2.0, 4.0);
+		let body = B2world::create_body(world.clone(), &body_def);
+		let circle_fixture = B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(circle)), 1.0);
+
+		let output = world.borrow().distance(ground_fixture.clone(), circle_fixture.clone());
+		assert_eq!(output.child_index_a, 1);
+		assert!(b2_abs(output.distance - 3.0) < 2.0 * B2_POLYGON_RADIUS);
+		assert!(b2_abs(output.normal.y - 1.0) < 0.01);
+
+		let closest = circle_fixture.borrow().closest_point(B2vec2::new(2.0, 0.0));
+		assert!(b2_abs(closest.distance - 3.0) < 0.01);
+		assert!(b2_abs(closest.point_a.y - 3.0) < 0.01);
+
+		let (nearest, output) = world.borrow().query_nearest(B2vec2::new(2.0, 6.0), 10.0, &B2queryFilter::default()).unwrap();
+		assert!(Rc::ptr_eq(&nearest, &circle_fixture));
+		assert!(b2_abs(output.distance - 1.0) < 0.01);
+
+		assert!(world.borrow().query_nearest(B2vec2::new(2.0, 6.0), 0.5, &B2queryFilter::default()).is_none());
+	}
+
+	#[test]
+	fn iterators()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 1.0;
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		let body_a = B2world::create_body(world.clone(), &body_def);
+		body_def.position = B2vec2::new(1.0, 0.0);
+		let body_b = B2world::create_body(world.clone(), &body_def);
+		body_def.position = B2vec2::new(10.0, 0.0);
+		let body_c = B2world::create_body(world.clone(), &body_def);
+		for body in [&body_a, &body_b, &body_c] {
+			B2body::create_fixture_by_shape((*body).clone(), Rc::new(RefCell::new(circle)), 1.0);
+		}
+		B2body::create_fixture_by_shape(body_a.clone(), Rc::new(RefCell::new(circle)), 1.0);
+
+		let mut joint_def = B2revoluteJointDef::default();
+		joint_def.initialize(body_a.clone(), body_c.clone(), B2vec2::new(5.0, 0.0));
+		world.borrow_mut().create_joint(&B2JointDefEnum::RevoluteJoint(joint_def));
+
+		world.borrow_mut().step(1.0 / 60.0, 6, 2);
+
+		assert_eq!(world.borrow().bodies().count(), 3);
+		assert_eq!(world.borrow().joints().count(), 1);
+		assert_eq!(body_a.borrow().fixtures().count(), 2);
+
+		let (_joint, other) = body_a.borrow().joints().next().unwrap();
+		assert!(Rc::ptr_eq(&other, &body_c));
+
+		// Both fixtures of body_a touch body_b, body_c touches nothing.
+		assert_eq!(world.borrow().contacts().count(), 2);
+		assert!(body_a.borrow().contacts().all(|(_contact, other)| Rc::ptr_eq(&other, &body_b)));
+		assert_eq!(body_c.borrow().contacts().count(), 0);
+
+		// Destroying the current body while iterating is allowed.
+		let bodies = world.borrow().bodies();
+		for body in bodies {
+			if Rc::ptr_eq(&body, &body_b) {
+				world.borrow_mut().destroy_body(body);
+			}
+		}
+		assert_eq!(world.borrow().bodies().count(), 2);
+	}
+
+	#[test]
+	fn character_controller()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		// Floor, a low step starting at x = 2 and a wall at x = 6.
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut floor = B2polygonShape::default();
+		floor.set_as_box_angle(20.0, 1.0, B2vec2::new(0.0, -1.0), 0.0);
+		B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(floor)), 0.0);
+		let mut step = B2polygonShape::default();
+		step.set_as_box_angle(2.0, 0.1, B2vec2::new(4.0, 0.1), 0.0);
+		B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(step)), 0.0);
+		let mut wall = B2polygonShape::default();
+		wall.set_as_box_angle(0.5, 5.0, B2vec2::new(6.5, 5.0), 0.0);
+		B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(wall)), 0.0);
+
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 0.5;
+		let def = B2characterControllerDef::<UserDataTypes> {
+			shape: Some(Rc::new(RefCell::new(circle))),
+			position: B2vec2::new(0.0, 2.0),
+			..Default::default()
+		};
+		let mut character = B2characterController::new(world.clone(), &def);
+		assert_eq!(character.get_body().borrow().get_type(), B2bodyType::B2KinematicBody);
+
+		let dt = 1.0 / 60.0;
+		for _ in 0..60 {
+			character.move_and_slide(B2vec2::new(0.0, -10.0 * dt), dt);
+			world.borrow_mut().step(dt, 6, 2);
+		}
+		assert!(character.is_grounded());
+		assert!(b2_abs(character.get_position().y - 0.5) < 2.0 * def.skin_width);
+
+		// Walk right, climbing the step and stopping at the wall.
+		for _ in 0..120 {
+			character.move_and_slide(B2vec2::new(5.0 * dt, -10.0 * dt), dt);
+			world.borrow_mut().step(dt, 6, 2);
+		}
+		let position = character.get_position();
+		assert!(character.is_grounded());
+		assert!(b2_abs(position.x - 5.5) < 0.05);
+		assert!(b2_abs(position.y - 0.7) < 0.05);
+
+		// Teleport into the wall and push out.
+		{
+			let body = character.get_body();
+			body.borrow_mut().set_transform(B2vec2::new(5.8, 0.7), 0.0);
+		}
+		let correction = character.depenetrate();
+		assert!(correction.x < 0.0);
+		assert!(character.get_position().x <= 5.5);
+	}
+
+	#[test]
+	fn vehicle()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::zero());
+
+		// A muddy area around the start.
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut mud = B2polygonShape::default();
+		mud.set_as_box(5.0, 5.0);
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.shape = Some(Rc::new(RefCell::new(mud)));
+		fixture_def.is_sensor = true;
+		let mud = B2body::create_fixture(ground.clone(), &fixture_def);
+
+		let mut chassis = B2polygonShape::default();
+		chassis.set_as_box(1.0, 2.0);
+		let front = B2tireDef {
+			steerable: true,
+			..Default::default()
+		};
+		let rear = B2tireDef {
+			driven: true,
+			handbrake: true,
+			..Default::default()
+		};
+		let mut def = B2vehicleDef::<UserDataTypes>::default();
+		def.chassis_shape = Some(Rc::new(RefCell::new(chassis)));
+		for (x, y) in [(-1.2, 1.5), (1.2, 1.5), (-1.2, -1.5), (1.2, -1.5)] {
+			let mut tire = if y > 0.0 { front.clone() } else { rear.clone() };
+			tire.local_position = B2vec2::new(x, y);
+			def.tires.push(tire);
+		}
+		let mut vehicle = B2vehicle::new(world.clone(), &def);
+		vehicle.set_surface_friction(mud, 0.5);
+		assert_eq!(vehicle.get_tires().len(), 4);
+
+		let dt = 1.0 / 60.0;
+		vehicle.set_input(B2vehicleInput { throttle: 1.0, ..Default::default() });
+		for i in 0..120 {
+			vehicle.update(dt);
+			world.borrow_mut().step(dt, 8, 3);
+			if i == 1 {
+				assert_eq!(vehicle.get_tires()[2].get_surface_friction(), 0.5);
+			}
+		}
+		assert!(vehicle.get_forward_speed() > 1.0);
+		let chassis = vehicle.get_chassis();
+		assert!(chassis.borrow().get_position().y > 1.0);
+		assert!(b2_abs(chassis.borrow().get_position().x) < 0.01);
+		for tire in vehicle.get_tires() {
+			assert!(b2_abs(tire.get_lateral_speed()) < 0.01);
+		}
+
+		// Steering to the left turns the car counter clockwise.
+		vehicle.set_input(B2vehicleInput { throttle: 1.0, steering: 1.0, ..Default::default() });
+		for _ in 0..60 {
+			vehicle.update(dt);
+			world.borrow_mut().step(dt, 8, 3);
+		}
+		assert!(chassis.borrow().get_angle() > 0.1);
+		assert!(b2_abs(vehicle.get_tires()[0].get_steer_angle() - front.max_steer_angle) < 0.01);
+
+		// Far away from the mud and braking to a stop.
+		assert_eq!(vehicle.get_tires()[2].get_surface_friction(), 1.0);
+		vehicle.set_input(B2vehicleInput { brake: 1.0, handbrake: true, ..Default::default() });
+		for _ in 0..300 {
+			vehicle.update(dt);
+			world.borrow_mut().step(dt, 8, 3);
+		}
+		assert!(chassis.borrow().get_linear_velocity().length() < 0.01);
+	}
+
+	#[test]
+	fn buoyancy_controller()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		// Water below y = 0, flowing to the right.
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut water = B2polygonShape::default();
+		water.set_as_box_angle(50.0, 10.0, B2vec2::new(0.0, -10.0), 0.0);
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.shape = Some(Rc::new(RefCell::new(water)));
+		fixture_def.is_sensor = true;
+		let water = B2body::create_fixture(ground.clone(), &fixture_def);
+		let mut controller = B2buoyancyController::new(world.clone(), water, &B2buoyancyControllerDef {
+			density: 2.0,
+			flow_velocity: B2vec2::new(1.0, 0.0),
+			..Default::default()
+		});
+
+		// Bodies half as dense as the water float half submerged.
+		let mut bodies = Vec::new();
+		for i in 0..2 {
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			body_def.position = B2vec2::new(-5.0 + 5.0 * i as f32, 2.0);
+			let body = B2world::create_body(world.clone(), &body_def);
+			if i == 0 {
+				let mut shape = B2polygonShape::default();
+				shape.set_as_box(0.5, 0.5);
+				B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(shape)), 1.0);
+			} else {
+				let mut shape = B2circleShape::default();
+				shape.base.m_radius = 0.5;
+				B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(shape)), 1.0);
+			}
+			bodies.push(body);
+		}
+
+		let dt = 1.0 / 60.0;
+		for _ in 0..600 {
+			controller.step();
+			world.borrow_mut().step(dt, 8, 3);
+		}
+
+		assert_eq!(controller.get_submerged_bodies().len(), 2);
+		for body in &bodies {
+			let body = body.borrow();
+			assert!(b2_abs(body.get_position().y) < 0.05);
+			assert!(b2_abs(body.get_linear_velocity().x - 1.0) < 0.05);
+			assert!(b2_abs(body.get_linear_velocity().y) < 0.05);
+		}
+		let submerged = &controller.get_submerged_bodies()[0];
+		assert!(b2_abs(submerged.area - 0.5) < 0.05);
+		assert!(submerged.centroid.y < 0.0);
+	}
+
+	#[test]
+	fn force_fields()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		let create_ball = |position: B2vec2, category_bits: u16| -> BodyPtr<UserDataTypes> {
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			body_def.position = position;
+			let body = B2world::create_body(world.clone(), &body_def);
+			let mut circle = B2circleShape::default();
+			circle.base.m_radius = 0.5;
+			let mut fixture_def = B2fixtureDef::default();
+			fixture_def.shape = Some(Rc::new(RefCell::new(circle)));
+			fixture_def.density = 1.0;
+			fixture_def.filter.category_bits = category_bits;
+			B2body::create_fixture(body.clone(), &fixture_def);
+			return body;
+		};
+
+		// A zero gravity wind tunnel around x = 0.
+		let mut tunnel = B2polygonShape::default();
+		tunnel.set_as_box(10.0, 10.0);
+		let tunnel: ShapeDefPtr = Rc::new(RefCell::new(tunnel));
+		let identity = B2Transform::new(B2vec2::zero(), B2Rot::new(0.0));
+		world.borrow_mut().create_force_field(&B2forceFieldDef {
+			area: Some(B2forceFieldArea::Shape { shape: tunnel.clone(), transform: identity }),
+			field_type: B2forceFieldType::GravityOverride { gravity: B2vec2::zero() },
+			mask_bits: 0x0001,
+			..Default::default()
+		});
+		let wind = world.borrow_mut().create_force_field(&B2forceFieldDef {
+			area: Some(B2forceFieldArea::Shape { shape: tunnel, transform: identity }),
+			field_type: B2forceFieldType::Directional { acceleration: B2vec2::new(2.0, 0.0) },
+			mask_bits: 0x0001,
+			..Default::default()
+		});
+
+		// A planet pulling towards its center, attached to a sensor fixture.
+		let mut planet_def = B2bodyDef::default();
+		planet_def.position = B2vec2::new(100.0, 0.0);
+		let planet = B2world::create_body(world.clone(), &planet_def);
+		let mut range = B2circleShape::default();
+		range.base.m_radius = 20.0;
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.shape = Some(Rc::new(RefCell::new(range)));
+		fixture_def.is_sensor = true;
+		let range = B2body::create_fixture(planet.clone(), &fixture_def);
+		world.borrow_mut().create_force_field(&B2forceFieldDef {
+			area: Some(B2forceFieldArea::Fixture(range)),
+			field_type: B2forceFieldType::Radial {
+				center: B2vec2::zero(),
+				strength: 10.0,
+				falloff: B2forceFieldFalloff::InverseSquare,
+				radius: 10.0,
+			},
+			..Default::default()
+		});
+
+		let blown = create_ball(B2vec2::zero(), 0x0001);
+		let ignored = create_ball(B2vec2::new(2.0, 0.0), 0x0002);
+		let orbiting = create_ball(B2vec2::new(90.0, 0.0), 0x0001);
+
+		world.borrow_mut().set_auto_clear_forces(false);
+		let dt = 1.0 / 60.0;
+		for _ in 0..60 {
+			world.borrow_mut().step(dt, 8, 3);
+		}
+
+		// Forces of the fields do not accumulate when auto clearing is off.
+		let velocity = blown.borrow().get_linear_velocity();
+		assert!(b2_abs(velocity.x - 2.0) < 0.01);
+		assert!(b2_abs(velocity.y) < 0.01);
+		assert!(b2_abs(blown.borrow().get_position().y) < 0.01);
+
+		let velocity = ignored.borrow().get_linear_velocity();
+		assert!(b2_abs(velocity.x) < 0.01);
+		assert!(b2_abs(velocity.y + 10.0) < 0.01);
+
+		// Gravity pulls down and the planet pulls right with ten times gravity at the field radius.
+		let velocity = orbiting.borrow().get_linear_velocity();
+		assert!(velocity.x > 10.0);
+
+		wind.borrow_mut().set_enabled(false);
+		let before = blown.borrow().get_linear_velocity();
+		world.borrow_mut().step(dt, 8, 3);
+		assert_eq!(blown.borrow().get_linear_velocity(), before);
+	}
+
+	#[test]
+	fn explode()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::zero());
+
+		// A wall shelters the body behind it.
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut wall = B2polygonShape::default();
+		wall.set_as_box_angle(0.5, 5.0, B2vec2::new(5.0, 0.0), 0.0);
+		B2body::create_fixture_by_shape(ground, Rc::new(RefCell::new(wall)), 0.0);
+
+		let mut bodies = Vec::new();
+		for x in [3.0, -4.0, 8.0, 20.0] {
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			body_def.position = B2vec2::new(x, 0.0);
+			let body = B2world::create_body(world.clone(), &body_def);
+			let mut circle = B2circleShape::default();
+			circle.base.m_radius = 0.5;
+			B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(circle)), 1.0);
+			bodies.push(body);
+		}
+
+		let hits = world.borrow().explode(B2vec2::zero(), 10.0, 10.0, B2forceFieldFalloff::Linear, true);
+		assert_eq!(hits.len(), 2);
+		let hit = hits.iter().find(|hit| Rc::ptr_eq(&hit.body, &bodies[0])).unwrap();
+		assert!(b2_abs(hit.distance - 2.5) < 0.01);
+		assert!(b2_abs(hit.impulse.x - 7.5) < 0.01);
+		assert!(b2_abs(hit.impulse.y) < 0.01);
+		let hit = hits.iter().find(|hit| Rc::ptr_eq(&hit.body, &bodies[1])).unwrap();
+		assert!(b2_abs(hit.impulse.x + 6.5) < 0.01);
+
+		let mass = bodies[0].borrow().get_mass();
+		assert!(b2_abs(bodies[0].borrow().get_linear_velocity().x - 7.5 / mass) < 0.01);
+		assert_eq!(bodies[2].borrow().get_linear_velocity(), B2vec2::zero());
+
+		// Without occlusion the sheltered body is pushed too.
+		let hits = world.borrow().explode(B2vec2::zero(), 10.0, 10.0, B2forceFieldFalloff::Constant, false);
+		assert_eq!(hits.len(), 3);
+		assert!(bodies[2].borrow().get_linear_velocity().x > 0.0);
+		assert_eq!(bodies[3].borrow().get_linear_velocity(), B2vec2::zero());
+
+		// The inverse square falloff never exceeds the impulse, even right at the surface.
+		let hits = world.borrow().explode(B2vec2::new(2.5, 0.0), 5.0, 10.0, B2forceFieldFalloff::InverseSquare, false);
+		let hit = hits.iter().find(|hit| Rc::ptr_eq(&hit.body, &bodies[0])).unwrap();
+		assert!(hit.distance < B2_LINEAR_SLOP);
+		assert!(b2_abs(hit.impulse.length() - 10.0) < 0.01);
+
+		// A body is pushed once, however many fixtures it has.
+		let world = B2world::<UserDataTypes>::new(B2vec2::zero());
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position = B2vec2::new(3.0, 0.0);
+		let body = B2world::create_body(world.clone(), &body_def);
+		for offset in [0.0, 1.0] {
+			let mut circle = B2circleShape::default();
+			circle.base.m_radius = 0.5;
+			circle.m_p = B2vec2::new(offset, 0.0);
+			B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(circle)), 1.0);
+		}
+		let hits = world.borrow().explode(B2vec2::zero(), 10.0, 10.0, B2forceFieldFalloff::Constant, false);
+		assert_eq!(hits.len(), 1);
+		assert!(b2_abs(hits[0].distance - 2.5) < 0.01);
+		assert!(b2_abs(hits[0].impulse.x - 10.0) < 0.01);
+		let mass = body.borrow().get_mass();
+		assert!(b2_abs(body.borrow().get_linear_velocity().x - 10.0 / mass) < 0.01);
+	}
+
+	#[test]
+	fn motion_limits()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+
+		// A slope that pushes bodies sideways.
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut slope = B2polygonShape::default();
+		slope.set_as_box_angle(10.0, 0.5, B2vec2::zero(), 0.3);
+		B2body::create_fixture_by_shape(ground, Rc::new(RefCell::new(slope)), 0.0);
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position = B2vec2::new(1.0, 3.0);
+		body_def.fixed_translation_x = true;
+		let locked = B2world::create_body(world.clone(), &body_def);
+		let mut circle = B2circleShape::default();
+		circle.base.m_radius = 0.5;
+		B2body::create_fixture_by_shape(locked.clone(), Rc::new(RefCell::new(circle)), 1.0);
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position = B2vec2::new(-20.0, 50.0);
+		body_def.angular_velocity = 100.0;
+		body_def.max_linear_speed = 4.0;
+		body_def.max_angular_speed = 2.0;
+		let limited = B2world::create_body(world.clone(), &body_def);
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(0.5, 0.5);
+		B2body::create_fixture_by_shape(limited.clone(), Rc::new(RefCell::new(shape)), 1.0);
+
+		for _ in 0..120 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+			assert_eq!(locked.borrow().get_world_center().x, 1.0);
+			assert!(limited.borrow().get_linear_velocity().length() <= 4.0 + 1.0e-4);
+			assert!(b2_abs(limited.borrow().get_angular_velocity()) <= 2.0);
+		}
+		// The locked body rests on the slope instead of sliding off.
+		assert!(locked.borrow().get_world_center().y < 2.0);
+		assert_eq!(locked.borrow().get_linear_velocity().x, 0.0);
+
+		locked.borrow_mut().set_fixed_translation_x(false);
+		locked.borrow_mut().set_awake(true);
+		assert!(locked.borrow().is_fixed_translation_x() == false);
+		for _ in 0..60 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		assert!(locked.borrow().get_world_center().x < 1.0);
+
+		// Contacts treat a locked body as immovable along its axis, so a body pushing
+		// into it stops at the surface instead of sinking in.
+		let world = B2world::<UserDataTypes>::new(B2vec2::zero());
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(0.5, 0.5);
+		let shape = Rc::new(RefCell::new(shape));
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.fixed_translation_x = true;
+		body_def.fixed_rotation = true;
+		let wall = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture_by_shape(wall.clone(), shape.clone(), 1.0);
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position = B2vec2::new(-1.0, 0.0);
+		body_def.linear_velocity = B2vec2::new(5.0, 0.0);
+		let pusher = B2world::create_body(world.clone(), &body_def);
+		B2body::create_fixture_by_shape(pusher.clone(), shape.clone(), 10.0);
+
+		for _ in 0..60 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+			pusher.borrow_mut().apply_force_to_center(B2vec2::new(500.0, 0.0), true);
+			assert_eq!(wall.borrow().get_world_center().x, 0.0);
+			assert!(pusher.borrow().get_world_center().x < -1.0 + 2.0 * B2_LINEAR_SLOP);
+		}
+		assert!(b2_abs(pusher.borrow().get_linear_velocity().x) < 0.1);
+	}
+
+	#[cfg(feature="serde_support")]
+	#[test]
+	fn motion_limits_serde()
+	{
+		use box2d_rs::serialize::serialize_b2_world::*;
+
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.fixed_translation_y = true;
+		body_def.max_linear_speed = 4.0;
+		B2world::create_body(world.clone(), &body_def);
+
+		// A JSON body saved before the motion limits existed. The removed fields are
+		// followed by other fields, so each ends with a comma.
+		let mut text = serde_json::to_string(&*world.borrow()).unwrap();
+		for key in ["fixed_translation_x", "fixed_translation_y", "max_linear_speed", "max_angular_speed"] {
+			let start: usize = text.find(&format!("\"{}\":", key)).unwrap();
+			let end: usize = start + text[start..].find(',').unwrap();
+			text.replace_range(start..=end, "");
+		}
+		assert!(text.contains("max_linear_speed") == false);
+
+		let loaded = serde_json::from_str::<B2worldDeserializeResult<UserDataTypes>>(&text).unwrap().world;
+		let body = loaded.borrow().get_body_list().iter().next().unwrap();
+		let body = body.borrow();
+		assert!(body.is_fixed_translation_x() == false);
+		assert!(body.is_fixed_translation_y() == false);
+		assert_eq!(body.get_max_linear_speed(), B2_MAX_FLOAT);
+		assert_eq!(body.get_max_angular_speed(), B2_MAX_FLOAT);
+	}
+
+	#[test]
+	fn target_transform()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::zero());
+		let dt = 1.0 / 60.0;
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2KinematicBody;
+		body_def.angle = 3.0;
+		let platform = B2world::create_body(world.clone(), &body_def);
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box_angle(2.0, 0.25, B2vec2::new(0.5, 0.0), 0.0);
+		B2body::create_fixture_by_shape(platform.clone(), Rc::new(RefCell::new(shape)), 1.0);
+
+		// Crossing pi turns the short way around.
+		let target = B2Transform::new(B2vec2::new(1.0, 1.0), B2Rot::new(-3.0));
+		platform.borrow_mut().set_target_transform(target, dt);
+		let w = platform.borrow().get_angular_velocity();
+		assert!(b2_abs(w - (2.0 * B2_PI - 6.0) / dt) < 1.0e-2);
+		world.borrow_mut().step(dt, 8, 3);
+		let xf = platform.borrow().get_transform();
+		assert!(b2_distance_vec2(xf.p, target.p) < 1.0e-4);
+		assert!(b2_abs(xf.q.s - target.q.s) < 1.0e-4 && b2_abs(xf.q.c - target.q.c) < 1.0e-4);
+
+		// Snapping lands exactly on the keyframes.
+		platform.borrow_mut().set_snap_to_target(true);
+		for i in 1..=30 {
+			let target = B2Transform::new(B2vec2::new(1.0 + 0.37 * i as f32, 1.0), B2Rot::new(-3.0 - 0.05 * i as f32));
+			platform.borrow_mut().set_target_transform(target, dt);
+			world.borrow_mut().step(dt, 8, 3);
+			let xf = platform.borrow().get_transform();
+			assert_eq!(xf.p, target.p);
+			assert!(b2_abs(xf.q.s - target.q.s) < 1.0e-6 && b2_abs(xf.q.c - target.q.c) < 1.0e-6);
+		}
+	}
+
+	#[test]
+	fn one_way_fixture()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let dt = 1.0 / 60.0;
+
+		// A tilted platform that can be jumped through from below.
+		let mut body_def = B2bodyDef::default();
+		body_def.position = B2vec2::new(0.0, 5.0);
+		body_def.angle = 0.3;
+		let platform = B2world::create_body(world.clone(), &body_def);
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(4.0, 0.1);
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.shape = Some(Rc::new(RefCell::new(shape)));
+		fixture_def.friction = 0.8;
+		fixture_def.one_way_direction = Some(B2vec2::new(0.0, 2.0));
+		let fixture = B2body::create_fixture(platform.clone(), &fixture_def);
+		assert_eq!(fixture.borrow().get_one_way_direction(), Some(B2vec2::new(0.0, 1.0)));
+
+		let create_box = |position: B2vec2, velocity: B2vec2, bullet: bool| -> BodyPtr<UserDataTypes> {
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			body_def.position = position;
+			body_def.angle = 0.3;
+			body_def.linear_velocity = velocity;
+			body_def.bullet = bullet;
+			let body = B2world::create_body(world.clone(), &body_def);
+			let mut shape = B2polygonShape::default();
+			shape.set_as_box(0.25, 0.25);
+			let mut fixture_def = B2fixtureDef::default();
+			fixture_def.shape = Some(Rc::new(RefCell::new(shape)));
+			fixture_def.density = 1.0;
+			fixture_def.friction = 0.8;
+			B2body::create_fixture(body.clone(), &fixture_def);
+			return body;
+		};
+
+		// Jumps up through the platform and lands on top of it.
+		let jumper = create_box(B2vec2::new(0.0, 3.0), B2vec2::new(0.0, 12.0), false);
+		// Too fast for the discrete solver, TOI must stop it on top.
+		let faller = create_box(B2vec2::new(-2.0, 30.0), B2vec2::new(0.0, -300.0), true);
+		// Too fast as well, but passes through from below.
+		let shooter = create_box(B2vec2::new(2.0, 3.0), B2vec2::new(0.0, 300.0), true);
+
+		// A moving platform carries what lands on it.
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2KinematicBody;
+		body_def.position = B2vec2::new(-20.0, 0.0);
+		body_def.linear_velocity = B2vec2::new(1.0, 0.0);
+		let carrier = B2world::create_body(world.clone(), &body_def);
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(3.0, 0.1);
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.shape = Some(Rc::new(RefCell::new(shape)));
+		fixture_def.friction = 0.8;
+		fixture_def.one_way_direction = Some(B2vec2::new(0.0, 1.0));
+		B2body::create_fixture(carrier.clone(), &fixture_def);
+		let rider = create_box(B2vec2::new(-20.0, 2.0), B2vec2::zero(), false);
+		rider.borrow_mut().set_transform(B2vec2::new(-20.0, 2.0), 0.0);
+
+		let mut jumper_above = false;
+		for _ in 0..480 {
+			world.borrow_mut().step(dt, 8, 3);
+			jumper_above = jumper_above || jumper.borrow().get_position().y > 7.0;
+		}
+		assert!(jumper_above);
+
+		let local = platform.borrow().get_local_point(jumper.borrow().get_position());
+		assert!(local.y > 0.3 && local.y < 0.4);
+		assert!(jumper.borrow().get_linear_velocity().length() < 0.1);
+		let local = platform.borrow().get_local_point(faller.borrow().get_position());
+		assert!(local.y > 0.3 && local.y < 0.4);
+		let local = carrier.borrow().get_local_point(rider.borrow().get_position());
+		assert!(local.y > 0.3 && local.y < 0.4);
+		assert!(b2_abs(rider.borrow().get_linear_velocity().x - 1.0) < 0.01);
+		assert!(shooter.borrow().get_position().y > 10.0 || shooter.borrow().get_linear_velocity().y > 0.0);
+	}
+
+	#[cfg(feature="serde_support")]
+	#[test]
+	fn one_way_fixture_serde()
+	{
+		use box2d_rs::serialize::serialize_b2_world::*;
+
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let platform = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(4.0, 0.1);
+		let mut fixture_def = B2fixtureDef::default();
+		fixture_def.shape = Some(Rc::new(RefCell::new(shape)));
+		fixture_def.one_way_direction = Some(B2vec2::new(0.0, 1.0));
+		fixture_def.toughness = 3.0;
+		B2body::create_fixture(platform.clone(), &fixture_def);
+		B2body::create_fixture_by_shape(platform.clone(), Rc::new(RefCell::new(shape)), 0.0);
+
+		let check = |loaded: B2worldPtr<UserDataTypes>| {
+			let body = loaded.borrow().get_body_list().iter().next().unwrap();
+			let mut directions: Vec<Option<B2vec2>> =
+				body.borrow().get_fixture_list().iter().map(|f| f.borrow().get_one_way_direction()).collect();
+			directions.sort_by_key(|d| d.is_some());
+			assert_eq!(directions, vec![None, Some(B2vec2::new(0.0, 1.0))]);
+			let toughness: f32 = body.borrow().get_fixture_list().iter().map(|f| f.borrow().get_toughness()).sum();
+			assert_eq!(toughness, 3.0);
+		};
+
+		let text = serde_json::to_string(&*world.borrow()).unwrap();
+		check(serde_json::from_str::<B2worldDeserializeResult<UserDataTypes>>(&text).unwrap().world);
+
+		// Binary saves read the fields in order.
+		let bytes = bincode::serialize(&*world.borrow()).unwrap();
+		check(bincode::deserialize::<B2worldDeserializeResult<UserDataTypes>>(&bytes).unwrap().world);
+	}
+
+	#[test]
+	fn speculative_contacts()
+	{
+		// Returns the ball and wall positions along x after shooting a fast ball at a thin
+		// dynamic wall.
+		let shoot = |speculative: bool| -> (f32, f32) {
+			let world = B2world::<UserDataTypes>::new(B2vec2::zero());
+			world.borrow_mut().set_speculative_contacts(speculative);
+			assert_eq!(world.borrow().get_speculative_contacts(), speculative);
+
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			let wall = B2world::create_body(world.clone(), &body_def);
+			let mut shape = B2polygonShape::default();
+			shape.set_as_box(0.05, 2.0);
+			B2body::create_fixture_by_shape(wall.clone(), Rc::new(RefCell::new(shape)), 1.0);
+
+			body_def.position = B2vec2::new(-5.0, 0.0);
+			body_def.linear_velocity = B2vec2::new(200.0, 0.0);
+			let ball = B2world::create_body(world.clone(), &body_def);
+			let mut circle = B2circleShape::default();
+			circle.base.m_radius = 0.1;
+			B2body::create_fixture_by_shape(ball.clone(), Rc::new(RefCell::new(circle)), 10.0);
+
+			for _ in 0..30 {
+				world.borrow_mut().step(1.0 / 60.0, 8, 3);
+			}
+			let ball_x = ball.borrow().get_position().x;
+			let wall_x = wall.borrow().get_position().x;
+			return (ball_x, wall_x);
+		};
+
+		// Dynamic against dynamic has no TOI, the ball tunnels through.
+		let (ball_x, wall_x) = shoot(false);
+		assert!(ball_x > wall_x);
+
+		let (ball_x, wall_x) = shoot(true);
+		assert!(ball_x < wall_x);
+		assert!(wall_x > 0.0);
+
+		// Resting contacts are not disturbed.
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		world.borrow_mut().set_speculative_contacts(true);
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(10.0, 0.5);
+		B2body::create_fixture_by_shape(ground, Rc::new(RefCell::new(shape)), 0.0);
+		let mut boxes = Vec::new();
+		for i in 0..5 {
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			body_def.position = B2vec2::new(0.0, 1.0 + 1.0 * i as f32);
+			let body = B2world::create_body(world.clone(), &body_def);
+			let mut shape = B2polygonShape::default();
+			shape.set_as_box(0.5, 0.5);
+			B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(shape)), 1.0);
+			boxes.push(body);
+		}
+		for _ in 0..120 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		for (i, body) in boxes.iter().enumerate() {
+			let position = body.borrow().get_position();
+			assert!(b2_abs(position.x) < 0.01);
+			assert!(b2_abs(position.y - (1.0 + 1.0 * i as f32)) < 0.05);
+		}
+	}
+
+	// An ellipse with semi-axes a and b centered at the origin.
+	struct Ellipse {
+		a: f32,
+		b: f32,
+	}
+
+	impl B2customShapeTrait for Ellipse {
+		fn support(&self, d: B2vec2) -> B2vec2 {
+			let p = B2vec2::new(self.a * self.a * d.x, self.b * self.b * d.y);
+			return (1.0 / b2_sqrt(b2_dot(p, d))) * p;
+		}
+
+		fn compute_aabb(&self, aabb: &mut B2AABB, xf: B2Transform) {
+			let (c, s) = (xf.q.c, xf.q.s);
+			let extents = B2vec2::new(
+				b2_sqrt(self.a * self.a * c * c + self.b * self.b * s * s),
+				b2_sqrt(self.a * self.a * s * s + self.b * self.b * c * c),
+			);
+			aabb.lower_bound = xf.p - extents;
+			aabb.upper_bound = xf.p + extents;
+		}
+
+		fn compute_mass(&self, mass_data: &mut B2massData, density: f32) {
+			mass_data.mass = density * B2_PI * self.a * self.b;
+			mass_data.center = B2vec2::zero();
+			mass_data.i = 0.25 * mass_data.mass * (self.a * self.a + self.b * self.b);
+		}
+
+		fn ray_cast(&self, output: &mut B2rayCastOutput, input: &B2rayCastInput, xf: B2Transform) -> bool {
+			// Scale the ellipse to the unit circle.
+			let scale = |v: B2vec2| B2vec2::new(v.x / self.a, v.y / self.b);
+			let p1 = scale(b2_mul_t_transform_by_vec2(xf, input.p1));
+			let p2 = scale(b2_mul_t_transform_by_vec2(xf, input.p2));
+			let r = p2 - p1;
+			let rr = b2_dot(r, r);
+			let b = b2_dot(p1, r);
+			let c = b2_dot(p1, p1) - 1.0;
+			let sigma = b * b - rr * c;
+			if sigma < 0.0 || rr < B2_EPSILON {
+				return false;
+			}
+			let t = (-b - b2_sqrt(sigma)) / rr;
+			if t < 0.0 || t > input.max_fraction {
+				return false;
+			}
+			let hit = p1 + t * r;
+			let mut normal = b2_mul_rot_by_vec2(xf.q, B2vec2::new(hit.x / self.a, hit.y / self.b));
+			normal.normalize();
+			output.fraction = t;
+			output.normal = normal;
+			return true;
+		}
+
+		#[cfg(feature="serde_support")]
+		fn serialize_data(&self) -> Option<String> {
+			return Some(format!("{} {}", self.a, self.b));
+		}
+	}
+
+	// A box given only by its support function.
+	struct SupportBox {
+		hx: f32,
+		hy: f32,
+	}
+
+	impl B2customShapeTrait for SupportBox {
+		fn support(&self, d: B2vec2) -> B2vec2 {
+			return B2vec2::new(if d.x < 0.0 { -self.hx } else { self.hx }, if d.y < 0.0 { -self.hy } else { self.hy });
+		}
+
+		fn compute_aabb(&self, aabb: &mut B2AABB, xf: B2Transform) {
+			let mut polygon = B2polygonShape::default();
+			polygon.set_as_box(self.hx, self.hy);
+			polygon.base.m_radius = 0.0;
+			polygon.compute_aabb(aabb, xf, 0);
+		}
+
+		fn compute_mass(&self, mass_data: &mut B2massData, density: f32) {
+			mass_data.mass = density * 4.0 * self.hx * self.hy;
+			mass_data.center = B2vec2::zero();
+			mass_data.i = mass_data.mass * (self.hx * self.hx + self.hy * self.hy) / 3.0;
+		}
+
+		fn ray_cast(&self, output: &mut B2rayCastOutput, input: &B2rayCastInput, xf: B2Transform) -> bool {
+			let mut polygon = B2polygonShape::default();
+			polygon.set_as_box(self.hx, self.hy);
+			return polygon.ray_cast(output, input, xf, 0);
+		}
+	}
+
+	// An ellipse that asks for more samples than a hull can hold.
+	struct FineEllipse(Ellipse);
+
+	impl B2customShapeTrait for FineEllipse {
+		fn support(&self, d: B2vec2) -> B2vec2 {
+			return self.0.support(d);
+		}
+
+		fn compute_aabb(&self, aabb: &mut B2AABB, xf: B2Transform) {
+			self.0.compute_aabb(aabb, xf);
+		}
+
+		fn compute_mass(&self, mass_data: &mut B2massData, density: f32) {
+			self.0.compute_mass(mass_data, density);
+		}
+
+		fn ray_cast(&self, output: &mut B2rayCastOutput, input: &B2rayCastInput, xf: B2Transform) -> bool {
+			return self.0.ray_cast(output, input, xf);
+		}
+
+		fn get_support_count(&self) -> usize {
+			return 1000;
+		}
+	}
+
+	#[test]
+	fn custom_shapes()
+	{
+		let ellipse: B2customShapePtr = Rc::new(Ellipse { a: 1.0, b: 0.5 });
+		let support_box: B2customShapePtr = Rc::new(SupportBox { hx: 0.5, hy: 0.5 });
+		let fine_ellipse: B2customShapePtr = Rc::new(FineEllipse(Ellipse { a: 1.0, b: 0.5 }));
+
+		// Contact features index the hull with a u8.
+		assert_eq!(B2customShape::new(fine_ellipse.clone()).get_hull().len(), 255);
+
+		// The support box samples to its four corners and collides like a polygon.
+		let custom_box = B2customShape::new(support_box.clone());
+		assert_eq!(custom_box.get_hull().len(), 4);
+		let mut polygon = B2polygonShape::default();
+		polygon.set_as_box(0.5, 0.5);
+		let xf_a = B2Transform::new(B2vec2::zero(), B2Rot::new(0.0));
+		let xf_b = B2Transform::new(B2vec2::new(0.2, 0.95), B2Rot::new(0.0));
+		let mut manifold = B2manifold::default();
+		b2_collide_convex(&mut manifold, &polygon, 0, &xf_a, &custom_box, 0, &xf_b);
+		assert_eq!(manifold.point_count, 2);
+		let mut world_manifold = B2worldManifold::default();
+		world_manifold.initialize(&manifold, xf_a, polygon.base.m_radius, xf_b, custom_box.base.m_radius);
+		assert!(b2_abs(world_manifold.normal.y - 1.0) < 1.0e-4);
+		for separation in &world_manifold.separations[..2] {
+			assert!(b2_abs(*separation + 0.05 + polygon.base.m_radius + custom_box.base.m_radius) < 1.0e-4);
+		}
+		assert!(custom_box.test_point(xf_b, B2vec2::new(0.6, 1.3)));
+		assert!(custom_box.test_point(xf_b, B2vec2::new(0.8, 1.3)) == false);
+
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(20.0, 0.5);
+		B2body::create_fixture_by_shape(ground, Rc::new(RefCell::new(shape)), 0.0);
+
+		let create = |shape: &B2customShapePtr, position: B2vec2, angle: f32, velocity: B2vec2| -> BodyPtr<UserDataTypes> {
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			body_def.position = position;
+			body_def.angle = angle;
+			body_def.linear_velocity = velocity;
+			let body = B2world::create_body(world.clone(), &body_def);
+			let mut fixture_def = B2fixtureDef::default();
+			fixture_def.shape = Some(Rc::new(RefCell::new(B2customShape::new(shape.clone()))));
+			fixture_def.density = 1.0;
+			fixture_def.friction = 0.6;
+			B2body::create_fixture(body.clone(), &fixture_def);
+			return body;
+		};
+
+		// A tilted ellipse rolls onto its flat side.
+		let lying = create(&ellipse, B2vec2::new(-5.0, 2.0), 0.4, B2vec2::zero());
+		lying.borrow_mut().set_angular_damping(3.0);
+		// A box stacked on a custom box.
+		let bottom = create(&support_box, B2vec2::new(5.0, 1.0), 0.0, B2vec2::zero());
+		let top = create(&support_box, B2vec2::new(5.0, 2.0), 0.0, B2vec2::zero());
+		// A custom shape on a polygon body.
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position = B2vec2::new(0.0, 1.0);
+		let crate_body = B2world::create_body(world.clone(), &body_def);
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(0.5, 0.5);
+		B2body::create_fixture_by_shape(crate_body.clone(), Rc::new(RefCell::new(shape)), 1.0);
+		let on_crate = create(&ellipse, B2vec2::new(0.0, 2.0), 0.0, B2vec2::zero());
+		// Too fast for the discrete solver, TOI must stop it at the ground.
+		let fast = create(&ellipse, B2vec2::new(10.0, 20.0), 0.0, B2vec2::new(0.0, -300.0));
+		// The capped hull collides like the coarse one.
+		let fine = create(&fine_ellipse, B2vec2::new(-10.0, 2.0), 1.2, B2vec2::zero());
+		fine.borrow_mut().set_angular_damping(3.0);
+
+		for _ in 0..600 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+
+		let position = lying.borrow().get_position();
+		let angle = lying.borrow().get_angle();
+		assert!(b2_abs(position.y - 1.0) < 0.02);
+		assert!(b2_abs(angle) < 0.02 || b2_abs(b2_abs(angle) - B2_PI) < 0.02);
+
+		for (body, y) in [(&bottom, 1.0), (&top, 2.0)] {
+			let position = body.borrow().get_position();
+			assert!(b2_abs(position.x - 5.0) < 0.01);
+			// Each contact rests on the polygon skins.
+			assert!(b2_abs(position.y - y) < 0.04);
+			assert!(b2_abs(body.borrow().get_angle()) < 0.01);
+			assert!(body.borrow().is_awake() == false);
+		}
+
+		let position = on_crate.borrow().get_position();
+		assert!(b2_abs(position.x) < 0.01);
+		assert!(b2_abs(position.y - 2.0) < 0.04);
+
+		assert!(fast.borrow().get_position().y > 0.9);
+
+		let position = fine.borrow().get_position();
+		assert!(b2_abs(position.y - 1.0) < 0.02);
+
+		// Ray casts go to the user shape.
+		let mut hit: Option<B2vec2> = None;
+		world.borrow().ray_cast(|_fixture, point, _normal, fraction| {
+			hit = Some(point);
+			return fraction;
+		}, B2vec2::new(-5.0, 5.0), B2vec2::new(-5.0, 1.2));
+		assert!(b2_abs(hit.unwrap().y - 1.5) < 0.02);
+	}
+
+	#[cfg(feature="serde_support")]
+	struct EllipseFactory;
+
+	#[cfg(feature="serde_support")]
+	impl box2d_rs::shapes::serialize_b2_custom_shape::B2customShapeFactory for EllipseFactory {
+		fn create_custom_shape(&self, data: &str) -> Option<B2customShapePtr> {
+			let mut axes = data.split(' ').map(|v| v.parse::<f32>());
+			let a = axes.next()?.ok()?;
+			let b = axes.next()?.ok()?;
+			return Some(Rc::new(Ellipse { a, b }));
+		}
+	}
+
+	#[cfg(feature="serde_support")]
+	#[test]
+	fn custom_shape_serde()
+	{
+		use box2d_rs::serialize::serialize_b2_world::*;
+		use serde::de::DeserializeSeed;
+
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		let body = B2world::create_body(world.clone(), &body_def);
+		let ellipse: B2customShapePtr = Rc::new(Ellipse { a: 1.0, b: 0.5 });
+		B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(B2customShape::new(ellipse))), 1.0);
+		// The support box has no save hook, so its fixture is left out.
+		let support_box: B2customShapePtr = Rc::new(SupportBox { hx: 0.5, hy: 0.5 });
+		B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(B2customShape::new(support_box))), 1.0);
+
+		let text = serde_json::to_string(&*world.borrow()).unwrap();
+
+		// Without the hook the custom shape can't be restored.
+		assert!(serde_json::from_str::<B2worldDeserializeResult<UserDataTypes>>(&text).is_err());
+
+		let context = B2worldDeserializeContext::<UserDataTypes> {
+			custom_shape_factory: Some(Rc::new(EllipseFactory)),
+			..Default::default()
+		};
+		let mut deserializer = serde_json::Deserializer::from_str(&text);
+		let loaded = context.deserialize(&mut deserializer).unwrap().world;
+
+		let body = loaded.borrow().get_body_list().iter().next().unwrap();
+		let fixtures: Vec<FixturePtr<UserDataTypes>> = body.borrow().get_fixture_list().iter().collect();
+		assert_eq!(fixtures.len(), 1);
+		assert_eq!(fixtures[0].borrow().get_type(), B2ShapeType::ECustom);
+		assert!(b2_abs(body.borrow().get_mass() - B2_PI * 0.5) < 1.0e-5);
+	}
+
+	#[test]
+	fn rope_attachments()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(20.0, 0.5);
+		B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(shape)), 0.0);
+
+		let create_box = |position: B2vec2| -> BodyPtr<UserDataTypes> {
+			let mut body_def = B2bodyDef::default();
+			body_def.body_type = B2bodyType::B2DynamicBody;
+			body_def.position = position;
+			let body = B2world::create_body(world.clone(), &body_def);
+			let mut shape = B2polygonShape::default();
+			shape.set_as_box(0.25, 0.25);
+			B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(shape)), 4.0);
+			return body;
+		};
+
+		let rope_def = |start: B2vec2, step: B2vec2| -> B2ropeDef {
+			let mut def = B2ropeDef::default();
+			for i in 0..10 {
+				def.vertices.push(B2ropeDefVertices {
+					position: start + (i as f32) * step,
+					mass: 0.1,
+				});
+			}
+			return def;
+		};
+
+		// A box hangs from the ground on a rope.
+		let hanging = create_box(B2vec2::new(-5.0, 5.25));
+		let rope = world.borrow_mut().create_rope(&rope_def(B2vec2::new(-5.0, 10.0), B2vec2::new(0.0, -0.5)));
+		rope.borrow_mut().attach(0, ground.clone(), B2vec2::new(-5.0, 10.0));
+		rope.borrow_mut().attach(9, hanging.clone(), B2vec2::new(-5.0, 5.5));
+
+		// A pendulum starts to the side and swings through the middle.
+		let pendulum = create_box(B2vec2::new(9.5, 10.0));
+		let pendulum_rope = world.borrow_mut().create_rope(&rope_def(B2vec2::new(5.0, 10.0), B2vec2::new(0.5, 0.0)));
+		pendulum_rope.borrow_mut().attach(0, ground.clone(), B2vec2::new(5.0, 10.0));
+		pendulum_rope.borrow_mut().attach(9, pendulum.clone(), B2vec2::new(9.5, 10.0));
+
+		// A free rope falls on the ground.
+		let free_rope = world.borrow_mut().create_rope(&rope_def(B2vec2::new(-15.0, 3.0), B2vec2::new(0.5, 0.0)));
+		assert_eq!(world.borrow().get_ropes().len(), 3);
+
+		let mut min_pendulum_x: f32 = B2_MAX_FLOAT;
+		for _ in 0..300 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+			min_pendulum_x = b2_min(min_pendulum_x, pendulum.borrow().get_position().x);
+		}
+
+		// The rope holds the box and keeps its length.
+		let position = hanging.borrow().get_position();
+		assert!(b2_abs(position.x + 5.0) < 0.05);
+		assert!(b2_abs(position.y - 5.25) < 0.1);
+		assert!(hanging.borrow().get_linear_velocity().length() < 0.1);
+		assert!(b2_distance_vec2(rope.borrow().get_rope().get_vertex(0), B2vec2::new(-5.0, 10.0)) < 0.01);
+		let anchor = hanging.borrow().get_world_point(B2vec2::new(0.0, 0.25));
+		assert!(b2_distance_vec2(rope.borrow().get_rope().get_vertex(9), anchor) < 0.01);
+
+		assert!(min_pendulum_x < 5.5);
+		assert!(b2_distance_vec2(pendulum.borrow().get_position(), B2vec2::new(5.0, 10.0)) < 4.7);
+
+		// Rope vertices rest on the ground.
+		{
+			let free_rope = free_rope.borrow();
+			let free_rope = free_rope.get_rope();
+			for i in 0..free_rope.get_vertex_count() {
+				let v = free_rope.get_vertex(i);
+				assert!(b2_abs(v.y - 0.5 - free_rope.get_radius() - B2_POLYGON_RADIUS) < 0.02);
+				assert!(free_rope.get_vertex_velocity(i).length() < 0.05);
+			}
+		}
+
+		// Destroying a body removes its attachments.
+		world.borrow_mut().destroy_body(hanging);
+		assert_eq!(rope.borrow().get_attachments().len(), 1);
+	}
+
+	#[test]
+	fn rope_cut_and_reel()
+	{
+		// A standalone rope hanging from its first vertex.
+		let mut def = B2ropeDef::default();
+		for i in 0..10 {
+			def.vertices.push(B2ropeDefVertices {
+				position: B2vec2::new(0.0, -0.5 * i as f32),
+				mass: if i == 0 { 0.0 } else { 0.1 },
+			});
+		}
+		def.gravity = B2vec2::new(0.0, -10.0);
+		def.tuning.damping = 1.0;
+		let mut rope = B2rope::default();
+		rope.create(&def);
+		assert_eq!(rope.get_segment_count(), 9);
+		assert!(b2_abs(rope.get_length() - 4.5) < 1.0e-4);
+
+		let step = |rope: &mut B2rope, count: usize| {
+			for _ in 0..count {
+				rope.step(1.0 / 60.0, 20, B2vec2::zero());
+			}
+		};
+		step(&mut rope, 300);
+
+		// Each segment carries the weight of the vertices below it.
+		for segment in 0..rope.get_segment_count() {
+			let weight: f32 = 0.1 * 10.0 * (rope.get_segment_count() - segment) as f32;
+			assert!(b2_abs(rope.get_tension(segment) - weight) < 0.1 * weight);
+		}
+
+		// Reel out like a winch, the new segments take the rest length of the
+		// existing ones.
+		rope.reel(1.0);
+		assert!(b2_abs(rope.get_length() - 5.5) < 1.0e-4);
+		assert_eq!(rope.get_vertex_count(), 12);
+		assert_eq!(rope.get_segment_count(), 11);
+		step(&mut rope, 300);
+		assert!(b2_abs(rope.get_current_length() - 5.5) < 0.1);
+		assert!(b2_abs(rope.get_vertex(11).y + 5.5) < 0.1);
+		assert!(b2_abs(rope.get_tension(0) - 11.0) < 1.1);
+
+		// Reel in.
+		rope.reel(-2.5);
+		assert!(b2_abs(rope.get_length() - 3.0) < 1.0e-4);
+		assert_eq!(rope.get_segment_count() + 1, rope.get_vertex_count());
+		step(&mut rope, 300);
+		assert!(b2_abs(rope.get_vertex(rope.get_vertex_count() - 1).y + 3.0) < 0.1);
+
+		// Cut a world rope holding a box, the box falls with the lower piece.
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position = B2vec2::new(0.0, 5.25);
+		let body = B2world::create_body(world.clone(), &body_def);
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(0.25, 0.25);
+		B2body::create_fixture_by_shape(body.clone(), Rc::new(RefCell::new(shape)), 4.0);
+
+		for (i, v) in def.vertices.iter_mut().enumerate() {
+			v.position = B2vec2::new(0.0, 10.0 - 0.5 * i as f32);
+			v.mass = 0.1;
+		}
+		let rope = world.borrow_mut().create_rope(&def);
+		rope.borrow_mut().attach(0, ground.clone(), B2vec2::new(0.0, 10.0));
+		rope.borrow_mut().attach(9, body.clone(), B2vec2::new(0.0, 5.5));
+		for _ in 0..60 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		assert!(b2_abs(body.borrow().get_position().y - 5.25) < 0.1);
+
+		let piece = world.borrow_mut().cut_rope(&rope, 4);
+		assert_eq!(world.borrow().get_ropes().len(), 2);
+		assert_eq!(rope.borrow().get_rope().get_vertex_count(), 5);
+		assert_eq!(piece.borrow().get_rope().get_vertex_count(), 5);
+		assert_eq!(rope.borrow().get_attachments()[0].vertex, 0);
+		assert_eq!(piece.borrow().get_attachments()[0].vertex, 4);
+		assert!(b2_abs(rope.borrow().get_rope().get_length() + piece.borrow().get_rope().get_length() - 4.0) < 1.0e-4);
+
+		for _ in 0..30 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		assert!(body.borrow().get_position().y < 4.0);
+		let anchor = body.borrow().get_world_point(B2vec2::new(0.0, 0.25));
+		assert!(b2_distance_vec2(piece.borrow().get_rope().get_vertex(4), anchor) < 0.02);
+		assert!(b2_distance_vec2(rope.borrow().get_rope().get_vertex(0), B2vec2::new(0.0, 10.0)) < 0.01);
+
+		// Reeling a world rope keeps the attachments on their vertices.
+		piece.borrow_mut().reel(1.0);
+		assert_eq!(piece.borrow().get_rope().get_vertex_count(), 7);
+		assert_eq!(piece.borrow().get_attachments()[0].vertex, 6);
+		piece.borrow_mut().reel(-1.0);
+		assert_eq!(piece.borrow().get_rope().get_vertex_count(), 5);
+		assert_eq!(piece.borrow().get_attachments()[0].vertex, 4);
+	}
+
+	#[test]
+	fn rope_friction()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut table = B2polygonShape::default();
+		table.set_as_box_angle(5.0, 0.5, B2vec2::new(-5.0, -0.5), 0.0);
+		B2body::create_fixture_by_shape(ground, Rc::new(RefCell::new(table)), 0.0);
+
+		// A limp rope lies on the table with its end over the edge. The end
+		// falls and pulls the rest of the rope along the table.
+		let create_rope = |friction: f32| -> B2ropePtr<UserDataTypes> {
+			let mut def = B2ropeDef::default();
+			for i in 0..12 {
+				def.vertices.push(B2ropeDefVertices {
+					position: B2vec2::new(-4.0 + 0.5 * i as f32, def.radius + B2_POLYGON_RADIUS),
+					mass: 0.1,
+				});
+			}
+			def.tuning.bend_stiffness = 0.0;
+			def.friction = friction;
+			return world.borrow_mut().create_rope(&def);
+		};
+		let sticky = create_rope(0.5);
+		let slippery = create_rope(0.0);
+
+		for _ in 0..300 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+
+		// The weight of the hanging vertices is less than the friction on the
+		// resting ones, which hold the rope although the rope pulls them along
+		// in iterations that don't push them out of the table.
+		let sticky = sticky.borrow();
+		let sticky = sticky.get_rope();
+		assert!(b2_abs(sticky.get_vertex(0).x + 4.0) < 1.0e-3);
+		assert!(sticky.get_vertex_velocity(0).length() < 1.0e-3);
+		assert!(slippery.borrow().get_rope().get_vertex(0).x > 0.0);
+	}
+
+	#[test]
+	fn soft_bodies()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(20.0, 0.5);
+		B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(shape)), 0.0);
+
+		// A blob lands on the ground and keeps its area.
+		let mut def = B2softBodyDef::default();
+		def.position.set(-5.0, 4.0);
+		def.set_as_circle(1.0, 24);
+		def.damping = 1.0;
+		let blob = world.borrow_mut().create_soft_body(&def);
+
+		// An inflated tyre with springy edges.
+		def.position.set(5.0, 4.0);
+		def.edge_hertz = 10.0;
+		def.edge_damping_ratio = 0.5;
+		def.pressure = 1.5;
+		let tyre = world.borrow_mut().create_soft_body(&def);
+		assert_eq!(world.borrow().get_soft_bodies().len(), 2);
+
+		for _ in 0..480 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+
+		for soft_body in [&blob, &tyre] {
+			let soft_body = soft_body.borrow();
+			let target: f32 = soft_body.get_pressure() * soft_body.get_rest_area();
+			assert!(b2_abs(soft_body.get_area() - target) < 0.05 * target);
+			assert!(soft_body.get_linear_velocity().length() < 0.05);
+			for v in soft_body.get_outline() {
+				assert!(v.y > 0.5 + soft_body.get_radius() - 0.02);
+			}
+		}
+		// Resting soft bodies don't roll away.
+		assert!(b2_abs(tyre.borrow().get_center().x - 5.0) < 0.05);
+
+		// The blob sags under its weight.
+		let center = blob.borrow().get_center();
+		assert!(b2_abs(center.x + 5.0) < 0.05);
+		assert!(center.y < 1.5 && center.y > 1.0);
+
+		// A blob thrown at a box pushes it.
+		world.borrow_mut().set_gravity(B2vec2::zero());
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position.set(3.0, 20.0);
+		let crate_body = B2world::create_body(world.clone(), &body_def);
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(0.5, 0.5);
+		B2body::create_fixture_by_shape(crate_body.clone(), Rc::new(RefCell::new(shape)), 1.0);
+
+		let mut def = B2softBodyDef::default();
+		def.position.set(0.0, 20.0);
+		def.set_as_circle(1.0, 24);
+		def.linear_velocity.set(4.0, 0.0);
+		let thrown = world.borrow_mut().create_soft_body(&def);
+		let momentum: f32 = 4.0 * thrown.borrow().get_mass();
+
+		for _ in 0..60 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		let crate_momentum: f32 = crate_body.borrow().get_mass() * crate_body.borrow().get_linear_velocity().x;
+		let blob_momentum: f32 = thrown.borrow().get_mass() * thrown.borrow().get_linear_velocity().x;
+		// The blob is soft, so it keeps moving with the box.
+		assert!(crate_momentum > 0.2 * momentum);
+		assert!(b2_abs(crate_momentum + blob_momentum - momentum) < 0.1 * momentum);
+
+		world.borrow_mut().destroy_soft_body(thrown);
+		assert_eq!(world.borrow().get_soft_bodies().len(), 2);
+	}
+
+	#[test]
+	fn particles()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		for (hx, hy, center) in [
+			(4.0, 0.5, B2vec2::new(0.0, -0.5)),
+			(0.5, 5.0, B2vec2::new(-4.5, 5.0)),
+			(0.5, 5.0, B2vec2::new(4.5, 5.0)),
+		] {
+			let mut shape = B2polygonShape::default();
+			shape.set_as_box_angle(hx, hy, center, 0.0);
+			B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(shape)), 0.0);
+		}
+
+		let system = world.borrow_mut().create_particle_system(&B2particleSystemDef::default());
+		assert_eq!(world.borrow().get_particle_systems().len(), 1);
+
+		// A block of fluid collapses into a pool.
+		let mut box_shape = B2polygonShape::default();
+		box_shape.set_as_box(1.5, 1.5);
+		let mut group_def = B2particleGroupDef::default();
+		group_def.position.set(-2.0, 1.6);
+		group_def.shape = Some(Rc::new(RefCell::new(box_shape)));
+		let fluid = system.borrow_mut().create_particle_group(&group_def);
+		let fluid_count: usize = fluid.borrow().get_particle_count();
+		assert!(fluid_count > 300);
+
+		// A rigid and an elastic block fall into it.
+		let mut block_shape = B2polygonShape::default();
+		block_shape.set_as_box(0.4, 0.4);
+		group_def.shape = Some(Rc::new(RefCell::new(block_shape)));
+		group_def.flags = B2particleGroupFlags::RIGID;
+		group_def.position.set(2.0, 3.0);
+		group_def.angle = 0.3;
+		let rigid = system.borrow_mut().create_particle_group(&group_def);
+		group_def.flags = B2particleGroupFlags::ELASTIC;
+		group_def.position.set(0.0, 5.0);
+		let elastic = system.borrow_mut().create_particle_group(&group_def);
+
+		// A particle inside the ground is pushed out.
+		let mut particle_def = B2particleDef::default();
+		particle_def.position.set(3.0, -0.2);
+		system.borrow_mut().create_particle(&particle_def);
+
+		// The distances of the particles of a group to the first one.
+		let shape_of = |group: &B2particleGroupPtr| -> Vec<f32> {
+			let system = system.borrow();
+			let positions: Vec<B2vec2> = (0..system.get_particle_count())
+				.filter(|i| system.get_particle_group(*i).map_or(false, |g| Rc::ptr_eq(&g, group)))
+				.map(|i| system.get_positions()[i])
+				.collect();
+			return positions.iter().map(|p| b2_distance_vec2(*p, positions[0])).collect();
+		};
+		let rigid_shape: Vec<f32> = shape_of(&rigid);
+		let elastic_shape: Vec<f32> = shape_of(&elastic);
+
+		// A light box floats.
+		let body_def = B2bodyDef {
+			body_type: B2bodyType::B2DynamicBody,
+			position: B2vec2::new(-2.0, 4.0),
+			..Default::default()
+		};
+		let floater = B2world::create_body(world.clone(), &body_def);
+		let mut floater_shape = B2polygonShape::default();
+		floater_shape.set_as_box(0.3, 0.3);
+		B2body::create_fixture_by_shape(floater.clone(), Rc::new(RefCell::new(floater_shape)), 0.5);
+
+		for _ in 0..360 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+
+		{
+			let system = system.borrow();
+			for p in system.get_positions() {
+				assert!(p.x > -4.0 && p.x < 4.0 && p.y > 0.0 && p.y < 10.0);
+			}
+			for v in system.get_velocities() {
+				assert!(v.length() < 1.0);
+			}
+		}
+		for (before, after) in rigid_shape.iter().zip(shape_of(&rigid)) {
+			assert!(b2_abs(before - after) < 0.01);
+		}
+		for (before, after) in elastic_shape.iter().zip(shape_of(&elastic)) {
+			assert!(b2_abs(before - after) < 0.1 * before + 0.02);
+		}
+		assert!(floater.borrow().get_position().y > 0.6);
+		assert_eq!(fluid.borrow().get_particle_count(), fluid_count);
+
+		let count: usize = system.borrow().get_particle_count();
+		let rigid_count: usize = rigid.borrow().get_particle_count();
+		system.borrow_mut().destroy_particle_group(rigid);
+		system.borrow_mut().destroy_particle(0);
+		assert_eq!(system.borrow().get_particle_count(), count - rigid_count - 1);
+		assert_eq!(system.borrow().get_particle_groups().len(), 2);
+		world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		assert_eq!(fluid.borrow().get_particle_count() + elastic.borrow().get_particle_count(), count - rigid_count - 2);
+
+		world.borrow_mut().destroy_particle_system(system);
+		assert!(world.borrow().get_particle_systems().is_empty());
+	}
+
+	#[derive(Default)]
+	struct MyFractureListener {
+		// The velocity and center of the broken body, and the pieces.
+		broken: Vec<(B2vec2, f32, B2vec2, Vec<FixturePtr<UserDataTypes>>)>,
+	}
+	impl B2fractureListener<UserDataTypes> for MyFractureListener {
+		fn copy_body_user_data(&mut self, body: BodyPtr<UserDataTypes>) -> Option<i32> {
+			return body.borrow().get_user_data().map(|id| id + 1);
+		}
+		fn fractured(&mut self, fixture: FixturePtr<UserDataTypes>, pieces: &[FixturePtr<UserDataTypes>]) {
+			let body = fixture.borrow().get_body();
+			let body = body.borrow();
+			self.broken.push((
+				body.get_linear_velocity(),
+				body.get_angular_velocity(),
+				body.get_world_center(),
+				pieces.to_vec(),
+			));
+		}
+	}
+
+	#[test]
+	fn fracture()
+	{
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let listener = Rc::new(RefCell::new(MyFractureListener::default()));
+		world.borrow_mut().set_fracture_listener(listener.clone());
+
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+		let mut ground_shape = B2polygonShape::default();
+		ground_shape.set_as_box(20.0, 0.5);
+		B2body::create_fixture_by_shape(ground.clone(), Rc::new(RefCell::new(ground_shape)), 0.0);
+
+		// Two spinning slabs fall on the ground, only the first one is breakable.
+		let mut slabs = Vec::new();
+		for (x, toughness) in [(-4.0, 2.0), (4.0, 0.0)] {
+			let body_def = B2bodyDef {
+				body_type: B2bodyType::B2DynamicBody,
+				position: B2vec2::new(x, 6.0),
+				angle: 0.2,
+				linear_velocity: B2vec2::new(1.0, 0.0),
+				angular_velocity: 1.0,
+				user_data: Some(7),
+				..Default::default()
+			};
+			let slab = B2world::create_body(world.clone(), &body_def);
+			let mut shape = B2polygonShape::default();
+			shape.set_as_box(1.0, 0.25);
+			let fixture_def = B2fixtureDef {
+				shape: Some(Rc::new(RefCell::new(shape))),
+				density: 2.0,
+				friction: 0.3,
+				toughness,
+				user_data: Some(7),
+				..Default::default()
+			};
+			B2body::create_fixture(slab.clone(), &fixture_def);
+			slabs.push(slab);
+		}
+		assert_eq!(world.borrow().get_body_count(), 3);
+
+		for _ in 0..120 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+			if listener.borrow().broken.is_empty() == false {
+				break;
+			}
+		}
+
+		assert_eq!(listener.borrow().broken.len(), 1);
+		let (v, w, center, pieces) = listener.borrow().broken[0].clone();
+		assert!(pieces.len() >= 2);
+		assert_eq!(world.borrow().get_body_count(), 2 + pieces.len());
+		assert_eq!(slabs[0].borrow().get_fixture_list().iter().count(), 0);
+		assert_eq!(slabs[1].borrow().get_fixture_list().iter().count(), 1);
+
+		let mut area: f32 = 0.0;
+		for piece in &pieces {
+			let piece = piece.borrow();
+			assert_eq!(piece.get_user_data(), Some(7));
+			assert_eq!(piece.get_toughness(), 2.0);
+			let shape = piece.get_shape();
+			let polygon = shape.as_polygon().unwrap();
+			assert!(polygon.m_count <= B2_MAX_POLYGON_VERTICES);
+			let mut mass_data = B2massData::default();
+			polygon.compute_mass(&mut mass_data, 1.0);
+			area += mass_data.mass;
+
+			let body = piece.get_body();
+			let body = body.borrow();
+			assert_eq!(body.get_user_data(), Some(8));
+			assert_eq!(body.get_angular_velocity(), w);
+			let expected: B2vec2 = v + b2_cross_scalar_by_vec(w, body.get_world_center() - center);
+			assert!(b2_distance_vec2(body.get_linear_velocity(), expected) < 1e-4);
+		}
+		assert!(b2_abs(area - 1.0) < 0.01);
+
+		// The pieces come to rest without breaking again, the other slab is still whole.
+		for piece in &pieces {
+			piece.borrow_mut().set_toughness(0.0);
+		}
+		for _ in 0..240 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		assert_eq!(world.borrow().get_body_count(), 2 + pieces.len());
+		assert_eq!(listener.borrow().broken.len(), 1);
+		for piece in &pieces {
+			let body = piece.borrow().get_body();
+			assert!(body.borrow().get_position().y > 0.0);
+		}
+	}
+
+	#[cfg(feature="serde_support")]
+	#[test]
+	fn rope_serde()
+	{
+		use box2d_rs::serialize::serialize_b2_world::*;
+
+		let world = B2world::<UserDataTypes>::new(B2vec2::new(0.0, -10.0));
+		let ground = B2world::create_body(world.clone(), &B2bodyDef::default());
+
+		let mut body_def = B2bodyDef::default();
+		body_def.body_type = B2bodyType::B2DynamicBody;
+		body_def.position.set(4.0, 10.0);
+		let weight = B2world::create_body(world.clone(), &body_def);
+		let mut shape = B2polygonShape::default();
+		shape.set_as_box(0.25, 0.25);
+		B2body::create_fixture_by_shape(weight.clone(), Rc::new(RefCell::new(shape)), 4.0);
+
+		let mut def = B2ropeDef::default();
+		for i in 0..9 {
+			def.vertices.push(B2ropeDefVertices {
+				position: B2vec2::new(0.5 * i as f32, 10.0),
+				mass: 0.1,
+			});
+		}
+		def.tuning.bending_model = B2bendingModel::B2XpbdAngleBendingModel;
+		def.radius = 0.1;
+		let rope = world.borrow_mut().create_rope(&def);
+		rope.borrow_mut().attach(0, ground.clone(), B2vec2::new(0.0, 10.0));
+		rope.borrow_mut().attach(8, weight.clone(), B2vec2::new(4.0, 10.0));
+
+		// Save in the middle of the swing.
+		for _ in 0..20 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+
+		let text = serde_json::to_string(&*world.borrow()).unwrap();
+		let loaded = serde_json::from_str::<B2worldDeserializeResult<UserDataTypes>>(&text).unwrap().world;
+		assert_eq!(loaded.borrow().get_ropes().len(), 1);
+		let loaded_rope = loaded.borrow().get_ropes()[0].clone();
+		{
+			let rope = rope.borrow();
+			let rope = rope.get_rope();
+			let loaded_rope = loaded_rope.borrow();
+			let attachments = loaded_rope.get_attachments();
+			let loaded_rope = loaded_rope.get_rope();
+			assert_eq!(loaded_rope.get_vertex_count(), rope.get_vertex_count());
+			for i in 0..rope.get_vertex_count() {
+				assert!(loaded_rope.get_vertex(i) == rope.get_vertex(i));
+				assert!(loaded_rope.get_vertex_velocity(i) == rope.get_vertex_velocity(i));
+			}
+			assert!(loaded_rope.get_radius() == 0.1);
+			assert!(loaded_rope.get_length() == rope.get_length());
+
+			// The attachments refer to the loaded bodies.
+			assert_eq!(attachments.len(), 2);
+			let weight_attachment = attachments.iter().find(|a| a.vertex == 8).unwrap();
+			assert!(weight_attachment.body.borrow().get_type() == B2bodyType::B2DynamicBody);
+			assert!(weight_attachment.body.borrow().get_position() == weight.borrow().get_position());
+		}
+
+		// Both worlds go on the same way.
+		for _ in 0..20 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+			loaded.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+		let rope = rope.borrow();
+		let rope = rope.get_rope();
+		for i in 0..rope.get_vertex_count() {
+			assert!(b2_distance_vec2(loaded_rope.borrow().get_rope().get_vertex(i), rope.get_vertex(i)) < 1.0e-3);
+		}
+
+		// A rope on its own is a document too, without the attachments.
+		let text = serde_json::to_string(rope).unwrap();
+		let copy: B2rope = serde_json::from_str(&text).unwrap();
+		assert_eq!(copy.get_vertex_count(), rope.get_vertex_count());
+		assert!(copy.get_vertex(4) == rope.get_vertex(4));
+
+		let text = serde_json::to_string(&def).unwrap();
+		let def_copy: B2ropeDef = serde_json::from_str(&text).unwrap();
+		assert_eq!(def_copy.vertices.len(), 9);
+		assert!(def_copy.tuning.bending_model == B2bendingModel::B2XpbdAngleBendingModel);
+	}
+
+	// Worlds saved with bincode by the format from before B2_WORLD_FORMAT_VERSION.
+	#[cfg(feature="serde_support")]
+	#[test]
+	fn world_format_v0()
+	{
+		use box2d_rs::serialize::serialize_b2_world::*;
+
+		let load = |bytes: &[u8]| -> B2worldPtr<UserDataTypes> {
+			return bincode::deserialize::<B2worldDeserializeResult<UserDataTypes>>(bytes).unwrap().world;
+		};
+
+		let world = load(include_bytes!("data/world_v0_empty.bincode"));
+		assert_eq!(world.borrow().get_gravity(), B2vec2::new(0.0, -10.0));
+
+		// A static ground and three dynamic bodies.
+		let world = load(include_bytes!("data/world_v0_bodies.bincode"));
+		let bodies: Vec<BodyPtr<UserDataTypes>> = world.borrow().get_body_list().iter().collect();
+		assert_eq!(bodies.len(), 4);
+		let ground = bodies.iter().find(|b| b.borrow().get_user_data() == Some(1)).unwrap();
+		assert_eq!(ground.borrow().get_type(), B2bodyType::B2StaticBody);
+		for id in 2..=4 {
+			let body = bodies.iter().find(|b| b.borrow().get_user_data() == Some(id)).unwrap();
+			let body = body.borrow();
+			assert_eq!(body.get_type(), B2bodyType::B2DynamicBody);
+			assert_eq!(body.get_position().y, 2.0);
+			assert_eq!(body.get_angular_damping(), 0.5);
+			assert_eq!(body.get_gravity_scale(), 0.5);
+			assert_eq!(body.is_bullet(), id == 3);
+			assert!(body.is_fixed_translation_x() == false);
+			assert_eq!(body.get_max_linear_speed(), B2_MAX_FLOAT);
+			assert_eq!(body.get_max_angular_speed(), B2_MAX_FLOAT);
+		}
+
+		// The same bodies with fixtures, the ground has an edge and a chain.
+		let world = load(include_bytes!("data/world_v0_fixtures.bincode"));
+		let find = |id: i32| -> BodyPtr<UserDataTypes> {
+			return world.borrow().get_body_list().iter().find(|b| b.borrow().get_user_data() == Some(id)).unwrap();
+		};
+		let mut types: Vec<B2ShapeType> = find(1).borrow().get_fixture_list().iter().map(|f| f.borrow().get_type()).collect();
+		types.sort_by_key(|t| *t as i32);
+		assert_eq!(types, vec![B2ShapeType::EEdge, B2ShapeType::EChain]);
+		let body = find(2);
+		assert!(b2_abs(body.borrow().get_mass() - (1.0 + 2.0 * B2_PI * 0.25 * 0.25)) < 1.0e-5);
+		let polygon = body.borrow().get_fixture_list().iter().find(|f| f.borrow().get_type() == B2ShapeType::EPolygon).unwrap();
+		assert_eq!(polygon.borrow().get_friction(), 0.3);
+		for id in 1..=4 {
+			for fixture in find(id).borrow().get_fixture_list().iter() {
+				assert!(fixture.borrow().get_one_way_direction().is_none());
+				assert_eq!(fixture.borrow().get_toughness(), 0.0);
+			}
+		}
+		assert_eq!(find(3).borrow().get_fixture_list().iter().count(), 1);
+		assert_eq!(find(4).borrow().get_fixture_list().iter().count(), 1);
+
+		// The same bodies with a revolute, a prismatic, a wheel, a distance and a gear joint.
+		let world = load(include_bytes!("data/world_v0.bincode"));
+		assert_eq!(world.borrow().get_body_count(), 4);
+		assert_eq!(world.borrow().get_joint_count(), 5);
+		for joint in world.borrow().get_joint_list().iter() {
+			match joint.borrow().as_derived() {
+				JointAsDerived::ERevoluteJoint(joint) => {
+					assert!(joint.is_limit_enabled());
+					assert_eq!(joint.get_lower_limit(), -0.5);
+					assert_eq!(joint.get_motor_speed(), 1.0);
+					assert_eq!(joint.get_max_motor_torque(), 10.0);
+					assert_eq!(joint.get_stiffness(), 0.0);
+					assert_eq!(joint.get_max_friction_torque(), 0.0);
+				}
+				JointAsDerived::EPrismaticJoint(joint) => {
+					assert_eq!(joint.get_lower_limit(), -1.0);
+					assert_eq!(joint.get_motor_speed(), 0.25);
+					assert_eq!(joint.get_max_motor_force(), 5.0);
+					assert_eq!(joint.get_max_friction_force(), 0.0);
+				}
+				JointAsDerived::EWheelJoint(joint) => {
+					assert_eq!(joint.get_max_motor_torque(), 20.0);
+					assert_eq!(joint.get_stiffness(), 3.0);
+					assert_eq!(joint.get_max_friction_torque(), 0.0);
+				}
+				JointAsDerived::EGearJoint(joint) => {
+					assert_eq!(joint.get_ratio(), 2.0);
+				}
+				JointAsDerived::EDistanceJoint(_) => {}
+				_ => panic!(),
+			}
+		}
+		for _ in 0..60 {
+			world.borrow_mut().step(1.0 / 60.0, 8, 3);
+		}
+
+		// New saves start with the version, also with a zero gravity.
+		world.borrow_mut().set_gravity(B2vec2::zero());
+		let bytes = bincode::serialize(&*world.borrow()).unwrap();
+		assert_eq!(bytes[..4], B2_WORLD_FORMAT_VERSION.to_le_bytes());
+		assert_eq!(load(&bytes).borrow().get_gravity(), B2vec2::zero());
+	}
+}